@@ -0,0 +1,67 @@
+//! Compares rendering a 1,000-leaf `ErrVec` once via `RenderedError` with rendering the live error once per output.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use errgonomic::{CanonicalKey, ErrVec, ErrorDisplayer, RenderedError, Verbosity, canonical_key, render_error_markdown, render_rendered_error_markdown, writeln_error_with_types};
+use std::hint::black_box;
+use std::io;
+use thiserror::Error;
+
+const LEAVES_LEN: usize = 1_000;
+
+fn sync_all_failed() -> SyncAllFailed {
+    SyncAllFailed {
+        source: (0..LEAVES_LEN)
+            .map(|index| SyncFailed {
+                source: io::Error::other(format!("connection to replica {index} refused")),
+                repo: format!("repo-{index}"),
+            })
+            .collect::<Vec<_>>()
+            .into(),
+    }
+}
+
+/// The outputs of a single failure: the trace, the trace with the names, the Markdown and the key
+type Outputs = (Vec<u8>, Vec<u8>, String, CanonicalKey);
+
+fn single_pass(error: &SyncAllFailed) -> Outputs {
+    let rendered = RenderedError::new(error);
+    let mut trace = Vec::new();
+    let mut detailed = Vec::new();
+    let _ = rendered.writeln(&mut trace, Verbosity::Normal);
+    let _ = rendered.writeln(&mut detailed, Verbosity::Detailed);
+    let markdown = render_rendered_error_markdown(&rendered, None);
+    (trace, detailed, markdown, rendered.key)
+}
+
+fn multi_pass(error: &SyncAllFailed) -> Outputs {
+    let trace = format!("{}\n", ErrorDisplayer(error)).into_bytes();
+    let mut detailed = Vec::new();
+    let _ = writeln_error_with_types(error, &mut detailed);
+    let markdown = render_error_markdown(error);
+    let key = canonical_key(error);
+    (trace, detailed, markdown, key)
+}
+
+fn bench_rendered_error(c: &mut Criterion) {
+    let error = sync_all_failed();
+    let mut group = c.benchmark_group("render_1000_leaf_err_vec");
+    group.bench_function("single_pass", |b| b.iter(|| single_pass(black_box(&error))));
+    group.bench_function("multi_pass", |b| b.iter(|| multi_pass(black_box(&error))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_rendered_error);
+criterion_main!(benches);
+
+#[derive(Error, Debug)]
+#[error("failed to sync {count} repos", count = source.len())]
+struct SyncAllFailed {
+    source: ErrVec<SyncFailed>,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to sync repo {repo}")]
+struct SyncFailed {
+    source: io::Error,
+    repo: String,
+}
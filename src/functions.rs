@@ -1,16 +1,117 @@
+mod error_size_report;
+mod find_resume_state;
+mod get_debug_name;
 mod get_root_error;
+mod next_error_seq;
 mod partition_result;
+mod set_error_detail_level;
+mod write_error_chain_to_fmt;
 
+pub use error_size_report::*;
+pub use find_resume_state::*;
+pub use get_debug_name::*;
 pub use get_root_error::*;
+pub use next_error_seq::*;
 pub use partition_result::*;
+pub use set_error_detail_level::*;
+pub use write_error_chain_to_fmt::*;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "std")] {
+        mod canonical_key;
+        mod collapse_repeated_subtrees;
+        mod fnv1a_hash;
+        mod collect_unique;
+        mod deadline_exceeded;
+        mod display_path;
+        mod dedup_errors;
+        mod decode_lossy_tail;
+        mod error_paths;
+        mod error_tree_shape;
+        mod exit_status_signal;
+        mod extract_retry_after;
+        mod hyperlink_paths;
+        mod merge_canonical;
+        mod notify_error;
+        mod number_chain_levels;
+        mod parse_error_trace;
+        mod read_env_vars;
+        mod render_error_markdown;
+        mod set_global_build_info;
+        mod set_global_path_scrubber;
+        mod terminal_width;
+        mod walk_dir_collect;
+        mod walk_error_tree;
+        mod write_all_buffered;
         mod writeln_error;
         mod write_to_named_temp_file;
         mod exit_result;
+        mod write_error_children_to_formatter;
+        pub use canonical_key::*;
+        pub use collapse_repeated_subtrees::*;
+        pub use fnv1a_hash::*;
+        pub use collect_unique::*;
+        pub use deadline_exceeded::*;
+        pub use display_path::*;
+        pub use dedup_errors::*;
+        pub use decode_lossy_tail::*;
+        pub use error_paths::*;
+        pub use error_tree_shape::*;
+        pub use exit_status_signal::*;
+        pub use extract_retry_after::*;
+        pub use hyperlink_paths::*;
+        pub use merge_canonical::*;
+        pub use notify_error::*;
+        pub use number_chain_levels::*;
+        pub use parse_error_trace::*;
+        pub use read_env_vars::*;
+        pub use render_error_markdown::*;
+        pub use set_global_build_info::*;
+        pub use set_global_path_scrubber::*;
+        pub use terminal_width::*;
+        pub use walk_dir_collect::*;
+        pub use walk_error_tree::*;
+        pub use write_all_buffered::*;
         pub use writeln_error::*;
         pub use write_to_named_temp_file::*;
         pub use exit_result::*;
+        pub use write_error_children_to_formatter::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tokio")] {
+        mod with_request_id;
+        pub use with_request_id::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "json")] {
+        mod writeln_error_jsonl;
+        pub use writeln_error_jsonl::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "testing")] {
+        mod audit_error_messages;
+        pub use audit_error_messages::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tonic")] {
+        mod from_status;
+        mod to_status;
+        pub use from_status::*;
+        pub use to_status::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "fault-injection")] {
+        mod inject_fault;
+        pub use inject_fault::*;
     }
 }
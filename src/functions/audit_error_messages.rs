@@ -0,0 +1,159 @@
+use crate::{MessageFinding, MessageFindingKind, ParsedTree, error_parsed_tree};
+use core::error::Error;
+
+/// Renders the sample errors and returns the fragments of the messages that look like the `Debug` output of a path (see [`MessageFindingKind`]).
+///
+/// The check is a heuristic: a double quote is suspicious if it's followed by the start of a path (`/`, `\`, `~/`, `./`, `../` or a drive letter like `C:\`).
+/// The messages of the whole tree are checked (including the sources and the children of the aggregates).
+/// Call it in a test with the samples of every error type that holds a path, and assert that the result is empty.
+/// Requires the `testing` feature.
+pub fn audit_error_messages<E: Error + 'static>(samples: impl IntoIterator<Item = E>) -> Vec<MessageFinding> {
+    let mut findings = Vec::new();
+    samples.into_iter().enumerate().for_each(|(sample, error)| {
+        let tree = error_parsed_tree(&error);
+        let mut messages = Vec::new();
+        collect_messages(&tree, &mut messages);
+        messages.into_iter().for_each(|message| {
+            findings.extend(audit_message(message).map(|(offset, kind)| MessageFinding {
+                sample,
+                message: message.to_string(),
+                offset,
+                kind,
+            }))
+        })
+    });
+    findings
+}
+
+fn collect_messages<'a>(tree: &'a ParsedTree, messages: &mut Vec<&'a str>) {
+    messages.push(&tree.message);
+    tree.children
+        .iter()
+        .for_each(|child| collect_messages(child, messages));
+    if let Some(source) = &tree.source {
+        collect_messages(source, messages)
+    }
+}
+
+fn audit_message(message: &str) -> impl Iterator<Item = (usize, MessageFindingKind)> + '_ {
+    message
+        .match_indices('"')
+        .filter(|(offset, _)| is_path_start(&message[offset + 1..]))
+        .map(|(offset, _)| match message[..offset].strip_suffix('\\') {
+            Some(_) => (offset - 1, MessageFindingKind::DoubleQuotedPath),
+            None => (offset, MessageFindingKind::DebugQuotedPath),
+        })
+}
+
+fn is_path_start(text: &str) -> bool {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('/' | '\\'), _, _) => true,
+        (Some('~' | '.'), Some('/' | '\\'), _) => true,
+        (Some('.'), Some('.'), Some('/' | '\\')) => true,
+        (Some(drive), Some(':'), Some('/' | '\\')) => drive.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DisplayAsDebug, ErrVec, ItemError, MessageFindingKind, PathBufDisplay, PathIoError, Provenance, WalkDirError, audit_error_messages};
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use std::path::PathBuf;
+    use thiserror::Error;
+
+    #[test]
+    fn must_find_debug_quoted_paths() {
+        let findings = audit_error_messages([
+            PathMessageError::new(format!("failed to read {:?}", PathBuf::from("/etc/app.toml"))),
+            PathMessageError::new(format!("failed to read {:?}", format!("file {:?}", PathBuf::from("/etc/app.toml")))),
+            PathMessageError::new(r#"failed to open "C:\\Users\\me""#),
+            PathMessageError::new(r#"failed to open "~/.config" and "../target""#),
+        ]);
+        let actual: Vec<_> = findings
+            .iter()
+            .map(|finding| (finding.sample, finding.offset, finding.kind))
+            .collect();
+        use MessageFindingKind::*;
+        assert_eq!(
+            actual,
+            vec![
+                (0, 15, DebugQuotedPath),
+                (1, 21, DoubleQuotedPath),
+                (2, 15, DebugQuotedPath),
+                (3, 15, DebugQuotedPath),
+                (3, 31, DebugQuotedPath)
+            ]
+        );
+    }
+
+    #[test]
+    fn must_not_find_false_positives() {
+        let findings = audit_error_messages([
+            PathMessageError::new("failed to read '/etc/app.toml'"),
+            PathMessageError::new(r#"expected "json" or "toml""#),
+            PathMessageError::new(r#"unexpected character "." in the key"#),
+            PathMessageError::new("ratio 1/2 is \"too low\""),
+        ]);
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn must_check_the_whole_tree() {
+        let error = ErrVec::new([ItemError {
+            item: DisplayAsDebug(PathBuf::from("/tmp/a")),
+            source: io::Error::other("denied"),
+        }]);
+        let findings = audit_error_messages([error]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].message, "error occurred for item \"/tmp/a\"");
+    }
+
+    #[test]
+    fn must_not_find_debug_quoted_paths_in_crate_errors() {
+        let path = PathBuf::from("/etc/app.toml");
+        let callback_error = WalkDirError::CallbackFailed {
+            source: ItemError {
+                item: PathBufDisplay::from(path.clone()),
+                source: PathMessageError::new(Provenance::File(PathBufDisplay::from(path.clone())).to_string()),
+            },
+        };
+        let path_io_error = PathIoError::new(&path, io::Error::other("denied"));
+        let read_dir_error = WalkDirError::<PathMessageError>::ReadDirFailed {
+            path: path.clone(),
+            source: io::Error::other("denied"),
+        };
+        let metadata_error = WalkDirError::<PathMessageError>::MetadataUnreadable {
+            path: path.clone(),
+            source: io::Error::other("denied"),
+        };
+        let symlink_error = WalkDirError::<PathMessageError>::SymlinkUnresolvable {
+            path: path.clone(),
+            source: io::Error::other("denied"),
+        };
+        assert_eq!(audit_error_messages([path_io_error]), vec![]);
+        let findings = audit_error_messages([
+            read_dir_error,
+            metadata_error,
+            symlink_error,
+            callback_error,
+        ]);
+        assert_eq!(findings, vec![]);
+    }
+
+    #[derive(Error, Debug)]
+    #[error("{message}")]
+    struct PathMessageError {
+        message: String,
+    }
+
+    impl PathMessageError {
+        fn new(message: impl Into<String>) -> Self {
+            Self {
+                message: message.into(),
+            }
+        }
+    }
+}
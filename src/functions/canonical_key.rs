@@ -0,0 +1,22 @@
+use crate::{AsDynError, CanonicalKey, Fnv1aHasher, ParsedTree, error_parsed_tree};
+use core::fmt::Write;
+use core::hash::Hasher;
+
+/// Returns the stable key of the error: the fingerprint of the whole error tree and the first line of the message.
+///
+/// The fingerprint is computed with FNV-1a (instead of [`DefaultHasher`](std::hash::DefaultHasher)), so it's the same on every machine and with every Rust version.
+pub fn canonical_key<E: AsDynError + ?Sized>(error: &E) -> CanonicalKey {
+    canonical_key_of_tree(&error_parsed_tree(error))
+}
+
+pub(crate) fn canonical_key_of_tree(tree: &ParsedTree) -> CanonicalKey {
+    let mut hasher = Fnv1aHasher::default();
+    // the trace is hashed while it's written, so a large tree doesn't need a large allocation (writing to the hasher never fails)
+    let _ = write!(hasher, "{tree}");
+    let fingerprint = hasher.finish();
+    let first_line = tree.message.lines().next().unwrap_or_default().to_string();
+    CanonicalKey {
+        fingerprint,
+        first_line,
+    }
+}
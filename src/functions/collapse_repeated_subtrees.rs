@@ -0,0 +1,48 @@
+use crate::fnv1a_hash;
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Replaces every repeated child subtree of the rendered error trace with `* (same as error above)`.
+///
+/// A child subtree is a `* ` bullet line together with all following lines that have a deeper indentation.
+/// The subtrees are compared by the FNV-1a hash of their rendered text (without the leading indentation), so identical subtrees at different depths are also collapsed.
+/// Unlike [`DefaultHasher`](std::hash::DefaultHasher), FNV-1a is not seeded, so the output is the same on every run and with every Rust version.
+/// The first occurrence of every subtree is kept as is.
+pub fn collapse_repeated_subtrees(trace: &str) -> String {
+    let lines: Vec<&str> = trace.lines().collect();
+    let mut seen = HashSet::new();
+    let mut output: Vec<Cow<'_, str>> = Vec::with_capacity(lines.len());
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let indent = get_indent(line);
+        if line[indent..].starts_with("* ") {
+            let end = lines[index + 1..]
+                .iter()
+                .position(|line| get_indent(line) <= indent)
+                .map_or(lines.len(), |offset| index + 1 + offset);
+            let hash = fnv1a_hash(lines[index..end].iter().flat_map(|line| {
+                line.get(indent..)
+                    .unwrap_or_default()
+                    .bytes()
+                    .chain([b'\n'])
+            }));
+            if !seen.insert(hash) {
+                output.push(Cow::Owned(format!("{}* (same as error above)", &line[..indent])));
+                index = end;
+                continue;
+            }
+        }
+        output.push(Cow::Borrowed(line));
+        index += 1;
+    }
+    let mut output = output.join("\n");
+    if trace.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
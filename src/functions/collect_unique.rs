@@ -0,0 +1,156 @@
+use crate::{DuplicateKey, DuplicateKeysError};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Collects the key-value pairs into a [`HashMap`], returning all duplicate keys (not just the first one) if the keys are not unique.
+///
+/// See also: [`handle_unique!`](crate::handle_unique)
+pub fn collect_unique<K: Eq + Hash, V>(iter: impl IntoIterator<Item = (K, V)>) -> Result<HashMap<K, V>, DuplicateKeysError<K, V>> {
+    collect_unique_into(iter)
+}
+
+/// Like [`collect_unique`], but collects into a [`BTreeMap`].
+pub fn collect_unique_btree<K: Ord, V>(iter: impl IntoIterator<Item = (K, V)>) -> Result<BTreeMap<K, V>, DuplicateKeysError<K, V>> {
+    collect_unique_into(iter)
+}
+
+/// Like [`collect_unique`], but computes the key of every value with `key_fn`.
+pub fn collect_unique_by<K: Eq + Hash, V>(iter: impl IntoIterator<Item = V>, mut key_fn: impl FnMut(&V) -> K) -> Result<HashMap<K, V>, DuplicateKeysError<K, V>> {
+    collect_unique(iter.into_iter().map(|value| (key_fn(&value), value)))
+}
+
+fn collect_unique_into<K: Eq, V, M: UniqueMap<K, V>>(iter: impl IntoIterator<Item = (K, V)>) -> Result<M, DuplicateKeysError<K, V>> {
+    let mut map = M::default();
+    // the first values stay in the map until the end, so they are moved into the error without cloning
+    let mut duplicates: Vec<(K, V, usize)> = Vec::new();
+    iter.into_iter().for_each(|(key, value)| {
+        if !map.contains(&key) {
+            map.insert(key, value);
+        } else if let Some((_, _, count)) = duplicates
+            .iter_mut()
+            .find(|(duplicate, _, _)| duplicate == &key)
+        {
+            *count += 1;
+        } else {
+            duplicates.push((key, value, 2));
+        }
+    });
+    if duplicates.is_empty() {
+        return Ok(map);
+    }
+    let duplicates = duplicates
+        .into_iter()
+        .filter_map(|(key, second, count)| {
+            map.remove(&key).map(|first| DuplicateKey {
+                key,
+                first,
+                second,
+                count,
+            })
+        })
+        .collect();
+    Err(DuplicateKeysError {
+        duplicates,
+    })
+}
+
+/// The common interface of [`HashMap`] and [`BTreeMap`]
+trait UniqueMap<K, V>: Default {
+    fn contains(&self, key: &K) -> bool;
+    fn insert(&mut self, key: K, value: V);
+    fn remove(&mut self, key: &K) -> Option<V>;
+}
+
+impl<K: Eq + Hash, V> UniqueMap<K, V> for HashMap<K, V> {
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        HashMap::remove(self, key)
+    }
+}
+
+impl<K: Ord, V> UniqueMap<K, V> for BTreeMap<K, V> {
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        BTreeMap::insert(self, key, value);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DuplicateKey, ErrorDisplayer, collect_unique, collect_unique_btree, collect_unique_by};
+    use core::fmt::{Display, Formatter};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_collect_unique_keys() {
+        let map = collect_unique([("a", 1), ("b", 2)]).unwrap();
+        assert_eq!(map.len(), 2);
+        let map = collect_unique_btree([("b", 2), ("a", 1)]).unwrap();
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), [("a", 1), ("b", 2)]);
+    }
+
+    #[test]
+    fn must_return_single_duplicate() {
+        let error = collect_unique_btree([("a", 1), ("b", 2), ("a", 3)]).unwrap_err();
+        assert_eq!(
+            error.duplicates,
+            [DuplicateKey {
+                key: "a",
+                first: 1,
+                second: 3,
+                count: 2,
+            }]
+        );
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- found 1 duplicate key\n  * - found duplicate key \"a\" (2 occurrences)");
+    }
+
+    #[test]
+    fn must_return_all_duplicates() {
+        let users = [
+            ("alice", 1),
+            ("bob", 2),
+            ("bob", 3),
+            ("carol", 4),
+            ("alice", 5),
+            ("bob", 6),
+        ];
+        let error = collect_unique_by(users, |(name, _)| *name).unwrap_err();
+        let duplicates = error
+            .duplicates
+            .iter()
+            .map(|duplicate| (duplicate.key, duplicate.first.1, duplicate.second.1, duplicate.count))
+            .collect::<Vec<_>>();
+        assert_eq!(duplicates, [("bob", 2, 3, 3), ("alice", 1, 5, 2)]);
+        assert_eq!(error.to_string().lines().next(), Some("found 2 duplicate keys"));
+    }
+
+    #[test]
+    fn must_display_keys_via_debug() {
+        let error = collect_unique([(Sku(42), "chair"), (Sku(42), "table")]).unwrap_err();
+        assert_eq!(Sku(42).to_string(), "SKU-0042");
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- found 1 duplicate key\n  * - found duplicate key Sku(42) (2 occurrences)");
+    }
+
+    #[derive(Eq, PartialEq, Hash, Debug)]
+    struct Sku(u32);
+
+    impl Display for Sku {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            write!(f, "SKU-{:04}", self.0)
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::Deadlined;
+use core::error::Error;
+use core::iter::successors;
+
+/// Returns `true` if any error in the chain was recorded after the ambient deadline had passed (so retrying within the same request is pointless).
+///
+/// Only the errors wrapped in [`Deadlined`] are checked (see [`handle_deadline!`](crate::handle_deadline)).
+pub fn deadline_exceeded(error: &(dyn Error + 'static)) -> bool {
+    successors(Some(error), |&error| error.source())
+        .filter_map(|error| error.downcast_ref::<Deadlined>())
+        .any(Deadlined::is_deadline_exceeded)
+}
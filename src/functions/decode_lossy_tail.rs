@@ -0,0 +1,59 @@
+use std::borrow::Cow;
+
+/// The default maximum length (in bytes) of the captured stderr of a failed child process.
+pub const STDERR_MAX_LEN: usize = 4096;
+
+/// The prefix of the cut output
+const ELLIPSIS: &str = "…";
+
+/// Decodes the bytes as UTF-8 (replacing the invalid sequences) and keeps only the last `max_len` bytes (the end of the output usually contains the actual error).
+///
+/// The output is cut at a char boundary, so it may be slightly shorter than `max_len`. If the output is cut, it starts with `…` (which counts towards `max_len`, unless `max_len` is too small to fit it).
+pub fn decode_lossy_tail(bytes: &[u8], max_len: usize) -> String {
+    let string = String::from_utf8_lossy(bytes);
+    if string.len() <= max_len {
+        return string.into_owned();
+    }
+    let (ellipsis, tail_len) = match max_len.checked_sub(ELLIPSIS.len()) {
+        Some(tail_len) => (ELLIPSIS, tail_len),
+        None => ("", max_len),
+    };
+    let start = (string.len() - tail_len..string.len())
+        .find(|&index| string.is_char_boundary(index))
+        .unwrap_or(string.len());
+    let tail = match string {
+        Cow::Borrowed(string) => &string[start..],
+        Cow::Owned(ref string) => &string[start..],
+    };
+    format!("{ellipsis}{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_keep_short_output() {
+        assert_eq!(decode_lossy_tail(b"error: not found\n", 100), "error: not found\n");
+    }
+
+    #[test]
+    fn must_keep_tail_at_char_boundary() {
+        // "…" is 3 bytes and "é" is 2 bytes, so the cut at 3 bytes from the end would split it
+        assert_eq!(decode_lossy_tail("abcééé".as_bytes(), 6), "…é");
+        assert_eq!(decode_lossy_tail(b"abcdefgh", 6), "…fgh");
+    }
+
+    #[test]
+    fn must_fit_ellipsis_into_max_len() {
+        let output = "x".repeat(STDERR_MAX_LEN * 2);
+        assert_eq!(decode_lossy_tail(output.as_bytes(), STDERR_MAX_LEN).len(), STDERR_MAX_LEN);
+        assert_eq!(decode_lossy_tail(b"abcdef", 2), "ef");
+    }
+
+    #[test]
+    fn must_replace_invalid_utf8() {
+        assert_eq!(decode_lossy_tail(b"ab\xffcd", 100), "ab\u{FFFD}cd");
+    }
+}
@@ -0,0 +1,25 @@
+use crate::Deduped;
+use core::error::Error;
+use std::collections::HashMap;
+
+/// Collapses the errors with the same `Display` message into a single [`Deduped`] error with the count (the first error with every message is kept, in the order of the first occurrences).
+///
+/// This is useful when all inputs fail in the same way (e.g. every file is missing because the directory doesn't exist). See also: [`handle_iter_dedup!`](crate::handle_iter_dedup)
+pub fn dedup_errors<E: Error>(errors: impl IntoIterator<Item = E>) -> Vec<Deduped<E>> {
+    let mut indexes: HashMap<String, usize> = HashMap::new();
+    let mut deduped: Vec<Deduped<E>> = Vec::new();
+    errors.into_iter().for_each(|error| {
+        let message = error.to_string();
+        match indexes.get(&message) {
+            Some(&index) => deduped[index].count += 1,
+            None => {
+                indexes.insert(message, deduped.len());
+                deduped.push(Deduped {
+                    error,
+                    count: 1,
+                })
+            }
+        }
+    });
+    deduped
+}
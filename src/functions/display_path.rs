@@ -0,0 +1,30 @@
+use core::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Returns a displayable path in single quotes (e.g. `'/etc/app.toml'`), written lossily without escapes.
+///
+/// Use it for every path in the error messages (e.g. `#[error("failed to read {path}", path = display_path(path))]`), so the paths are quoted in the same way across the whole trace.
+/// Interpolating the `Debug` output of a path (`{path:?}`) produces double quotes and escapes, which are doubled if the message itself is debugged (see [`audit_error_messages`](crate::audit_error_messages)).
+pub fn display_path(path: &Path) -> impl Display + '_ {
+    QuotedPath(path)
+}
+
+struct QuotedPath<'a>(&'a Path);
+
+impl Display for QuotedPath<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "'{}'", self.0.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display_path;
+    use std::path::Path;
+
+    #[test]
+    fn must_quote_without_escapes() {
+        assert_eq!(display_path(Path::new("/etc/app.toml")).to_string(), "'/etc/app.toml'");
+        assert_eq!(display_path(Path::new(r#"C:\Users\"me""#)).to_string(), r#"'C:\Users\"me"'"#);
+    }
+}
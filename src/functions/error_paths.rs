@@ -0,0 +1,50 @@
+use crate::{AsDynError, ErrorRegistry, walk_error_tree};
+use std::path::PathBuf;
+
+/// Returns the paths that the errors in the error tree concern (e.g. the path of a [`PathIoError`](crate::PathIoError)), in the order of the traversal, without duplicates.
+///
+/// The paths are collected via [`ErrorPath`](crate::ErrorPath) while walking the error tree (including the children of the aggregates), so only the errors that are registered via [`ErrorRegistry::register_path`] are included (this is the case for [`PathIoError`](crate::PathIoError) and [`WalkDirError`](crate::WalkDirError)).
+/// The `Display` impls are not called, and the paths are returned as is (including the paths that are not valid UTF-8).
+pub fn error_paths<E: AsDynError + ?Sized>(error: &E) -> Vec<PathBuf> {
+    let mut paths = Vec::<PathBuf>::new();
+    walk_error_tree(error.as_dyn(), &mut |error| {
+        if let Some(path) = ErrorRegistry::path(error).filter(|path| !paths.iter().any(|known| known == path)) {
+            paths.push(path.to_path_buf())
+        }
+    });
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, PathIoError, error_parsed_tree, error_paths};
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use std::path::PathBuf;
+
+    fn not_found() -> io::Error {
+        io::Error::from(io::ErrorKind::NotFound)
+    }
+
+    #[test]
+    fn must_collect_paths_without_changing_messages() {
+        let error = ErrVec::new([
+            PathIoError::new("/etc/app.toml", not_found()),
+            PathIoError::new("/etc/app.d/local.toml", not_found()),
+            PathIoError::new("/etc/app.toml", not_found()),
+        ]);
+        assert_eq!(error_paths(&error), ["/etc/app.toml", "/etc/app.d/local.toml"].map(PathBuf::from));
+        let tree = error_parsed_tree(&error);
+        assert_eq!(tree.children[0].message, "I/O operation failed for '/etc/app.toml'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_keep_non_utf8_paths() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let path = PathBuf::from(OsStr::from_bytes(b"/srv/data/report-\xff.csv"));
+        let error = PathIoError::new(path.clone(), not_found());
+        assert_eq!(error_paths(&error), [path]);
+    }
+}
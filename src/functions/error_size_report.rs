@@ -0,0 +1,76 @@
+use crate::ErrorSizeReport;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::mem::{align_of, size_of};
+
+/// Returns the size and the alignment of the error type.
+///
+/// Use [`error_size_report!`](crate::error_size_report!) to include the payload sizes of the variants.
+pub fn error_size_report<E>() -> ErrorSizeReport {
+    ErrorSizeReport {
+        type_name: type_name::<E>(),
+        size: size_of::<E>(),
+        align: align_of::<E>(),
+        variants: Vec::new(),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{VariantSize, assert_error_size, error_size_report};
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use thiserror::Error;
+
+    assert_error_size!(ReadRecordError, 24);
+
+    #[test]
+    fn must_report_sizes() {
+        let report = error_size_report!(ReadRecordError, {
+            ReadFailed { source: io::Error, code: u64 },
+            RecordInvalid { offset: u32, len: u32 },
+            RecordNotFound,
+        });
+        assert_eq!(report.size, 24);
+        assert_eq!(report.align, 8);
+        assert_eq!(
+            report.variants,
+            vec![
+                VariantSize {
+                    name: "ReadFailed",
+                    payload_size: 16,
+                    non_source_size: 8
+                },
+                VariantSize {
+                    name: "RecordInvalid",
+                    payload_size: 8,
+                    non_source_size: 8
+                },
+                VariantSize {
+                    name: "RecordNotFound",
+                    payload_size: 0,
+                    non_source_size: 0
+                },
+            ]
+        );
+        let over_budget: Vec<_> = report
+            .variants_over_budget(4)
+            .map(|variant| variant.name)
+            .collect();
+        assert_eq!(over_budget, vec!["ReadFailed", "RecordInvalid"]);
+        assert_eq!(report.variants_over_budget(8).count(), 0);
+        let expected = format!("{}: size 24 bytes, align 8 bytes\n  variant         payload  non-source\n  ReadFailed      16       8\n  RecordInvalid   8        8\n  RecordNotFound  0        0", report.type_name);
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[allow(dead_code)]
+    #[derive(Error, Debug)]
+    enum ReadRecordError {
+        #[error("failed to read the record: code {code}")]
+        ReadFailed { source: io::Error, code: u64 },
+        #[error("record is invalid: {offset}..{len}")]
+        RecordInvalid { offset: u32, len: u32 },
+        #[error("record not found")]
+        RecordNotFound,
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{AggregateMessage, AsDynError, ErrorRegistry, ParsedTree, TreeShape, get_debug_name};
+use core::error::Error;
+
+/// Returns the structural summary of the error tree (see [`TreeShape`]).
+///
+/// The children of the aggregates are collected via [`ErrorChildren`](crate::ErrorChildren), so only the aggregates that are registered in the [`ErrorRegistry`] are expanded (this is the case for [`ErrVec`](crate::ErrVec) and the other aggregates in this crate).
+pub fn error_tree_shape<E: AsDynError + ?Sized>(error: &E) -> TreeShape {
+    collect_error_node(None, error.as_dyn()).into_shape()
+}
+
+/// Returns the tree of the messages of the errors (see [`ParsedTree`]).
+///
+/// The message of an aggregate doesn't include its children (they are collected in the same way as in [`error_tree_shape`]).
+pub fn error_parsed_tree<E: AsDynError + ?Sized>(error: &E) -> ParsedTree {
+    collect_error_node(None, error.as_dyn()).into_parsed_tree()
+}
+
+/// Returns both the tree of the messages and the shape of the error, calling every `Display` impl once (see [`RenderedError`](crate::RenderedError)).
+pub fn error_parsed_tree_and_shape<E: AsDynError + ?Sized>(error: &E) -> (ParsedTree, TreeShape) {
+    let node = collect_error_node(None, error.as_dyn());
+    let shape = node.to_shape();
+    (node.into_parsed_tree(), shape)
+}
+
+/// A node of the error tree with both the name and the message (converted into [`TreeShape`] or [`ParsedTree`])
+struct ErrorNode {
+    name: String,
+    message: String,
+    label: Option<String>,
+    children: Vec<ErrorNode>,
+    source: Option<Box<ErrorNode>>,
+}
+
+impl ErrorNode {
+    fn into_shape(self) -> TreeShape {
+        TreeShape {
+            name: self.name,
+            label: self.label,
+            children: self.children.into_iter().map(Self::into_shape).collect(),
+            source: self.source.map(|source| Box::new(source.into_shape())),
+        }
+    }
+
+    fn to_shape(&self) -> TreeShape {
+        TreeShape {
+            name: self.name.clone(),
+            label: self.label.clone(),
+            children: self.children.iter().map(Self::to_shape).collect(),
+            source: self
+                .source
+                .as_deref()
+                .map(|source| Box::new(source.to_shape())),
+        }
+    }
+
+    fn into_parsed_tree(self) -> ParsedTree {
+        ParsedTree {
+            message: self.message,
+            label: self.label,
+            children: self
+                .children
+                .into_iter()
+                .map(Self::into_parsed_tree)
+                .collect(),
+            source: self
+                .source
+                .map(|source| Box::new(source.into_parsed_tree())),
+        }
+    }
+}
+
+/// Collects the node of the error tree (the shared visitor of [`error_tree_shape`], [`error_parsed_tree`] and the functions that are based on them, e.g. [`canonical_key`](crate::canonical_key)).
+///
+/// The visitor walks the `source` chain and the children of the aggregates that are registered in the [`ErrorRegistry`], so every `Display` impl is called once (the messages of the aggregates are written via [`ErrorChildren::fmt_message`](crate::ErrorChildren::fmt_message)).
+/// An aggregate that is formatted inside a `Display` impl (e.g. via `write!(f, "{}", errors)`) stays a part of the message.
+fn collect_error_node(label: Option<String>, error: &(dyn Error + 'static)) -> ErrorNode {
+    // the source is requested before the lookup, because the aggregates register themselves in their `source` impls
+    let source = error.source();
+    let (message, children) = match ErrorRegistry::children(error) {
+        Some(aggregate) => (
+            AggregateMessage(aggregate).to_string(),
+            aggregate
+                .children()
+                .map(|child| collect_error_node(child.label, child.error))
+                .collect(),
+        ),
+        None => (error.to_string(), Vec::new()),
+    };
+    ErrorNode {
+        name: get_debug_name(error),
+        message,
+        label,
+        children,
+        source: source.map(|source| Box::new(collect_error_node(None, source))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, error_parsed_tree, error_tree_shape};
+    use FetchAllError::*;
+    use FetchError::*;
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use thiserror::Error;
+
+    fn fetch_all_failed(messages: [&str; 2]) -> FetchAllError {
+        FetchAllFailed {
+            source: messages
+                .map(|message| RequestFailed {
+                    source: io::Error::other(message.to_string()),
+                    url: "https://example.com".to_string(),
+                })
+                .into_iter()
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+
+    #[test]
+    fn must_ignore_messages() {
+        let actual = error_tree_shape(&fetch_all_failed(["connection refused", "timed out"]));
+        let expected = error_tree_shape(&fetch_all_failed(["Connection refused (os error 111)", "operation timed out"]));
+        assert_eq!(actual, expected);
+        assert_eq!(actual.to_string(), "- FetchAllFailed\n- ErrVec\n  * - RequestFailed\n    - Custom\n  * - RequestFailed\n    - Custom");
+    }
+
+    #[test]
+    fn must_detect_different_nesting() {
+        let nested = error_tree_shape(&fetch_all_failed(["a", "b"]));
+        let flat = error_tree_shape(&RequestFailed {
+            source: io::Error::other("a"),
+            url: "https://example.com".to_string(),
+        });
+        assert_ne!(nested, flat);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn must_assert_same_shape() {
+        let error = fetch_all_failed(["a", "b"]);
+        crate::assert_same_shape!(
+            &error,
+            r#"{
+                "name": "FetchAllFailed",
+                "source": {
+                    "name": "ErrVec",
+                    "children": [
+                        { "name": "RequestFailed", "source": { "name": "Custom" } },
+                        { "name": "RequestFailed", "source": { "name": "Custom" } }
+                    ]
+                }
+            }"#
+        );
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| crate::assert_same_shape!(&error, r#"{ "name": "FetchAllFailed", "source": { "name": "ErrVec" } }"#)));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("error tree shapes are different"), "{message}");
+        assert!(message.contains("\"children\""), "{message}");
+    }
+
+    #[test]
+    fn must_keep_formatted_aggregate_in_message() {
+        let error = SummaryFailed {
+            summary: ErrVec::new([io::Error::other("disk full")]),
+        };
+        let tree = error_parsed_tree(&error);
+        assert_eq!(tree.message, "failed to write the summary: encountered 1 error\n  * - disk full");
+        assert_eq!(tree.children, []);
+        assert_eq!(error_tree_shape(&error).to_string(), "- SummaryFailed");
+    }
+
+    #[derive(Error, Debug)]
+    enum FetchAllError {
+        #[error("failed to fetch {len} urls", len = source.len())]
+        FetchAllFailed { source: ErrVec<FetchError> },
+        #[error("failed to write the summary: {summary}")]
+        SummaryFailed { summary: ErrVec<io::Error> },
+    }
+
+    #[derive(Error, Debug)]
+    enum FetchError {
+        #[error("failed to fetch '{url}'")]
+        RequestFailed { source: io::Error, url: String },
+    }
+}
@@ -1,6 +1,6 @@
-use crate::eprintln_error;
+use crate::{ErrorBin, Verbosity, eprintln_error, eprintln_error_with_verbosity};
 use std::error::Error;
-use std::process::ExitCode;
+use std::process::{ExitCode, Termination};
 
 #[cfg(feature = "futures")]
 use futures::Stream;
@@ -8,17 +8,74 @@ use futures::Stream;
 use futures::StreamExt;
 #[cfg(feature = "futures")]
 use std::pin::pin;
+#[cfg(feature = "tokio")]
+use std::time::Duration;
 
 /// Converts a [`Result`] into an [`ExitCode`], printing a detailed error trace on failure.
-pub fn exit_result<E: Error>(result: Result<ExitCode, E>) -> ExitCode {
-    result.unwrap_or_else(|err| {
-        eprintln_error(&err);
-        ExitCode::FAILURE
-    })
+///
+/// The `Ok` value is converted via [`Termination::report`], so it may be an [`ExitCode`], `()`, or any other [`Termination`] type.
+#[must_use = "the exit code should be returned from `main`"]
+pub fn exit_result<T: Termination, E: Error + 'static>(result: Result<T, E>) -> ExitCode {
+    match result {
+        Ok(value) => value.report(),
+        Err(error) => {
+            eprintln_error(&error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Like [`exit_result`], but also reports the errors from the [`ErrorBin`] (e.g. the [`NotFinalized`](crate::NotFinalized) errors of the dropped [`MustFinalize`](crate::MustFinalize) guards).
+///
+/// Returns [`ExitCode::FAILURE`] if the bin is not empty, even if the result is `Ok`. Call this function after all guards have been dropped.
+#[must_use = "the exit code should be returned from `main`"]
+pub fn exit_result_with_bin<T: Termination, E: Error + 'static>(result: Result<T, E>) -> ExitCode {
+    let exit_code = exit_result(result);
+    match ErrorBin::take() {
+        Some(errors) => {
+            eprintln_error(&errors);
+            ExitCode::FAILURE
+        }
+        None => exit_code,
+    }
+}
+
+/// Like [`exit_result`], but writes the error trace according to the verbosity (e.g. the number of the `-v` flags, see [`Verbosity`]).
+///
+/// The full error report file is written regardless of the verbosity.
+#[must_use = "the exit code should be returned from `main`"]
+pub fn exit_result_verbosity<T: Termination, E: Error + 'static>(result: Result<T, E>, verbosity: impl Into<Verbosity>) -> ExitCode {
+    match result {
+        Ok(value) => value.report(),
+        Err(error) => {
+            eprintln_error_with_verbosity(&error, verbosity.into());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Like [`exit_result`], but runs the `shutdown` future (bounded by `timeout`) before printing the error trace.
+///
+/// This is useful for servers that should close the connections and flush the buffers before exiting.
+/// If the shutdown doesn't complete in time, a note is printed after the error trace.
+#[cfg(feature = "tokio")]
+pub async fn exit_result_graceful<T: Termination, E: Error + 'static, F: Future<Output = ()>>(result: Result<T, E>, shutdown: F, timeout: Duration) -> ExitCode {
+    match result {
+        Ok(value) => value.report(),
+        Err(error) => {
+            let shutdown_result = tokio::time::timeout(timeout, shutdown).await;
+            eprintln_error(&error);
+            if shutdown_result.is_err() {
+                eprintln!("graceful shutdown did not complete within {timeout:?}");
+            }
+            ExitCode::FAILURE
+        }
+    }
 }
 
 /// Converts an [`impl IntoIterator<Item = Result<(), E>>`](IntoIterator) into an [`ExitCode`], printing a detailed error trace on the first failure.
-pub fn exit_iterator_of_results_print_first<E: Error>(iter: impl IntoIterator<Item = Result<(), E>>) -> ExitCode {
+#[must_use = "the exit code should be returned from `main`"]
+pub fn exit_iterator_of_results_print_first<E: Error + 'static>(iter: impl IntoIterator<Item = Result<(), E>>) -> ExitCode {
     for result in iter.into_iter() {
         if let Err(error) = result {
             eprintln_error(&error);
@@ -30,7 +87,7 @@ pub fn exit_iterator_of_results_print_first<E: Error>(iter: impl IntoIterator<It
 
 #[cfg(feature = "futures")]
 /// Converts an [`impl IntoIterator<Item = Result<(), E>>`](IntoIterator) into an [`ExitCode`], printing a detailed error trace on the first failure.
-pub async fn exit_stream_of_results_print_first<E: Error>(stream: impl Stream<Item = Result<(), E>>) -> ExitCode {
+pub async fn exit_stream_of_results_print_first<E: Error + 'static>(stream: impl Stream<Item = Result<(), E>>) -> ExitCode {
     let mut stream = pin!(stream);
     if let Some(Err(error)) = stream.next().await {
         eprintln_error(&error);
@@ -38,3 +95,60 @@ pub async fn exit_stream_of_results_print_first<E: Error>(stream: impl Stream<It
     }
     ExitCode::SUCCESS
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::exit_result;
+    use std::io;
+    use std::process::ExitCode;
+
+    #[test]
+    fn must_report_ok_value() {
+        assert_eq!(exit_result(Ok::<_, io::Error>(ExitCode::from(7))), ExitCode::from(7));
+        assert_eq!(exit_result(Ok::<_, io::Error>(())), ExitCode::SUCCESS);
+        assert_eq!(exit_result(Err::<ExitCode, _>(io::Error::other("failed"))), ExitCode::FAILURE);
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod graceful_tests {
+    use crate::exit_result_graceful;
+    use std::io;
+    use std::process::ExitCode;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn must_run_shutdown_on_error() {
+        let is_shut_down = Arc::new(AtomicBool::new(false));
+        let shutdown = {
+            let is_shut_down = is_shut_down.clone();
+            async move { is_shut_down.store(true, Ordering::SeqCst) }
+        };
+        let exit_code = exit_result_graceful(Err::<ExitCode, _>(io::Error::other("server crashed")), shutdown, Duration::from_secs(10)).await;
+        assert_eq!(exit_code, ExitCode::FAILURE);
+        assert!(is_shut_down.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn must_stop_waiting_for_shutdown_after_timeout() {
+        let is_shut_down = Arc::new(AtomicBool::new(false));
+        let shutdown = {
+            let is_shut_down = is_shut_down.clone();
+            async move {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                is_shut_down.store(true, Ordering::SeqCst)
+            }
+        };
+        let exit_code = exit_result_graceful(Err::<ExitCode, _>(io::Error::other("server crashed")), shutdown, Duration::from_millis(10)).await;
+        assert_eq!(exit_code, ExitCode::FAILURE);
+        assert!(!is_shut_down.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn must_not_run_shutdown_on_success() {
+        let exit_code = exit_result_graceful(Ok::<_, io::Error>(ExitCode::SUCCESS), async { panic!("shutdown must not run") }, Duration::from_secs(10)).await;
+        assert_eq!(exit_code, ExitCode::SUCCESS);
+    }
+}
@@ -0,0 +1,13 @@
+use std::process::ExitStatus;
+
+/// Returns the number of the signal that terminated the process (always [`None`] on non-unix platforms).
+pub fn exit_status_signal(status: &ExitStatus) -> Option<i32> {
+    cfg_if::cfg_if! {
+        if #[cfg(unix)] {
+            std::os::unix::process::ExitStatusExt::signal(status)
+        } else {
+            let _ = status;
+            None
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{ErrorRegistry, walk_error_tree};
+use core::error::Error;
+use std::time::{Duration, SystemTime};
+
+/// Returns the longest delay of the [`RetryAfter`](crate::RetryAfter) errors in the error tree (including the children of [`ErrVec`](crate::ErrVec) and the other aggregates), or `None` if there are no such errors.
+///
+/// The wrappers are found by downcasting every error in the tree (see [`walk_error_tree`]), so the `Display` impls are not called.
+pub fn extract_retry_after(error: &(dyn Error + 'static)) -> Option<Duration> {
+    extract_retry_after_at(error, SystemTime::now())
+}
+
+/// Like [`extract_retry_after`], but computes the remaining delays at `now`.
+pub fn extract_retry_after_at(error: &(dyn Error + 'static), now: SystemTime) -> Option<Duration> {
+    let mut longest = None;
+    walk_error_tree(error, &mut |error| {
+        if let Some(delay) = ErrorRegistry::retry_delay(error) {
+            longest = longest.max(Some(delay.remaining_at(now)))
+        }
+    });
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, RetryAfter, RetryDelay, error_parsed_tree, extract_retry_after, extract_retry_after_at};
+    use std::io;
+    use std::time::{Duration, SystemTime};
+    use thiserror::Error;
+
+    fn rate_limited(delay: RetryDelay) -> SyncError {
+        SyncError::PageFetchFailed {
+            source: FetchError::RequestFailed {
+                source: RetryAfter::new(io::Error::other("429 Too Many Requests"), delay),
+            },
+        }
+    }
+
+    #[test]
+    fn must_find_wrapper_at_depth_3() {
+        let error = JobError::SyncFailed {
+            source: rate_limited(RetryDelay::from_header_value("30").unwrap()),
+        };
+        assert_eq!(extract_retry_after(&error), Some(Duration::from_secs(30)));
+        let error = JobError::SyncFailed {
+            source: SyncError::PageFetchFailed {
+                source: FetchError::RequestFailed {
+                    source: RetryAfter::new(io::Error::other("500 Internal Server Error"), RetryDelay::After(Duration::ZERO)),
+                },
+            },
+        };
+        assert_eq!(extract_retry_after(&error), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn must_return_none_without_wrapper() {
+        let error = io::Error::other("connection reset");
+        assert_eq!(extract_retry_after(&error), None);
+    }
+
+    #[test]
+    fn must_return_max_of_err_vec() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let error = JobError::AllSyncsFailed {
+            source: ErrVec::new([
+                rate_limited(RetryDelay::After(Duration::from_secs(10))),
+                rate_limited(RetryDelay::At(now + Duration::from_secs(90))),
+                rate_limited(RetryDelay::After(Duration::from_secs(45))),
+            ]),
+        };
+        assert_eq!(extract_retry_after_at(&error, now), Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn must_find_direct_children_without_changing_message() {
+        let error = JobError::RequestsRateLimited {
+            source: ErrVec::new([
+                RetryAfter::new(io::Error::other("429 Too Many Requests"), RetryDelay::After(Duration::from_secs(5))),
+                RetryAfter::new(io::Error::other("503 Service Unavailable"), RetryDelay::After(Duration::from_secs(20))),
+            ]),
+        };
+        assert_eq!(extract_retry_after(&error), Some(Duration::from_secs(20)));
+        assert_eq!(
+            error_parsed_tree(&error)
+                .source
+                .map(|source| source.children[1].message.clone()),
+            Some("503 Service Unavailable".to_string())
+        );
+    }
+
+    #[derive(Error, Debug)]
+    enum JobError {
+        #[error("failed to sync")]
+        SyncFailed { source: SyncError },
+        #[error("failed to run all syncs")]
+        AllSyncsFailed { source: ErrVec<SyncError> },
+        #[error("requests were rate-limited")]
+        RequestsRateLimited { source: ErrVec<RetryAfter<io::Error>> },
+    }
+
+    #[derive(Error, Debug)]
+    enum SyncError {
+        #[error("failed to fetch the page")]
+        PageFetchFailed { source: FetchError },
+    }
+
+    #[derive(Error, Debug)]
+    enum FetchError {
+        #[error("request failed")]
+        RequestFailed { source: RetryAfter<io::Error> },
+    }
+}
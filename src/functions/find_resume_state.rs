@@ -0,0 +1,12 @@
+use crate::Resumable;
+use core::error::Error;
+
+/// Returns the resume state of the deepest [`Resumable<E, S>`](Resumable) in the error chain (the error itself is included in the search).
+///
+/// The type of the inner error must be specified because the errors can only be downcast to concrete types: `find_resume_state::<ImportItemError, Checkpoint>(&error)`.
+pub fn find_resume_state<'a, E: Error + 'static, S: core::fmt::Debug + 'static>(error: &'a (dyn Error + 'static)) -> Option<&'a S> {
+    core::iter::successors(Some(error), |&error| error.source())
+        .filter_map(|error| error.downcast_ref::<Resumable<E, S>>())
+        .map(|resumable| &resumable.resume_state)
+        .last()
+}
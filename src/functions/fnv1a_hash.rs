@@ -0,0 +1,23 @@
+use crate::Fnv1aHasher;
+use core::hash::Hasher;
+
+/// Returns the 64-bit FNV-1a hash of the bytes.
+///
+/// Unlike [`DefaultHasher`](std::hash::DefaultHasher), the hash is the same on every machine and with every Rust version.
+pub fn fnv1a_hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    let mut hasher = Fnv1aHasher::default();
+    bytes.into_iter().for_each(|byte| hasher.write_u8(byte));
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fnv1a_hash;
+
+    #[test]
+    fn must_match_reference_values() {
+        assert_eq!(fnv1a_hash(*b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_hash(*b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_hash(*b"foobar"), 0x85944171f73967e8);
+    }
+}
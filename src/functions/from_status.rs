@@ -0,0 +1,53 @@
+use crate::{RemoteError, RemoteGrpcError};
+use serde_json::Value;
+use tonic::Status;
+
+/// Converts the [`Status`] that was received from a gRPC call into an error that can be stored in the `source` field of the variant for the failed call.
+///
+/// If the status has the JSON chain that was attached by [`to_status`](crate::to_status), the remote tree is recovered, so the client-side trace shows the server-side errors under the call.
+/// Otherwise (e.g. the server doesn't use this crate, or the chain was too large), only the code and the message are kept.
+pub fn from_status(status: &Status) -> RemoteGrpcError {
+    let remote = serde_json::from_slice::<Value>(status.details())
+        .ok()
+        .and_then(|chain| remote_error_from_json_chain(&chain, None));
+    RemoteGrpcError {
+        code: status.code(),
+        message: status.message().to_string(),
+        remote,
+    }
+}
+
+/// Converts the JSON chain (see [`to_status`](crate::to_status)) into a remote error with the label, or returns `None` if the JSON is not a non-empty chain
+fn remote_error_from_json_chain(chain: &Value, label: Option<String>) -> Option<RemoteError> {
+    let top = chain
+        .as_array()?
+        .iter()
+        .rev()
+        .try_fold(None, |source: Option<RemoteError>, error| {
+            let message = error.get("message")?.as_str()?.to_string();
+            let children = match error.get("children") {
+                None => Vec::new(),
+                Some(children) => children
+                    .as_array()?
+                    .iter()
+                    .map(|child| {
+                        let label = match child.get("label") {
+                            None => None,
+                            Some(label) => Some(label.as_str()?.to_string()),
+                        };
+                        remote_error_from_json_chain(child.get("chain")?, label)
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+            };
+            Some(Some(RemoteError {
+                message,
+                label: None,
+                children,
+                source: source.map(Box::new),
+            }))
+        })??;
+    Some(RemoteError {
+        label,
+        ..top
+    })
+}
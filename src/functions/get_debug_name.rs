@@ -0,0 +1,56 @@
+use alloc::string::String;
+use core::fmt::{Debug, Write};
+
+/// Returns the leading identifier of the [`Debug`] output (e.g. the variant name of a `#[derive(Debug)]` enum, or the name of a struct).
+///
+/// The formatting is aborted as soon as the identifier ends, so the full Debug output is never built.
+pub fn get_debug_name(value: &(impl Debug + ?Sized)) -> String {
+    let mut writer = NameWriter(String::new());
+    // the error is expected: the writer aborts the formatting after the name
+    let _ = write!(writer, "{value:?}");
+    writer.0
+}
+
+struct NameWriter(String);
+
+impl Write for NameWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        match s.find(|char: char| !(char.is_alphanumeric() || char == '_')) {
+            Some(end) => {
+                self.0.push_str(&s[..end]);
+                Err(core::fmt::Error)
+            }
+            None => {
+                self.0.push_str(s);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::get_debug_name;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { radius: u32 },
+        Square(u32),
+        Empty,
+    }
+
+    #[test]
+    fn must_get_debug_name() {
+        assert_eq!(
+            get_debug_name(&Shape::Circle {
+                radius: 1
+            }),
+            "Circle"
+        );
+        assert_eq!(get_debug_name(&Shape::Square(1)), "Square");
+        assert_eq!(get_debug_name(&Shape::Empty), "Empty");
+        assert_eq!(get_debug_name("quoted"), "");
+    }
+}
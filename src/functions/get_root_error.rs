@@ -8,3 +8,44 @@ pub fn get_root_source(error: &dyn Error) -> &dyn Error {
     }
     source
 }
+
+/// Returns the deepest error in the error chain that satisfies the predicate (e.g. the lowest-level [`io::Error`](std::io::Error)), or `None` if no error satisfies it.
+///
+/// The error itself is included in the search. The errors are `'static`, so the predicate can downcast them (e.g. `|error| error.is::<io::Error>()`).
+pub fn get_root_source_where<'a>(error: &'a (dyn Error + 'static), predicate: impl Fn(&(dyn Error + 'static)) -> bool) -> Option<&'a (dyn Error + 'static)> {
+    core::iter::successors(Some(error), |&error| error.source())
+        .filter(|&error| predicate(error))
+        .last()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::get_root_source_where;
+    use std::io;
+    use thiserror::Error;
+
+    #[test]
+    fn must_return_deepest_matching_source() {
+        let error = LoadError::ReadFailed {
+            source: io::Error::other(ParseError::DecodeFailed {
+                source: io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"),
+            }),
+        };
+        let root = get_root_source_where(&error, |error| error.is::<io::Error>()).unwrap();
+        assert_eq!(root.to_string(), "stream did not contain valid UTF-8");
+        assert!(get_root_source_where(&error, |error| error.is::<LoadError>()).is_some_and(|error| error.is::<LoadError>()));
+        assert!(get_root_source_where(&error, |error| error.is::<std::fmt::Error>()).is_none());
+    }
+
+    #[derive(Error, Debug)]
+    enum LoadError {
+        #[error("failed to read the file")]
+        ReadFailed { source: io::Error },
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseError {
+        #[error("failed to decode the input")]
+        DecodeFailed { source: io::Error },
+    }
+}
@@ -0,0 +1,100 @@
+use core::cmp::Reverse;
+use std::fmt::Write;
+use std::path::{Path, absolute};
+
+/// Wraps every occurrence of the displayed paths in the rendered error trace in an OSC 8 `file://` hyperlink (supported by most modern terminals).
+///
+/// The trace is scanned once, so every occurrence is wrapped at most once, even if the paths repeat or one path is a part of another (the longest path that starts at the position wins).
+/// The relative paths are resolved against the current directory. The text of the trace is not changed (the terminals that don't support OSC 8 ignore the escape sequences).
+pub fn hyperlink_paths(trace: &str, paths: &[&Path]) -> String {
+    let mut links = paths
+        .iter()
+        .map(|path| (path.display().to_string(), *path))
+        .filter(|(text, _)| !text.is_empty())
+        .collect::<Vec<_>>();
+    links.sort_by_key(|(text, _)| Reverse(text.len()));
+    let mut output = String::with_capacity(trace.len());
+    let mut rest = trace;
+    while let Some(char) = rest.chars().next() {
+        match links
+            .iter()
+            .find(|(text, _)| rest.starts_with(text.as_str()))
+        {
+            Some((text, path)) => {
+                // writing to a String never fails
+                let _ = write!(output, "\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\", url = file_url(path));
+                rest = &rest[text.len()..];
+            }
+            None => {
+                output.push(char);
+                rest = &rest[char.len_utf8()..];
+            }
+        }
+    }
+    output
+}
+
+fn file_url(path: &Path) -> String {
+    let path = absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut url = String::from("file://");
+    // the bytes are encoded as is, so the paths that are not valid UTF-8 are linked correctly
+    path.as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .for_each(|&byte| {
+            if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+                url.push(byte as char)
+            } else {
+                // writing to a String never fails
+                let _ = write!(url, "%{byte:02X}");
+            }
+        });
+    url
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::hyperlink_paths;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    #[test]
+    fn must_wrap_paths_in_osc8() {
+        let trace = "- failed to read the config\n- I/O operation failed for '/etc/my app/config.toml'\n- No such file or directory (os error 2)";
+        let actual = hyperlink_paths(trace, &[Path::new("/etc/my app/config.toml")]);
+        let expected = "- failed to read the config\n- I/O operation failed for '\x1b]8;;file:///etc/my%20app/config.toml\x1b\\/etc/my app/config.toml\x1b]8;;\x1b\\'\n- No such file or directory (os error 2)";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn must_wrap_every_occurrence_once() {
+        let trace = "- failed to copy '/data/in' to '/data/in.bak'\n- failed to read '/data/in'";
+        let actual = hyperlink_paths(
+            trace,
+            &[
+                Path::new("/data/in"),
+                Path::new("/data/in.bak"),
+                Path::new("/data/in"),
+            ],
+        );
+        let link = |path: &str| format!("\x1b]8;;file://{path}\x1b\\{path}\x1b]8;;\x1b\\");
+        let expected = format!("- failed to copy '{input}' to '{bak}'\n- failed to read '{input}'", input = link("/data/in"), bak = link("/data/in.bak"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn must_encode_non_utf8_paths() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let path = Path::new(OsStr::from_bytes(b"/srv/report-\xff.csv"));
+        let actual = hyperlink_paths("- I/O operation failed for '/srv/report-\u{FFFD}.csv'", &[path]);
+        let expected = "- I/O operation failed for '\x1b]8;;file:///srv/report-%FF.csv\x1b\\/srv/report-\u{FFFD}.csv\x1b]8;;\x1b\\'";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn must_ignore_missing_paths() {
+        let trace = "- failed to connect";
+        assert_eq!(hyperlink_paths(trace, &[Path::new("/tmp/socket"), Path::new("")]), trace);
+    }
+}
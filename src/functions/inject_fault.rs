@@ -0,0 +1,102 @@
+use core::any::{Any, type_name};
+use core::error::Error;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+
+thread_local! {
+    static FAULTS: RefCell<HashMap<&'static str, VecDeque<Option<Fault>>>> = RefCell::new(HashMap::new());
+}
+
+struct Fault {
+    error: Box<dyn Any>,
+    type_name: &'static str,
+}
+
+/// Arms a fault for the next evaluation of the [`handle_fi!`](crate::handle_fi) sites with the `label`: the expression is not evaluated, and the site returns the error variant with `error` as the source.
+///
+/// The faults are stored per thread, so the tests that run in parallel (on separate threads) don't affect each other, and the faults that were not consumed are dropped at the end of the test.
+/// The faults don't apply to the sites that are evaluated on other threads (e.g. in spawned tasks).
+pub fn inject_fault<E: Error + 'static>(label: &'static str, error: E) {
+    inject_faults(label, [Some(error)])
+}
+
+/// Arms the faults for the next evaluations of the [`handle_fi!`](crate::handle_fi) sites with the `label`, one item per evaluation: `Some(error)` fails the evaluation, `None` lets it run normally.
+///
+/// For example, `inject_faults("write_chunk", [None, Some(io::Error::other("disk full"))])` fails the second write only.
+/// The faults are appended to the faults that are already armed for the `label`.
+pub fn inject_faults<E: Error + 'static>(label: &'static str, faults: impl IntoIterator<Item = Option<E>>) {
+    let faults = faults.into_iter().map(|fault| {
+        fault.map(|error| Fault {
+            error: Box::new(error),
+            type_name: type_name::<E>(),
+        })
+    });
+    FAULTS.with_borrow_mut(|registry| registry.entry(label).or_default().extend(faults))
+}
+
+/// Returns the number of the evaluations that are still armed for the `label` on the current thread (including the ones that run normally).
+///
+/// This is useful for checking that the test has actually reached the site.
+pub fn pending_faults(label: &'static str) -> usize {
+    FAULTS.with_borrow(|registry| registry.get(label).map_or(0, VecDeque::len))
+}
+
+/// Disarms all faults on the current thread.
+pub fn clear_faults() {
+    FAULTS.with_borrow_mut(HashMap::clear)
+}
+
+/// Consumes the next armed evaluation of the `label`, returning the injected error if it must fail.
+///
+/// Panics if the injected error doesn't have the error type of the site.
+#[doc(hidden)]
+pub fn take_fault<E: 'static>(label: &'static str) -> Option<E> {
+    let fault = FAULTS.with_borrow_mut(|registry| {
+        let faults = registry.get_mut(label)?;
+        let fault = faults.pop_front();
+        if faults.is_empty() {
+            registry.remove(label);
+        }
+        fault.flatten()
+    })?;
+    match fault.error.downcast::<E>() {
+        Ok(error) => Some(*error),
+        Err(_) => panic!("the fault injected for '{label}' has the type {injected}, but the site expects {expected}", injected = fault.type_name, expected = type_name::<E>()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{clear_faults, inject_fault, inject_faults, pending_faults, take_fault};
+    use std::io;
+
+    #[test]
+    fn must_consume_faults_in_order() {
+        inject_faults("write_chunk", [None, Some(io::Error::other("disk full"))]);
+        inject_fault("write_chunk", io::Error::other("disk is read-only"));
+        assert_eq!(pending_faults("write_chunk"), 3);
+        assert!(take_fault::<io::Error>("write_chunk").is_none());
+        assert_eq!(take_fault::<io::Error>("write_chunk").unwrap().to_string(), "disk full");
+        assert_eq!(take_fault::<io::Error>("write_chunk").unwrap().to_string(), "disk is read-only");
+        assert!(take_fault::<io::Error>("write_chunk").is_none());
+        assert_eq!(pending_faults("write_chunk"), 0);
+    }
+
+    #[test]
+    fn must_isolate_threads() {
+        inject_fault("read_chunk", io::Error::other("timed out"));
+        let pending = std::thread::spawn(|| pending_faults("read_chunk"))
+            .join()
+            .unwrap();
+        assert_eq!(pending, 0);
+        clear_faults();
+        assert_eq!(pending_faults("read_chunk"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the fault injected for 'parse' has the type std::io::error::Error")]
+    fn must_panic_on_type_mismatch() {
+        inject_fault("parse", io::Error::other("disk full"));
+        let _ = take_fault::<core::num::ParseIntError>("parse");
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Deduped, ErrVec, canonical_key};
+use core::cmp::Ordering;
+use core::error::Error;
+
+/// Merges two aggregates that are sorted by the [`canonical_key`] of the inner errors (see [`ErrVec::into_canonical`]), summing the counts of the identical errors.
+///
+/// The result is sorted too, so the merge can be repeated. The rendered result doesn't depend on the order of the merges (as long as the identical errors are rendered identically).
+pub fn merge_canonical<E: Error + 'static>(a: ErrVec<Deduped<E>>, b: ErrVec<Deduped<E>>) -> ErrVec<Deduped<E>> {
+    let mut a =
+        a.0.into_iter()
+            .map(|deduped| (canonical_key(&deduped.error), deduped))
+            .peekable();
+    let mut b =
+        b.0.into_iter()
+            .map(|deduped| (canonical_key(&deduped.error), deduped))
+            .peekable();
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    loop {
+        let ordering = match (a.peek(), b.peek()) {
+            (Some((key_a, _)), Some((key_b, _))) => key_a.cmp(key_b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        let next = match ordering {
+            Ordering::Less => a.next(),
+            Ordering::Greater => b.next(),
+            Ordering::Equal => a
+                .next()
+                .zip(b.next())
+                .map(|((key, mut deduped), (_, other))| {
+                    deduped.count += other.count;
+                    (key, deduped)
+                }),
+        };
+        merged.extend(next.map(|(_, deduped)| deduped));
+    }
+    ErrVec(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer, RemoteError, error_parsed_tree, merge_canonical};
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_render_the_same_report_regardless_of_merge_order() {
+        let worker = |ids: &[u16]| ErrVec::new(ids.iter().map(|&id| fetch_failed(id))).into_canonical();
+        let a = || worker(&[3, 1, 2, 1]);
+        let b = || worker(&[2, 4]);
+        let c = || worker(&[1, 4, 5]);
+        let render = |errors: ErrVec<_>| ErrorDisplayer(&errors).to_string();
+        let expected = render(merge_canonical(merge_canonical(a(), b()), c()));
+        assert_eq!(render(merge_canonical(merge_canonical(c(), b()), a())), expected);
+        assert_eq!(render(merge_canonical(a(), merge_canonical(c(), b()))), expected);
+        assert_eq!(render(merge_canonical(merge_canonical(b(), a()), c())), expected);
+        let merged = merge_canonical(merge_canonical(a(), b()), c());
+        assert_eq!(merged.len(), 5);
+        assert_eq!(merged.iter().map(|deduped| deduped.count).sum::<usize>(), 9);
+        let remote = RemoteError::from(error_parsed_tree(&merged));
+        assert!(
+            remote
+                .children
+                .iter()
+                .any(|child| child.message == "failed to fetch item 1 (occurred 3 times)")
+        );
+    }
+
+    fn fetch_failed(id: u16) -> FetchItemError {
+        FetchItemError::RequestFailed {
+            source: HttpError::Timeout,
+            id,
+        }
+    }
+
+    #[derive(Error, Debug)]
+    enum FetchItemError {
+        #[error("failed to fetch item {id}")]
+        RequestFailed { source: HttpError, id: u16 },
+    }
+
+    #[derive(Error, Debug)]
+    enum HttpError {
+        #[error("request timed out")]
+        Timeout,
+    }
+}
@@ -0,0 +1,10 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the next number of the global monotonically increasing sequence (used by [`handle_seq!`](crate::handle_seq)).
+///
+/// The numbers are unique across threads, so the errors can be sorted by the order of their occurrence.
+pub fn next_error_seq() -> u64 {
+    NEXT_SEQ.fetch_add(1, Ordering::Relaxed)
+}
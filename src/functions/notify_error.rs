@@ -0,0 +1,36 @@
+use crate::{AsDynError, Notification, NotificationSink, Severity, canonical_key, error_parsed_tree};
+
+/// Converts the error into a [`Notification`] and sends it to the sink.
+///
+/// The severity is read from the marker at the beginning of the message of the top error (see [`WithSeverity`](crate::WithSeverity)), and defaults to [`Severity::Error`].
+pub fn notify_error<E: AsDynError + ?Sized>(error: &E, sink: &mut dyn NotificationSink) {
+    sink.notify(error_notification(error))
+}
+
+/// Returns the [`Notification`] for the error (see [`notify_error`]).
+pub fn error_notification<E: AsDynError + ?Sized>(error: &E) -> Notification {
+    let tree = error_parsed_tree(error);
+    let first_line = |message: &str| message.lines().next().unwrap_or_default().to_string();
+    let title = first_line(&tree.message);
+    let (severity, title) = [Severity::Warning, Severity::Error, Severity::Fatal]
+        .into_iter()
+        .find_map(|severity| {
+            title
+                .strip_prefix(severity.marker())
+                .and_then(|rest| rest.strip_prefix(' '))
+                .map(|rest| (severity, rest.to_string()))
+        })
+        .unwrap_or((Severity::Error, title));
+    let body = match tree.leaves().as_slice() {
+        [leaf] if core::ptr::eq(*leaf, &tree) => None,
+        [leaf, ..] => Some(first_line(&leaf.message)),
+        [] => None,
+    };
+    Notification {
+        title,
+        body,
+        severity,
+        count: 1,
+        key: canonical_key(error),
+    }
+}
@@ -0,0 +1,110 @@
+/// Prefixes every level of the rendered error trace with its number (`1. top`, `2. mid`, `3. root`).
+///
+/// The levels of the child chains (the `* ` bullets) are numbered with the path to the child: the levels of the second child of level 3 are numbered `3.2.1`, `3.2.2`, etc.
+/// The labeled children (`* [shard 3] - ...`) are numbered the same way, and the label is kept after the number.
+/// The continuation lines of the multi-line messages and the `* (same as error above)` lines are kept as is.
+pub fn number_chain_levels(trace: &str) -> String {
+    let mut chains = vec![Chain::default()];
+    let mut output = trace
+        .lines()
+        .map(|line| {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            let (prefix, rest) = line.split_at(indent);
+            if let Some((label, message)) = strip_child_bullet(rest) {
+                chains.truncate(
+                    chains
+                        .iter()
+                        .rposition(|chain| chain.levels_indent + 2 <= indent)
+                        .unwrap_or_default()
+                        + 1,
+                );
+                let Some(parent) = chains.last_mut() else {
+                    return line.to_string();
+                };
+                parent.children_count += 1;
+                let mut path = parent.path.clone();
+                path.extend([parent.levels_count, parent.children_count]);
+                let mut chain = Chain {
+                    path,
+                    levels_indent: indent + 2,
+                    levels_count: 0,
+                    children_count: 0,
+                };
+                let number = chain.next_level();
+                chains.push(chain);
+                match label {
+                    Some(label) => format!("{prefix}{number} [{label}] {message}"),
+                    None => format!("{prefix}{number} {message}"),
+                }
+            } else if let Some(message) = rest.strip_prefix("- ") {
+                chains.truncate(
+                    chains
+                        .iter()
+                        .rposition(|chain| chain.levels_indent <= indent)
+                        .unwrap_or_default()
+                        + 1,
+                );
+                match chains.last_mut() {
+                    Some(chain) if chain.levels_indent == indent => {
+                        let number = chain.next_level();
+                        format!("{prefix}{number} {message}")
+                    }
+                    _ => line.to_string(),
+                }
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if trace.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+/// Splits the first line of a child chain (`* - message` or `* [label] - message`) into the label and the message
+fn strip_child_bullet(line: &str) -> Option<(Option<&str>, &str)> {
+    let rest = line.strip_prefix("* ")?;
+    if let Some(message) = rest.strip_prefix("- ") {
+        Some((None, message))
+    } else {
+        let (label, message) = rest.strip_prefix('[')?.split_once("] - ")?;
+        Some((Some(label), message))
+    }
+}
+
+#[derive(Default, Debug)]
+struct Chain {
+    path: Vec<usize>,
+    levels_indent: usize,
+    levels_count: usize,
+    children_count: usize,
+}
+
+impl Chain {
+    fn next_level(&mut self) -> String {
+        self.levels_count += 1;
+        self.children_count = 0;
+        self.path
+            .iter()
+            .chain([&self.levels_count])
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+            + "."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number_chain_levels;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_number_labeled_children() {
+        let trace = "- failed to sync shards\n- encountered errors in 2 shards\n  * [shard 3] - connection refused\n  * [shard 7] - failed to sync 2 replicas\n    - encountered 2 errors\n      * - timeout\n      * - timeout\n";
+        let expected = "1. failed to sync shards\n2. encountered errors in 2 shards\n  2.1.1. [shard 3] connection refused\n  2.2.1. [shard 7] failed to sync 2 replicas\n    2.2.2. encountered 2 errors\n      2.2.2.1.1. timeout\n      2.2.2.2.1. timeout\n";
+        assert_eq!(number_chain_levels(trace), expected);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::ParsedTree;
+use thiserror::Error;
+
+/// The first line of the strict trace format v1 (see [`ParsedTree`] for the description of the format).
+pub const ERROR_TRACE_HEADER: &str = "errgonomic-trace v1";
+
+/// Parses the error trace in the strict format v1 (as written by [`writeln_error_strict`](crate::writeln_error_strict)).
+///
+/// This is useful for the tools that reconstruct the error trees from the captured stderr.
+/// The result is equal to the output of [`error_parsed_tree`](crate::error_parsed_tree) for the error that was written.
+pub fn parse_error_trace(text: &str) -> Result<ParsedTree, ParseTraceError> {
+    use ParseTraceError::*;
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    if header != ERROR_TRACE_HEADER {
+        return Err(HeaderInvalid {
+            line: header.to_string(),
+        });
+    }
+    let lines = lines
+        .enumerate()
+        .map(|(index, line)| TraceLine::parse(index + 2, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut position = 0;
+    let tree = parse_node(&lines, &mut position, 0, None, '-')?;
+    match lines.get(position) {
+        Some(line) => Err(line.unexpected()),
+        None => Ok(tree),
+    }
+}
+
+fn parse_node(lines: &[TraceLine<'_>], position: &mut usize, depth: usize, label: Option<String>, marker: char) -> Result<ParsedTree, ParseTraceError> {
+    let line = match lines.get(*position) {
+        Some(line) if line.is(marker, depth) => line,
+        Some(line) => return Err(line.unexpected()),
+        None => return Err(ParseTraceError::TraceEmpty),
+    };
+    *position += 1;
+    let mut message = line.text.clone();
+    while let Some(line) = lines.get(*position).filter(|line| line.is('|', depth)) {
+        message.push('\n');
+        message.push_str(&line.text);
+        *position += 1;
+    }
+    let mut children = Vec::new();
+    while let Some(line) = lines
+        .get(*position)
+        .filter(|line| line.is('@', depth + 1) || line.is('*', depth + 1))
+    {
+        let label = if line.marker == '@' {
+            *position += 1;
+            Some(line.text.clone())
+        } else {
+            None
+        };
+        children.push(parse_node(lines, position, depth + 1, label, '*')?);
+    }
+    let source = match lines.get(*position) {
+        Some(line) if line.is('-', depth) => Some(Box::new(parse_node(lines, position, depth, None, '-')?)),
+        _ => None,
+    };
+    Ok(ParsedTree {
+        message,
+        label,
+        children,
+        source,
+    })
+}
+
+struct TraceLine<'a> {
+    number: usize,
+    marker: char,
+    depth: usize,
+    text: String,
+    line: &'a str,
+}
+
+impl<'a> TraceLine<'a> {
+    fn parse(number: usize, line: &'a str) -> Result<Self, ParseTraceError> {
+        let invalid = || ParseTraceError::LineInvalid {
+            number,
+            line: line.to_string(),
+        };
+        let rest = line.trim_start_matches(' ');
+        let mut chars = rest.chars();
+        let marker = chars
+            .next()
+            .filter(|marker| "-*@|".contains(*marker))
+            .ok_or_else(invalid)?;
+        let rest = chars.as_str().strip_prefix('[').ok_or_else(invalid)?;
+        let (depth, rest) = rest.split_once(']').ok_or_else(invalid)?;
+        let depth = depth.parse().map_err(|_| invalid())?;
+        let text = match rest.strip_prefix(' ') {
+            Some(text) => text,
+            None if rest.is_empty() => rest,
+            None => return Err(invalid()),
+        };
+        let text = unescape(text).ok_or_else(invalid)?;
+        Ok(Self {
+            number,
+            marker,
+            depth,
+            text,
+            line,
+        })
+    }
+
+    fn is(&self, marker: char, depth: usize) -> bool {
+        self.marker == marker && self.depth == depth
+    }
+
+    fn unexpected(&self) -> ParseTraceError {
+        ParseTraceError::LineUnexpected {
+            number: self.number,
+            line: self.line.to_string(),
+        }
+    }
+}
+
+/// Reverses the escaping of the `\`, `\r` and `\n` characters (see [`ParsedTree`]), returning `None` for an unknown escape sequence
+fn unescape(text: &str) -> Option<String> {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            output.push(char);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => output.push('\\'),
+            'r' => output.push('\r'),
+            'n' => output.push('\n'),
+            _ => return None,
+        }
+    }
+    Some(output)
+}
+
+/// Errors returned by [`parse_error_trace`].
+#[derive(Error, Debug)]
+pub enum ParseTraceError {
+    #[error("trace header must be '{ERROR_TRACE_HEADER}', found '{line}'")]
+    HeaderInvalid { line: String },
+    #[error("line {number} is not a valid trace line: '{line}'")]
+    LineInvalid { number: usize, line: String },
+    #[error("line {number} is not expected at this position: '{line}'")]
+    LineUnexpected { number: usize, line: String },
+    #[error("trace doesn't contain any errors")]
+    TraceEmpty,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParseTraceError, ParsedTree, parse_error_trace};
+    use pretty_assertions::assert_eq;
+
+    fn leaf(message: &str) -> ParsedTree {
+        ParsedTree {
+            message: message.to_string(),
+            label: None,
+            children: vec![],
+            source: None,
+        }
+    }
+
+    #[test]
+    fn must_parse_ambiguous_messages() {
+        let tree = ParsedTree {
+            message: "- starts with a dash\n* and a star\n|[0] and a marker".to_string(),
+            label: None,
+            children: vec![
+                ParsedTree {
+                    label: Some("shard ]3[".to_string()),
+                    source: Some(Box::new(leaf(""))),
+                    ..leaf("first")
+                },
+                leaf("second"),
+            ],
+            source: Some(Box::new(leaf("root"))),
+        };
+        let text = format!("errgonomic-trace v1\n{tree}\n");
+        assert_eq!(parse_error_trace(&text).unwrap(), tree);
+    }
+
+    #[test]
+    fn must_escape_line_breaks() {
+        let tree = ParsedTree {
+            message: "line 1\r\nline 2 ends with a backslash \\".to_string(),
+            label: None,
+            children: vec![ParsedTree {
+                label: Some("C:\\data\nshard 3\r".to_string()),
+                ..leaf("first")
+            }],
+            source: None,
+        };
+        let text = format!("errgonomic-trace v1\n{tree}\n");
+        assert_eq!(text, "errgonomic-trace v1\n-[0] line 1\\r\n|[0] line 2 ends with a backslash \\\\\n  @[1] C:\\\\data\\nshard 3\\r\n  *[1] first\n");
+        assert_eq!(parse_error_trace(&text).unwrap(), tree);
+        assert_eq!(parse_error_trace(&text.replace('\n', "\r\n")).unwrap(), tree);
+        assert!(matches!(
+            parse_error_trace("errgonomic-trace v1\n-[0] a\\tb"),
+            Err(ParseTraceError::LineInvalid {
+                number: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn must_reject_invalid_traces() {
+        assert!(matches!(parse_error_trace(""), Err(ParseTraceError::HeaderInvalid { .. })));
+        assert!(matches!(parse_error_trace("errgonomic-trace v1"), Err(ParseTraceError::TraceEmpty)));
+        assert!(matches!(
+            parse_error_trace("errgonomic-trace v1\n- a"),
+            Err(ParseTraceError::LineInvalid {
+                number: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_error_trace("errgonomic-trace v1\n-[0] a\n*[2] b"),
+            Err(ParseTraceError::LineUnexpected {
+                number: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_error_trace("errgonomic-trace v1\n-[0] a\n  @[1] label\n-[0] b"),
+            Err(ParseTraceError::LineUnexpected {
+                number: 4,
+                ..
+            })
+        ));
+    }
+}
@@ -1,14 +1,28 @@
 use alloc::vec::Vec;
 
+/// The maximum number of the `Ok` values that [`partition_result`] pre-allocates for (the pre-allocated vec is wasted if an error appears, because the `Ok` values are dropped).
+pub const PARTITION_RESULT_PREALLOCATION_MAX_LEN: usize = 64;
+
 /// Collects `Ok` values unless at least one `Err` is encountered.
 ///
 /// This is optimized for `handle_iter!`: once an error appears, previously
 /// collected `Ok` values are dropped and further `Ok` values are ignored.
+/// The errors vec is shrunk to fit, because it is usually stored in a long-lived error value.
 #[doc(hidden)]
 pub fn partition_result<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Result<Vec<T>, Vec<E>> {
+    partition_result_with_total(results).map_err(|(errors, _total)| errors)
+}
+
+/// Like [`partition_result`], but also returns the total number of the processed results on failure.
+#[doc(hidden)]
+pub fn partition_result_with_total<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Result<Vec<T>, (Vec<E>, usize)> {
     let iter = results.into_iter();
-    let (lower, _) = iter.size_hint();
-    let (oks, errors) = iter.fold((Vec::with_capacity(lower), Vec::new()), |(mut oks, mut errors), result| {
+    let (lower, upper) = iter.size_hint();
+    if upper == Some(0) {
+        return Ok(Vec::new());
+    }
+    let capacity = lower.min(PARTITION_RESULT_PREALLOCATION_MAX_LEN);
+    let (oks, mut errors, total) = iter.fold((Vec::with_capacity(capacity), Vec::new(), 0), |(mut oks, mut errors, total), result| {
         match result {
             Ok(value) => {
                 if errors.is_empty() {
@@ -22,8 +36,99 @@ pub fn partition_result<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -
                 errors.push(error);
             }
         }
-        (oks, errors)
+        (oks, errors, total + 1)
     });
 
-    if errors.is_empty() { Ok(oks) } else { Err(errors) }
+    if errors.is_empty() {
+        Ok(oks)
+    } else {
+        errors.shrink_to_fit();
+        Err((errors, total))
+    }
+}
+
+/// Like [`partition_result`], but stops at the first `Err` (the rest of the iterator is not consumed).
+///
+/// This is optimized for [`handle_iter_first!`](crate::handle_iter_first): the errors vec contains exactly one error.
+#[doc(hidden)]
+pub fn partition_result_fail_fast<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Result<Vec<T>, Vec<E>> {
+    partition_result_fail_fast_with_total(results).map_err(|(errors, _total)| errors)
+}
+
+/// Like [`partition_result_fail_fast`], but also returns the number of the processed results on failure (including the failed one).
+#[doc(hidden)]
+pub fn partition_result_fail_fast_with_total<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Result<Vec<T>, (Vec<E>, usize)> {
+    let iter = results.into_iter();
+    let capacity = iter
+        .size_hint()
+        .0
+        .min(PARTITION_RESULT_PREALLOCATION_MAX_LEN);
+    let mut oks = Vec::with_capacity(capacity);
+    for (index, result) in iter.enumerate() {
+        match result {
+            Ok(value) => oks.push(value),
+            Err(error) => return Err((alloc::vec![error], index + 1)),
+        }
+    }
+    Ok(oks)
+}
+
+/// Collects the errors from the results of the unit type (without collecting the `Ok` values), returning the errors and the total number of the processed results on failure.
+///
+/// The errors vec is allocated only when the first error appears. This is optimized for [`handle_iter_unit!`](crate::handle_iter_unit).
+#[doc(hidden)]
+pub fn partition_unit_result_with_total<E>(results: impl IntoIterator<Item = Result<(), E>>) -> Result<(), (Vec<E>, usize)> {
+    let (mut errors, total) = results
+        .into_iter()
+        .fold((Vec::new(), 0), |(mut errors, total), result| {
+            if let Err(error) = result {
+                errors.push(error);
+            }
+            (errors, total + 1)
+        });
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.shrink_to_fit();
+        Err((errors, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{partition_result, partition_result_fail_fast, partition_result_fail_fast_with_total, partition_result_with_total, partition_unit_result_with_total};
+
+    #[test]
+    fn must_shrink_errors_to_fit() {
+        let results = (0..100).map(|number| if number % 3 == 0 { Err(number) } else { Ok(number) });
+        let errors = partition_result(results).unwrap_err();
+        assert_eq!(errors.len(), 34);
+        assert_eq!(errors.capacity(), errors.len());
+    }
+
+    #[test]
+    fn must_count_processed_results() {
+        let results = (0..100).map(|number| if number % 3 == 0 { Err(number) } else { Ok(number) });
+        let (errors, total) = partition_result_with_total(results.take(10)).unwrap_err();
+        assert_eq!(errors, alloc::vec![0, 3, 6, 9]);
+        assert_eq!(total, 10);
+    }
+
+    #[test]
+    fn must_collect_unit_errors() {
+        assert_eq!(partition_unit_result_with_total((0..10).map(|_| Ok::<_, u32>(()))), Ok(()));
+        let results = (0..10).map(|number| if number % 4 == 0 { Err(number) } else { Ok(()) });
+        assert_eq!(partition_unit_result_with_total(results), Err((alloc::vec![0, 4, 8], 10)));
+    }
+
+    #[test]
+    fn must_stop_at_first_error() {
+        let mut consumed = 0;
+        let results = (0..1_000_000)
+            .inspect(|_| consumed += 1)
+            .map(|number| if number % 3 == 2 { Err(number) } else { Ok(number) });
+        assert_eq!(partition_result_fail_fast_with_total(results), Err((alloc::vec![2], 3)));
+        assert_eq!(consumed, 3);
+        assert_eq!(partition_result_fail_fast((0..5).map(Ok::<_, u32>)), Ok(alloc::vec![0, 1, 2, 3, 4]));
+    }
 }
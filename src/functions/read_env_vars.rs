@@ -0,0 +1,65 @@
+use crate::OsStrDisplay;
+use std::env::var_os;
+use std::ffi::{OsStr, OsString};
+
+/// Returns the values of the named environment variables (the non-UTF-8 values are kept losslessly, see [`OsStrDisplay`]).
+///
+/// See also: [`handle_env!`](crate::handle_env)
+pub fn read_env_vars(names: &[&str]) -> Vec<(String, Option<OsStrDisplay>)> {
+    read_env_vars_with(names, |name| var_os(name))
+}
+
+/// Like [`read_env_vars`], but with the injected lookup of the variables (so that the tests don't have to modify the environment)
+fn read_env_vars_with(names: &[&str], lookup: impl Fn(&str) -> Option<OsString>) -> Vec<(String, Option<OsStrDisplay>)> {
+    names
+        .iter()
+        .map(|name| (name.to_string(), lookup(name).map(OsStrDisplay::from)))
+        .collect()
+}
+
+/// Returns the value of the environment variable if it is present and valid UTF-8, or the raw value otherwise (`None` if the variable is not present).
+///
+/// See also: [`handle_os_var!`](crate::handle_os_var)
+pub fn read_os_var(name: impl AsRef<OsStr>) -> Result<String, Option<OsStrDisplay>> {
+    os_var_from_value(var_os(name))
+}
+
+/// Converts the value of the variable that was read by [`read_os_var`] (so that the tests don't have to modify the environment)
+fn os_var_from_value(value: Option<OsString>) -> Result<String, Option<OsStrDisplay>> {
+    match value {
+        Some(value) => value.into_string().map_err(|value| Some(value.into())),
+        None => Err(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{os_var_from_value, read_env_vars_with};
+    use crate::OsStrDisplay;
+    use std::ffi::OsString;
+
+    #[test]
+    fn must_read_env_vars_in_order() {
+        let lookup = |name: &str| (name == "THREADS").then(|| OsString::from("many"));
+        let vars = read_env_vars_with(&["THREADS", "UNSET"], lookup);
+        assert_eq!(
+            vars,
+            vec![
+                ("THREADS".to_string(), Some(OsString::from("many").into())),
+                ("UNSET".to_string(), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn must_keep_invalid_os_var() {
+        assert_eq!(os_var_from_value(Some(OsString::from("/var/log"))), Ok("/var/log".to_string()));
+        assert_eq!(os_var_from_value(None), Err(None));
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStringExt;
+            let value = OsString::from_vec(b"/var/l\xf6g".to_vec());
+            assert_eq!(os_var_from_value(Some(value.clone())), Err(Some(OsStrDisplay(value))));
+        }
+    }
+}
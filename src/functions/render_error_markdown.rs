@@ -0,0 +1,196 @@
+use crate::RenderedError;
+use core::error::Error;
+use core::fmt::Write;
+use std::path::Path;
+
+/// The maximum length (in bytes) of the Debug output in [`render_error_markdown`].
+pub const MARKDOWN_DEBUG_MAX_LEN: usize = 32 * 1024;
+
+/// Renders the error as Markdown that can be pasted into a GitHub issue.
+///
+/// The output contains a `**Error:**` line, a nested list that mirrors the error trace (the Markdown metacharacters in the messages are escaped), and a collapsed `<details>` block with the Debug output (truncated to [`MARKDOWN_DEBUG_MAX_LEN`]).
+pub fn render_error_markdown(error: &(dyn Error + 'static)) -> String {
+    render_rendered_error_markdown(&RenderedError::new(error), None)
+}
+
+/// Like [`render_error_markdown`], but also includes the path to the full error report (see [`write_to_named_temp_file`](crate::write_to_named_temp_file)).
+///
+/// The path is written in a fenced code block, so it's rendered verbatim even if it contains the backticks or the Markdown metacharacters.
+pub fn render_error_markdown_with_report(error: &(dyn Error + 'static), report_path: &Path) -> String {
+    render_rendered_error_markdown(&RenderedError::new(error), Some(report_path))
+}
+
+/// Like [`render_error_markdown`], but reads the error that is already rendered (see [`RenderedError`]).
+pub fn render_rendered_error_markdown(rendered: &RenderedError, report_path: Option<&Path>) -> String {
+    let trace = rendered.to_string();
+    let items = trace.lines().fold(Vec::<String>::new(), |mut items, line| {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let (prefix, rest) = line.split_at(indent);
+        match rest
+            .strip_prefix("* - ")
+            .or_else(|| rest.strip_prefix("- "))
+        {
+            Some(message) => items.push(format!("{prefix}- {}", escape_markdown(message))),
+            None => match items.last_mut() {
+                Some(item) => {
+                    item.push_str("<br>");
+                    item.push_str(&escape_markdown(rest));
+                }
+                None => items.push(escape_markdown(rest)),
+            },
+        }
+        items
+    });
+    let debug = truncate_head(&rendered.debug.to_string(), MARKDOWN_DEBUG_MAX_LEN);
+    let fence = code_fence(&debug);
+    let mut output = String::new();
+    // writing to a String never fails
+    let _ = writeln!(output, "**Error:** {}\n", escape_markdown(rendered.title()));
+    items.iter().for_each(|item| {
+        let _ = writeln!(output, "{item}");
+    });
+    if let Some(report_path) = report_path {
+        let report_path = report_path.display().to_string();
+        let fence = code_fence(&report_path);
+        let _ = writeln!(output, "\nFull error report:\n\n{fence}text\n{report_path}\n{fence}");
+    }
+    let _ = write!(output, "\n<details>\n<summary>Debug output</summary>\n\n{fence}text\n{debug}\n{fence}\n\n</details>\n");
+    output
+}
+
+/// Escapes the characters that have a special meaning in inline Markdown (including `<` and `>`, which GitHub would otherwise interpret as HTML).
+pub fn escape_markdown(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut output, char| {
+            if matches!(char, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '&') {
+                output.push('\\');
+            }
+            output.push(char);
+            output
+        })
+}
+
+fn truncate_head(text: &str, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text.to_string();
+    }
+    let end = (0..=max_len)
+        .rev()
+        .find(|&index| text.is_char_boundary(index))
+        .unwrap_or_default();
+    format!("{}\n… (truncated)", &text[..end])
+}
+
+/// Returns the fence of a code block that is longer than any run of backticks in the text (so the text can't close it)
+fn code_fence(text: &str) -> String {
+    "`".repeat(max_backtick_run(text).max(2) + 1)
+}
+
+fn max_backtick_run(text: &str) -> usize {
+    text.split(|char| char != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrVec;
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use thiserror::Error;
+
+    #[test]
+    fn must_escape_markdown_metacharacters() {
+        assert_eq!(escape_markdown("use `cargo` *now*"), "use \\`cargo\\` \\*now\\*");
+        assert_eq!(escape_markdown("expected <path>, found [x] | y_z"), "expected \\<path\\>, found \\[x\\] \\| y\\_z");
+        assert_eq!(escape_markdown("a\\b & ~c~"), "a\\\\b \\& \\~c\\~");
+        assert_eq!(escape_markdown("version 0.5.0 - done"), "version 0.5.0 - done");
+    }
+
+    #[test]
+    fn must_escape_io_error_message() {
+        let error = io::Error::new(io::ErrorKind::NotFound, "file <config.toml> not found");
+        let markdown = render_error_markdown(&error);
+        assert!(markdown.starts_with("**Error:** file \\<config.toml\\> not found\n\n- file \\<config.toml\\> not found\n"));
+    }
+
+    #[test]
+    fn must_use_longer_fence_than_debug_output() {
+        assert_eq!(max_backtick_run("a ```` b ` c"), 4);
+        let error = io::Error::other("````");
+        assert!(render_error_markdown(&error).contains("\n`````text\n"));
+    }
+
+    #[test]
+    fn must_write_report_path_verbatim() {
+        let error = io::Error::other("denied");
+        let markdown = render_error_markdown_with_report(&error, Path::new("/tmp/```report_*1*`.txt"));
+        assert!(markdown.contains("\nFull error report:\n\n````text\n/tmp/```report_*1*`.txt\n````\n"));
+    }
+
+    #[test]
+    fn must_truncate_at_char_boundary() {
+        assert_eq!(truncate_head("aé", 2), "a\n… (truncated)");
+        assert_eq!(truncate_head("abc", 3), "abc");
+    }
+
+    #[test]
+    fn must_render_nested_error_markdown() {
+        let error = SyncAllFailed {
+            source: vec![
+                SyncFailed {
+                    source: ReadManifestFailed {
+                        source: vec![InvalidKey {
+                            key: "*name*".to_string(),
+                        }]
+                        .into(),
+                    },
+                    repo: "errgonomic".to_string(),
+                },
+                SyncFailed {
+                    source: ParseFailed {
+                        expected: "`u32`".to_string(),
+                    },
+                    repo: "<unknown>".to_string(),
+                },
+            ]
+            .into(),
+        };
+        let actual = render_error_markdown_with_report(&error, Path::new("/tmp/report.txt"));
+        assert_eq!(actual, include_str!("render_error_markdown/fixtures/must_render_nested_error_markdown.txt"))
+    }
+
+    use ManifestError::*;
+    use SyncAllError::*;
+    use SyncError::*;
+
+    #[derive(Error, Debug)]
+    enum SyncAllError {
+        #[error("failed to sync {len} repos", len = source.len())]
+        SyncAllFailed { source: ErrVec<SyncError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum SyncError {
+        #[error("failed to sync repo {repo}")]
+        SyncFailed { source: ManifestError, repo: String },
+    }
+
+    #[derive(Error, Debug)]
+    enum ManifestError {
+        #[error("failed to read {len} manifest keys", len = source.len())]
+        ReadManifestFailed { source: ErrVec<KeyError> },
+        #[error("failed to parse manifest\nexpected: {expected}")]
+        ParseFailed { expected: String },
+    }
+
+    #[derive(Error, Debug)]
+    enum KeyError {
+        #[error("key {key} is invalid")]
+        InvalidKey { key: String },
+    }
+
+    use KeyError::*;
+}
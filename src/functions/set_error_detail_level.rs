@@ -0,0 +1,8 @@
+use crate::ErrorDetailLevel;
+
+/// Sets the global error detail level (see [`ErrorDetailLevel`]).
+///
+/// This function should be called at startup, before any errors are constructed: the values that have already been omitted can't be restored.
+pub fn set_error_detail_level(level: ErrorDetailLevel) {
+    level.store()
+}
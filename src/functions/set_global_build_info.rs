@@ -0,0 +1,8 @@
+use crate::BuildInfo;
+
+/// Sets the global build info, which is written after the error report by [`writeln_error_to_writer_and_file`](crate::writeln_error_to_writer_and_file) and [`eprintln_error`](crate::eprintln_error).
+///
+/// This function should be called at startup. If the build info is not set, no extra lines are written.
+pub fn set_global_build_info(info: BuildInfo) {
+    BuildInfo::store_global(Some(info))
+}
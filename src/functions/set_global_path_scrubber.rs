@@ -0,0 +1,8 @@
+use crate::PathScrubber;
+
+/// Sets the global path scrubber, which rewrites the paths in the error traces and in the full error reports written by [`writeln_error_to_writer_and_file`](crate::writeln_error_to_writer_and_file) and [`eprintln_error`](crate::eprintln_error).
+///
+/// This function should be called at startup (typically with [`PathScrubber::from_env`]). If the scrubber is not set, the paths are written as is.
+pub fn set_global_path_scrubber(scrubber: PathScrubber) {
+    PathScrubber::store_global(Some(scrubber))
+}
@@ -0,0 +1,41 @@
+use core::num::NonZeroUsize;
+use std::env::var;
+
+/// The terminal width that is used when the width can't be detected.
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Returns the width of the terminal that the error traces are written to.
+///
+/// Returns the value of the `COLUMNS` environment variable if it contains a positive integer, or [`DEFAULT_TERMINAL_WIDTH`] otherwise (including the case when stderr is redirected to a file).
+pub fn terminal_width() -> Option<usize> {
+    Some(parse_columns(var("COLUMNS").ok().as_deref()).unwrap_or(DEFAULT_TERMINAL_WIDTH))
+}
+
+/// Parses the value of the `COLUMNS` environment variable (`None` if it's not set or is not a positive integer)
+fn parse_columns(value: Option<&str>) -> Option<usize> {
+    value
+        .and_then(|columns| columns.trim().parse::<NonZeroUsize>().ok())
+        .map(NonZeroUsize::get)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_columns;
+    use crate::{DEFAULT_TERMINAL_WIDTH, terminal_width};
+
+    #[test]
+    fn must_parse_columns() {
+        assert_eq!(parse_columns(Some("132")), Some(132));
+        assert_eq!(parse_columns(Some(" 100\n")), Some(100));
+        assert_eq!(parse_columns(Some("0")), None);
+        assert_eq!(parse_columns(Some("wide")), None);
+        assert_eq!(parse_columns(None), None);
+    }
+
+    #[test]
+    fn must_fall_back_to_default_width() {
+        // the environment is not modified, so the width is either the inherited `COLUMNS` or the default
+        let expected = parse_columns(std::env::var("COLUMNS").ok().as_deref()).unwrap_or(DEFAULT_TERMINAL_WIDTH);
+        assert_eq!(terminal_width(), Some(expected));
+    }
+}
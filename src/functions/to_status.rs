@@ -0,0 +1,51 @@
+use crate::{AsDynError, GrpcStatus, ParsedTree, error_parsed_tree};
+use core::iter::successors;
+use serde_json::{Map, Value};
+use tonic::Status;
+
+/// The size of the largest JSON chain (in bytes) that is attached to the details of the [`Status`] by [`to_status`].
+///
+/// The details are sent in the `grpc-status-details-bin` trailer, which is limited by the peers (8 KiB of headers by default in many servers and proxies).
+pub const GRPC_DETAILS_MAX_LEN: usize = 4 * 1024;
+
+/// Converts the error into a [`Status`]: the code is [`GrpcStatus::grpc_code`], the message is the top error message (without the chain), and the whole error tree is attached as the JSON chain in the [details](Status::details).
+///
+/// The JSON chain is an array of the errors in the `source` chain. Every error has the `message`, and every aggregate error has the `children`, where every child has the optional `label` and its own `chain`:
+///
+/// ```json
+/// [{"message": "failed to load 2 user profiles"}, {"message": "encountered 2 errors", "children": [{"chain": [{"message": "failed to query profile 1"}, {"message": "connection reset"}]}]}]
+/// ```
+///
+/// The details are sent in the `grpc-status-details-bin` trailer, so the client can recover the tree with [`from_status`](crate::from_status).
+/// The details are omitted if the chain is larger than [`GRPC_DETAILS_MAX_LEN`] (the client falls back to the message).
+pub fn to_status<E: GrpcStatus + AsDynError + ?Sized>(error: &E) -> Status {
+    let tree = error_parsed_tree(error);
+    let details = json_chain(&tree).to_string();
+    if details.len() <= GRPC_DETAILS_MAX_LEN {
+        Status::with_details(error.grpc_code(), tree.message, details.into_bytes().into())
+    } else {
+        Status::new(error.grpc_code(), tree.message)
+    }
+}
+
+/// Returns the JSON chain of the tree (see [`to_status`])
+fn json_chain(tree: &ParsedTree) -> Value {
+    successors(Some(tree), |tree| tree.source.as_deref())
+        .map(|tree| {
+            let mut error = Map::new();
+            error.insert("message".to_string(), Value::from(tree.message.as_str()));
+            if !tree.children.is_empty() {
+                let children = tree.children.iter().map(|child| {
+                    let mut object = Map::new();
+                    if let Some(label) = &child.label {
+                        object.insert("label".to_string(), Value::from(label.as_str()));
+                    }
+                    object.insert("chain".to_string(), json_chain(child));
+                    Value::Object(object)
+                });
+                error.insert("children".to_string(), children.collect());
+            }
+            Value::Object(error)
+        })
+        .collect()
+}
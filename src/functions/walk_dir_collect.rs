@@ -0,0 +1,236 @@
+use crate::{ErrVec, ItemError, PathBufDisplay, SymlinkPolicy, WalkDirError};
+use WalkDirError::*;
+use core::error::Error;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Calls `f` for every file under `root` (recursively), collecting both the outputs and the errors (the walk is never aborted early).
+///
+/// The entries of every directory are visited in the order of their names, so the outputs are sorted by path.
+/// The symlinks are skipped (use [`walk_dir_collect_with`] to follow them).
+///
+/// See also: [`handle_walk!`](crate::handle_walk)
+pub fn walk_dir_collect<T, E: Error + 'static>(root: &Path, f: impl FnMut(&Path) -> Result<T, E>) -> Result<Vec<T>, ErrVec<WalkDirError<E>>> {
+    walk_dir_collect_with(root, SymlinkPolicy::Skip, f)
+}
+
+/// Like [`walk_dir_collect`], but treats the symlinks according to the `policy`.
+pub fn walk_dir_collect_with<T, E: Error + 'static>(root: &Path, policy: SymlinkPolicy, mut f: impl FnMut(&Path) -> Result<T, E>) -> Result<Vec<T>, ErrVec<WalkDirError<E>>> {
+    let mut walker = Walker {
+        policy,
+        visited: HashSet::new(),
+        oks: Vec::new(),
+        errors: Vec::new(),
+    };
+    walker.walk_dir(root, &mut f);
+    if walker.errors.is_empty() { Ok(walker.oks) } else { Err(ErrVec::new(walker.errors)) }
+}
+
+struct Walker<T, E> {
+    policy: SymlinkPolicy,
+    /// The canonical paths of the directories that have been entered (only tracked if the symlinks are followed)
+    visited: HashSet<PathBuf>,
+    oks: Vec<T>,
+    errors: Vec<WalkDirError<E>>,
+}
+
+impl<T, E> Walker<T, E> {
+    fn walk_dir(&mut self, dir: &Path, f: &mut impl FnMut(&Path) -> Result<T, E>) {
+        if self.policy == SymlinkPolicy::Follow {
+            if let Ok(canonical) = fs::canonicalize(dir) {
+                if !self.visited.insert(canonical) {
+                    return;
+                }
+            }
+        }
+        let entries = fs::read_dir(dir).and_then(|entries| entries.collect::<Result<Vec<_>, _>>());
+        let mut entries = match entries {
+            Ok(entries) => entries,
+            Err(source) => {
+                return self.errors.push(ReadDirFailed {
+                    path: dir.to_path_buf(),
+                    source,
+                });
+            }
+        };
+        entries.sort_by_key(|entry| entry.file_name());
+        entries
+            .into_iter()
+            .for_each(|entry| self.walk_entry(entry.path(), f));
+    }
+
+    fn walk_entry(&mut self, path: PathBuf, f: &mut impl FnMut(&Path) -> Result<T, E>) {
+        let file_type = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata.file_type(),
+            Err(source) => {
+                return self.errors.push(MetadataUnreadable {
+                    path,
+                    source,
+                });
+            }
+        };
+        let file_type = if file_type.is_symlink() {
+            match self.policy {
+                SymlinkPolicy::Skip => return,
+                SymlinkPolicy::Follow => match fs::metadata(&path) {
+                    Ok(metadata) => metadata.file_type(),
+                    Err(source) => {
+                        return self.errors.push(SymlinkUnresolvable {
+                            path,
+                            source,
+                        });
+                    }
+                },
+            }
+        } else {
+            file_type
+        };
+        if file_type.is_dir() {
+            self.walk_dir(&path, f)
+        } else {
+            match f(&path) {
+                Ok(value) => self.oks.push(value),
+                Err(source) => self.errors.push(CallbackFailed {
+                    source: ItemError {
+                        item: PathBufDisplay(path),
+                        source,
+                    },
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SymlinkPolicy, WalkDirError, walk_dir_collect, walk_dir_collect_with};
+    use pretty_assertions::assert_eq;
+    use std::error::Error;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    fn read_to_string(path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn create_tree(files: &[(&str, &str)]) -> io::Result<TempDir> {
+        let root = tempfile::tempdir()?;
+        files.iter().try_for_each(|(path, contents)| {
+            let path = root.path().join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)
+        })?;
+        Ok(root)
+    }
+
+    #[test]
+    fn must_collect_in_sorted_order() -> TestResult {
+        let root = create_tree(&[
+            ("b/2.txt", "b2"),
+            ("a.txt", "a"),
+            ("b/1.txt", "b1"),
+            ("c/d/e.txt", "e"),
+            ("ab.txt", "ab"),
+        ])?;
+        fs::create_dir(root.path().join("empty"))?;
+        let actual = walk_dir_collect(root.path(), read_to_string)?;
+        assert_eq!(actual, ["a", "ab", "b1", "b2", "e"]);
+        Ok(())
+    }
+
+    #[test]
+    fn must_collect_empty_dir() -> TestResult {
+        let root = tempfile::tempdir()?;
+        let actual = walk_dir_collect(root.path(), read_to_string)?;
+        assert_eq!(actual, Vec::<String>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn must_not_abort_on_callback_errors() -> TestResult {
+        let root = create_tree(&[
+            ("a.txt", "1"),
+            ("b.txt", "x"),
+            ("c/d.txt", "y"),
+            ("c/e.txt", "2"),
+        ])?;
+        let mut visited = 0;
+        let errors = walk_dir_collect(root.path(), |path| {
+            visited += 1;
+            fs::read_to_string(path)?
+                .parse::<u32>()
+                .map_err(io::Error::other)
+        })
+        .err()
+        .ok_or("the walk must fail")?;
+        assert_eq!(visited, 4);
+        let items = errors
+            .iter()
+            .map(|error| match error {
+                WalkDirError::CallbackFailed {
+                    source,
+                } => Some(source.item.0.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            [
+                Some(root.path().join("b.txt")),
+                Some(root.path().join("c/d.txt"))
+            ]
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_handle_dangling_symlink() -> TestResult {
+        let root = create_tree(&[("a.txt", "a")])?;
+        std::os::unix::fs::symlink(root.path().join("missing.txt"), root.path().join("dangling.txt"))?;
+        std::os::unix::fs::symlink(root.path().join("a.txt"), root.path().join("link.txt"))?;
+        assert_eq!(walk_dir_collect(root.path(), read_to_string)?, ["a"]);
+        let errors = walk_dir_collect_with(root.path(), SymlinkPolicy::Follow, read_to_string)
+            .err()
+            .ok_or("the walk must fail")?;
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], WalkDirError::SymlinkUnresolvable { path, .. } if path.ends_with("dangling.txt")));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_not_follow_symlink_cycles() -> TestResult {
+        let root = create_tree(&[("a/b.txt", "b")])?;
+        std::os::unix::fs::symlink(root.path(), root.path().join("a/loop"))?;
+        let actual = walk_dir_collect_with(root.path(), SymlinkPolicy::Follow, read_to_string)?;
+        assert_eq!(actual, ["b"]);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_continue_after_permission_denied() -> TestResult {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let root = create_tree(&[("a.txt", "a"), ("locked/b.txt", "b"), ("z.txt", "z")])?;
+        // the temp dir is owned by the effective user, and the permissions are not enforced for root
+        if fs::metadata(root.path())?.uid() == 0 {
+            return Ok(());
+        }
+        let locked = root.path().join("locked");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000))?;
+        let result = walk_dir_collect(root.path(), read_to_string);
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755))?;
+        let errors = result.err().ok_or("the walk must fail")?;
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], WalkDirError::ReadDirFailed { path, .. } if path == &locked));
+        Ok(())
+    }
+}
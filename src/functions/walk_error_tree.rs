@@ -0,0 +1,19 @@
+use crate::ErrorRegistry;
+use core::error::Error;
+
+/// Calls `visit` for every error in the error tree: the error itself, the children of the aggregates that are registered in the [`ErrorRegistry`], and the sources (in the order of the trace).
+///
+/// The `Display` impls of the errors are not called, so this function is cheaper than rendering the trace (e.g. for the functions that look for a specific error type, like [`extract_retry_after`](crate::extract_retry_after)).
+pub fn walk_error_tree(error: &(dyn Error + 'static), visit: &mut dyn FnMut(&(dyn Error + 'static))) {
+    // the source is requested before the lookup, because the aggregates register themselves in their `source` impls
+    let source = error.source();
+    visit(error);
+    if let Some(aggregate) = ErrorRegistry::children(error) {
+        aggregate
+            .children()
+            .for_each(|child| walk_error_tree(child.error, visit));
+    }
+    if let Some(source) = source {
+        walk_error_tree(source, visit)
+    }
+}
@@ -0,0 +1,6 @@
+use crate::RequestId;
+
+/// Polls `future` with `id` set as the ambient request id of the current task (see [`handle_id!`](crate::handle_id)).
+pub async fn with_request_id<F: Future>(id: impl Into<RequestId>, future: F) -> F::Output {
+    RequestId::scope_async(id.into(), future).await
+}
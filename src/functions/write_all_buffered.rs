@@ -0,0 +1,81 @@
+use std::io;
+use std::io::Write;
+
+/// Renders the output into an in-memory buffer and then writes it to the writer with a single [`write_all`](Write::write_all) call.
+///
+/// This is useful for the shared writers (e.g. [`Stderr`](std::io::Stderr)) that lock on every call: the output of concurrent callers is not interleaved, and the writer is not locked while the output is rendered (so a `Display` impl that writes to the same writer doesn't deadlock).
+/// Returns the output of `render` (e.g. the result of the rendering) if the buffer was written successfully.
+pub fn write_all_buffered<T>(writer: &mut dyn Write, render: impl FnOnce(&mut Vec<u8>) -> T) -> io::Result<T> {
+    let mut buffer = Vec::new();
+    let output = render(&mut buffer);
+    writer.write_all(&buffer)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, write_all_buffered, writeln_error_to_writer};
+    use std::collections::HashSet;
+    use std::io;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A writer that locks the shared buffer on every call (like `Stderr`)
+    #[derive(Clone, Default)]
+    struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let mut buffer = self
+                .0
+                .lock()
+                .map_err(|_| io::Error::other("lock poisoned"))?;
+            buffer.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn get_error(thread: usize, index: usize) -> ErrVec<io::Error> {
+        ErrVec::new((0..3).map(|child| io::Error::other(format!("thread {thread} error {index} child {child}"))))
+    }
+
+    #[test]
+    fn must_not_interleave_concurrent_writes() {
+        let writer = SharedWriter::default();
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let mut writer = writer.clone();
+                thread::spawn(move || {
+                    (0..100).for_each(|index| {
+                        let error = get_error(thread, index);
+                        write_all_buffered(&mut writer, |buffer| writeln_error_to_writer(&error, buffer))
+                            .unwrap()
+                            .unwrap()
+                    })
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .for_each(|handle| handle.join().unwrap());
+        let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 8 * 100 * 4);
+        let mut seen = HashSet::new();
+        lines.chunks(4).for_each(|report| {
+            let header = report[0];
+            assert_eq!(header, "- encountered 3 errors");
+            let prefix = report[1]
+                .strip_prefix("  * - ")
+                .and_then(|line| line.strip_suffix(" child 0"))
+                .unwrap_or_else(|| panic!("unexpected line: {}", report[1]));
+            (1..3).for_each(|child| assert_eq!(report[child + 1], format!("  * - {prefix} child {child}")));
+            assert!(seen.insert(prefix.to_string()), "duplicate report: {prefix}");
+        });
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{AggregateMessage, AsDynError, ErrorChildren, FmtPrefixer, get_debug_name};
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::Write;
+
+/// Appends the error trace to the string (the same output as [`write_error_chain_to_fmt`], [`ErrorDisplayer`](crate::ErrorDisplayer) and [`writeln_error_to_writer`](crate::writeln_error_to_writer) without the trailing newline).
+///
+/// This function doesn't return an error, because writing to a `String` never fails (if a `Display` impl of an error returns an error, the trace is truncated at that point).
+pub fn push_error_trace(error: &(dyn Error + 'static), out: &mut String) {
+    let _ = write_error_chain_to_fmt(error, out);
+}
+
+/// Writes the error trace to the [`fmt::Write`](Write) trait object (see [`write_error_chain_to_fmt`]).
+pub fn write_error_trace_fmt(error: &(dyn Error + 'static), mut out: &mut dyn Write) -> core::fmt::Result {
+    write_error_chain_to_fmt(error, &mut out)
+}
+
+/// Writes a human-readable error trace to the provided [`fmt::Write`](Write) (one `- {error}` bullet per error in the chain, without a trailing newline).
+///
+/// The continuation lines of multi-line messages are indented to stay inside their bullet.
+/// The children of the aggregates that are registered in the [`ErrorRegistry`](crate::ErrorRegistry) are written as nested bullets (see [`ErrorChildren`]), so the `Display` impls of the aggregates are not called.
+///
+/// This function doesn't require `std`, so it can be used to render errors into a `String` or a [`Formatter`](core::fmt::Formatter) (without `std`, the aggregates are written via their `Display` impls).
+pub fn write_error_chain_to_fmt<E: AsDynError + ?Sized>(error: &E, writer: &mut impl Write) -> core::fmt::Result {
+    write_error_tree(error.as_dyn(), writer, false)
+}
+
+/// Like [`write_error_chain_to_fmt`], but prefixes every message with the name of the error (`- [VariantName] {error}`), which is derived from its [`Debug`](core::fmt::Debug) output (see [`get_debug_name`]).
+///
+/// The children of the aggregates are prefixed in the same way.
+pub fn write_error_chain_with_names_to_fmt<E: AsDynError + ?Sized>(error: &E, writer: &mut impl Write) -> core::fmt::Result {
+    write_error_tree(error.as_dyn(), writer, true)
+}
+
+fn write_error_tree(error: &(dyn Error + 'static), writer: &mut dyn Write, is_named: bool) -> core::fmt::Result {
+    // the source is requested before the lookup, because the aggregates register themselves in their `source` impls
+    let source = error.source();
+    if is_named {
+        write!(writer, "- [{}] ", get_debug_name(error))?;
+    } else {
+        writer.write_str("- ")?;
+    }
+    {
+        let mut prefixer = FmtPrefixer::continuation(writer, "  ");
+        match aggregate_children(error) {
+            Some(aggregate) => {
+                write!(prefixer, "{}", AggregateMessage(aggregate))?;
+                aggregate.children().try_for_each(|child| {
+                    match child.label.as_deref() {
+                        Some(label) => write!(prefixer, "\n* [{label}] ")?,
+                        None => prefixer.write_str("\n* ")?,
+                    }
+                    write_error_tree(child.error, &mut FmtPrefixer::continuation(&mut prefixer, "  "), is_named)
+                })?
+            }
+            None => write!(prefixer, "{error}")?,
+        }
+    }
+    match source {
+        Some(source) => {
+            writer.write_char('\n')?;
+            write_error_tree(source, writer, is_named)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the children of the error if it's an aggregate that is registered in the [`ErrorRegistry`](crate::ErrorRegistry) (the registry requires `std`).
+fn aggregate_children<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a dyn ErrorChildren> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "std")] {
+            crate::ErrorRegistry::children(error)
+        } else {
+            let _ = error;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{push_error_trace, write_error_chain_to_fmt, write_error_chain_with_names_to_fmt, write_error_trace_fmt};
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_write_to_string() {
+        let error = LoadConfigError::ReadFileFailed {
+            source: ReadFileError::FileNotFound,
+        };
+        let mut string = String::new();
+        write_error_chain_to_fmt(&error, &mut string).expect("always succeeds because writing to a String never fails");
+        assert_eq!(string, "- failed to read the file\n- file not found");
+    }
+
+    #[test]
+    fn must_write_names_to_string() {
+        let error = LoadConfigError::ReadFileFailed {
+            source: ReadFileError::FileNotFound,
+        };
+        let mut string = String::new();
+        write_error_chain_with_names_to_fmt(&error, &mut string).expect("always succeeds because writing to a String never fails");
+        assert_eq!(string, "- [ReadFileFailed] failed to read the file\n- [FileNotFound] file not found");
+    }
+
+    #[test]
+    fn must_render_the_same_trace_via_every_path() {
+        let controls: String = (0u8..0x20).chain([0x7f]).map(char::from).collect();
+        let messages = [
+            controls.clone(),
+            format!("é ü 日本 🦀\n{controls}\n"),
+            "\n\n- * [x]\r\n".to_string(),
+        ];
+        messages.into_iter().for_each(|message| {
+            let error = WrapError::Wrapped {
+                message: message.clone(),
+                source: LeafError::Leaf {
+                    message,
+                },
+            };
+            let mut pushed = String::from("prefix: ");
+            push_error_trace(&error, &mut pushed);
+            let mut written = String::new();
+            write_error_trace_fmt(&error, &mut written).unwrap();
+            assert_eq!(pushed.strip_prefix("prefix: "), Some(written.as_str()));
+            #[cfg(feature = "std")]
+            {
+                assert_eq!(crate::ErrorDisplayer(&error).to_string(), written);
+                let mut bytes = std::vec::Vec::new();
+                crate::writeln_error_to_writer(&error, &mut bytes).unwrap();
+                assert_eq!(bytes, format!("{written}\n").into_bytes());
+            }
+        });
+    }
+
+    #[derive(Error, Debug)]
+    enum WrapError {
+        #[error("wrapped {message}")]
+        Wrapped { message: String, source: LeafError },
+    }
+
+    #[derive(Error, Debug)]
+    enum LeafError {
+        #[error("{message}")]
+        Leaf { message: String },
+    }
+
+    #[derive(Error, Debug)]
+    enum LoadConfigError {
+        #[error("failed to read the file")]
+        ReadFileFailed { source: ReadFileError },
+    }
+
+    #[derive(Error, Debug)]
+    enum ReadFileError {
+        #[error("file not found")]
+        FileNotFound,
+    }
+}
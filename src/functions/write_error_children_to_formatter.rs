@@ -0,0 +1,23 @@
+use crate::{AsDynError, ErrorChildren, ErrorDisplayer, FmtPrefixer};
+use core::fmt::{Formatter, Write};
+
+/// Writes every child of the aggregate as a nested bullet (one `\n  * ` line per child, with continuation lines indented).
+///
+/// This function doesn't write the message of the aggregate itself, so it should be called from the `Display` impl of the aggregate after writing its message.
+pub fn write_error_children_to_formatter(children: &dyn ErrorChildren, f: &mut Formatter<'_>) -> core::fmt::Result {
+    children
+        .children()
+        .try_for_each(|child| write_error_child_to_formatter(child.label.as_deref(), child.error, f))
+}
+
+/// Writes a single child of the aggregate as a nested bullet, prefixing the first line with the label (if any).
+pub fn write_error_child_to_formatter<E: AsDynError + ?Sized>(label: Option<&str>, error: &E, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_char('\n')?;
+    let mut indenter = FmtPrefixer::new(f, "  ");
+    let mut prefixer = FmtPrefixer::continuation(&mut indenter, "  ");
+    match label {
+        Some(label) => write!(prefixer, "* [{label}] ")?,
+        None => prefixer.write_str("* ")?,
+    }
+    write!(prefixer, "{}", ErrorDisplayer(error))
+}
@@ -1,18 +1,23 @@
 use crate::{handle, map_err};
 use std::fs::File;
 use std::io;
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use tempfile::{NamedTempFile, PersistError};
 use thiserror::Error;
 
-/// Writes the provided buffer to a named temporary file and persists it to disk.
+/// Writes the output of the provided closure to a named temporary file and persists it to disk.
+///
+/// The closure writes directly into a buffered file writer, so the caller doesn't need to build the whole contents in memory.
 ///
 /// Returns the persisted file handle and its path.
-pub fn write_to_named_temp_file(buf: &[u8]) -> Result<(File, PathBuf), WriteToNamedTempFileError> {
+pub fn write_to_named_temp_file(write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> Result<(File, PathBuf), WriteToNamedTempFileError> {
     use WriteToNamedTempFileError::*;
     let mut temp = handle!(NamedTempFile::new(), CreateTempFileFailed);
-    handle!(temp.write_all(buf), WriteFailed);
+    let mut writer = BufWriter::new(temp.as_file_mut());
+    handle!(write(&mut writer), WriteFailed);
+    handle!(writer.flush(), FlushFailed);
+    drop(writer);
     map_err!(temp.keep(), KeepFailed)
 }
 
@@ -22,9 +27,12 @@ pub enum WriteToNamedTempFileError {
     /// Failed to create a temporary file.
     #[error("failed to create a temporary file")]
     CreateTempFileFailed { source: io::Error },
-    /// Failed to write the buffer into the temporary file.
+    /// Failed to write the contents into the temporary file.
     #[error("failed to write to a temporary file")]
     WriteFailed { source: io::Error },
+    /// Failed to flush the buffered contents into the temporary file.
+    #[error("failed to flush a temporary file")]
+    FlushFailed { source: io::Error },
     /// Failed to persist the temporary file to its final path.
     #[error("failed to persist the temporary file")]
     KeepFailed { source: PersistError },
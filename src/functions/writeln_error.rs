@@ -1,49 +1,441 @@
-use crate::{ErrorDisplayer, WriteToNamedTempFileError, map_err, write_to_named_temp_file};
+use crate::{AsDynError, BuildInfo, ERROR_TRACE_HEADER, ErrorDisplayer, ParsedTree, PathScrubber, Quiet, RenderedError, ScrubWriter, TraceFormat, Verbosity, WriteToNamedTempFileError, collapse_repeated_subtrees, error_parsed_tree, error_paths, hyperlink_paths, map_err, number_chain_levels, write_all_buffered, write_error_chain_to_fmt, write_error_chain_with_names_to_fmt, write_to_named_temp_file};
 use core::error::Error;
-use core::fmt::Formatter;
+use core::fmt::Write as _;
+use core::fmt::{Display, Formatter};
+use core::mem;
+use std::env;
 use std::io;
-use std::io::{Write, stderr};
+use std::io::{IsTerminal, Write, stderr};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{OnceLock, mpsc};
+use std::thread;
+use std::time::Duration;
 
 /// Writes a human-readable error trace to the provided formatter.
-pub fn writeln_error_to_formatter<E: Error + ?Sized>(error: &E, f: &mut Formatter<'_>) -> core::fmt::Result {
-    use std::fmt::Write;
-    write!(f, "- {error}")?;
-    if let Some(source_new) = error.source() {
-        f.write_char('\n')?;
-        writeln_error_to_formatter(source_new, f)
+pub fn writeln_error_to_formatter<E: AsDynError + ?Sized>(error: &E, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write_error_chain_to_fmt(error, f)
+}
+
+/// Writes a human-readable error trace to the provided writer (without the report trailer).
+pub fn writeln_error_to_writer<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let displayer = ErrorDisplayer(error);
+    writeln!(writer, "{displayer}")
+}
+
+/// Writes a human-readable error trace to the provided writer, replacing the repeated subtrees with `(same as error above)`.
+///
+/// This is useful when the same failure is recorded at multiple aggregation levels. See [`collapse_repeated_subtrees`].
+pub fn writeln_error_deduped<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let trace = ErrorDisplayer(error).to_string();
+    writeln!(writer, "{}", collapse_repeated_subtrees(&trace))
+}
+
+/// Writes a human-readable error trace to the provided writer, prefixing every message with the name of the error (e.g. `- [ReadFileFailed] failed to read the file`).
+///
+/// This is useful for debugging, because the messages of thiserror errors usually don't include the variant names. See [`write_error_chain_with_names_to_fmt`].
+pub fn writeln_error_with_types<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let mut trace = String::new();
+    // writing to a String never fails
+    let _ = write_error_chain_with_names_to_fmt(error, &mut trace);
+    writeln!(writer, "{trace}")
+}
+
+/// Writes a human-readable error trace to the provided writer, prefixing every level with its number.
+///
+/// This is useful for referencing specific levels in bug reports. See [`number_chain_levels`].
+pub fn writeln_error_numbered<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let trace = ErrorDisplayer(error).to_string();
+    writeln!(writer, "{}", number_chain_levels(&trace))
+}
+
+/// Writes a human-readable error trace to the provided writer, wrapping the paths in OSC 8 hyperlinks if the writer is a terminal (see [`writeln_error_with_hyperlinks`]).
+pub fn writeln_error_hyperlinked<E: AsDynError + ?Sized, W: Write + IsTerminal>(error: &E, writer: &mut W) -> io::Result<()> {
+    if writer.is_terminal() {
+        writeln_error_with_hyperlinks(error, writer)
     } else {
-        Ok(())
+        writeln_error_to_writer(error, writer)
     }
 }
 
+/// Writes a human-readable error trace to the provided writer, wrapping the paths of the errors in the error tree in OSC 8 hyperlinks (see [`error_paths`] and [`hyperlink_paths`]).
+///
+/// Implement [`ErrorPath`](crate::ErrorPath) for your errors and register them via [`ErrorRegistry::register_path`](crate::ErrorRegistry::register_path) to hyperlink their paths, or use [`hyperlink_paths`] directly to hyperlink other paths.
+pub fn writeln_error_with_hyperlinks<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let paths = error_paths(error);
+    let paths = paths.iter().map(PathBuf::as_path).collect::<Vec<&Path>>();
+    let trace = ErrorDisplayer(error).to_string();
+    writeln!(writer, "{}", hyperlink_paths(&trace, &paths))
+}
+
+/// Writes the error trace in the strict format v1 to the provided writer (see [`ParsedTree`](crate::ParsedTree) for the description of the format).
+///
+/// The strict format is intended for the tools that consume stderr: it can be parsed back with [`parse_error_trace`](crate::parse_error_trace).
+pub fn writeln_error_strict<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    writeln!(writer, "{ERROR_TRACE_HEADER}\n{}", error_parsed_tree(error))
+}
+
+/// Writes the error trace to every sink in the format of the sink (e.g. the human-readable trace to stderr and the JSON lines to a log file).
+///
+/// Every format is rendered once, even if multiple sinks use it. The trace is written to every sink even if some sinks fail (the first error is returned).
+pub fn writeln_error_tee<E: AsDynError + ?Sized>(error: &E, sinks: &mut [(&mut dyn Write, TraceFormat)]) -> io::Result<()> {
+    let mut traces: Vec<(TraceFormat, Vec<u8>)> = Vec::new();
+    let mut first_error = None;
+    for (writer, format) in sinks.iter_mut() {
+        let index = match traces.iter().position(|(rendered, _)| rendered == format) {
+            Some(index) => index,
+            None => {
+                let mut trace = Vec::new();
+                format.writeln(error, &mut trace)?;
+                traces.push((*format, trace));
+                traces.len() - 1
+            }
+        };
+        if let Err(error) = writer.write_all(&traces[index].1) {
+            first_error.get_or_insert(error);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Writes the first line of the message of the quiet error to the provided writer (without the source chain and without the full error report).
+///
+/// If the global path scrubber is set (see [`set_global_path_scrubber`](crate::set_global_path_scrubber)), the paths in the line are scrubbed.
+pub fn writeln_quiet_error_to_writer<E: Quiet + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    match PathScrubber::global() {
+        None => writeln_first_line(error, writer),
+        Some(scrubber) => {
+            let mut line = Vec::new();
+            writeln_first_line(error, &mut line)?;
+            writer.write_all(scrubber.scrub(&String::from_utf8_lossy(&line)).as_bytes())
+        }
+    }
+}
+
+fn writeln_first_line<E: Error + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let message = error.to_string();
+    let line = message.lines().next().unwrap_or_default();
+    writeln!(writer, "{line}")
+}
+
 /// Writes a human-readable error trace to the provided writer and persists the full debug output to a temp file.
 ///
 /// This is useful for CLI tools that want a concise error trace on stderr and a path to a full report.
-pub fn writeln_error_to_writer_and_file<E: Error>(error: &E, writer: &mut dyn Write) -> Result<(), WritelnErrorToWriterAndFileError> {
-    use WritelnErrorToWriterAndFileError::*;
-    let displayer = ErrorDisplayer(error);
-    map_err!(writeln!(writer, "{displayer}"), WriteFailed)?;
-    map_err!(writeln!(writer), WriteFailed)?;
-    let error_debug = format!("{error:#?}");
-    let result = write_to_named_temp_file(error_debug.as_bytes());
-    match result {
-        Ok((_file, path_buf)) => {
-            map_err!(writeln!(writer, "See the full error report:"), WriteFailed)?;
-            if cfg!(windows) {
-                map_err!(writeln!(writer, "{}", path_buf.display()), WriteFailed)?;
-            } else {
-                // assuming `less` is available
-                map_err!(writeln!(writer, "less {}", path_buf.display()), WriteFailed)?;
+/// The trailer (a blank line followed by the path to the report) is written after the trace.
+/// If the full report is not larger than [`REPORT_INLINE_THRESHOLD`], it is written inline after the trace instead (without a temp file).
+/// The report is rendered only up to the threshold to check its size, and a larger report is streamed into the temp file, so it's never built in memory as a whole.
+/// If the global build info is set (see [`set_global_build_info`](crate::set_global_build_info)), the trailer ends with a line like `myapp 1.4.2 (abc1234) on host01, pid 4242`, and the report starts with a header block.
+/// If the global path scrubber is set (see [`set_global_path_scrubber`](crate::set_global_path_scrubber)), the paths in the trace and in the report are scrubbed.
+/// The trace is flushed before the report is written, and the write of the temp file is bounded by [`REPORT_WRITE_TIMEOUT`] (see [`writeln_error_with_report_timeout`]).
+pub fn writeln_error_to_writer_and_file<E: Error + 'static>(error: &E, writer: &mut dyn Write) -> Result<(), WritelnErrorToWriterAndFileError> {
+    writeln_error_with_verbosity(error, writer, Verbosity::Normal)
+}
+
+/// Like [`writeln_error_to_writer_and_file`], but writes the trace according to the verbosity (see [`Verbosity`]).
+///
+/// The full error report is written regardless of the verbosity, so nothing is lost.
+pub fn writeln_error_with_verbosity<E: Error + 'static>(error: &E, writer: &mut dyn Write, verbosity: Verbosity) -> Result<(), WritelnErrorToWriterAndFileError> {
+    writeln_error_to_writer_and_file_with_settings(error, writer, verbosity, &ReportSettings::global())
+}
+
+/// Like [`writeln_error_to_writer_and_file`], but with a custom size of the largest full error report (in bytes) that is written inline instead of a temp file.
+///
+/// Pass `0` to always write the report to a temp file, or `usize::MAX` to never write it to a temp file.
+pub fn writeln_error_with_inline_threshold<E: Error + 'static>(error: &E, writer: &mut dyn Write, inline_threshold: usize) -> Result<(), WritelnErrorToWriterAndFileError> {
+    let settings = ReportSettings {
+        inline_threshold,
+        ..ReportSettings::global()
+    };
+    writeln_error_to_writer_and_file_with_settings(error, writer, Verbosity::Normal, &settings)
+}
+
+/// The environment variable that switches the verbosity of [`writeln_error_auto`] at runtime (see [`Verbosity::from_env_value`]).
+pub const VERBOSITY_ENV_VAR: &str = "ERRGONOMIC_VERBOSE";
+
+/// Writes the error trace according to the [`VERBOSITY_ENV_VAR`] environment variable: the one-line root-cause output if the variable is not set, or the full trace if it's set (e.g. `ERRGONOMIC_VERBOSE=1`).
+///
+/// This gives the operators runtime control over the output without code changes.
+/// The variable is read once per process (the later changes are ignored).
+pub fn writeln_error_auto<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+    writeln_error_auto_with(error, writer, &VERBOSITY, || verbosity_from_env_var(env::var(VERBOSITY_ENV_VAR).ok().as_deref()))
+}
+
+/// Like [`writeln_error_auto`], but with the injected cache and reader of the verbosity (so that the tests don't have to modify the environment)
+fn writeln_error_auto_with<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write, verbosity: &OnceLock<Verbosity>, read: impl FnOnce() -> Verbosity) -> io::Result<()> {
+    let verbosity = *verbosity.get_or_init(read);
+    RenderedError::new(error).writeln(writer, verbosity)
+}
+
+/// Converts the value of the [`VERBOSITY_ENV_VAR`] environment variable (`None` if it's not set)
+fn verbosity_from_env_var(value: Option<&str>) -> Verbosity {
+    value.map_or(Verbosity::Concise, Verbosity::from_env_value)
+}
+
+/// Writes a single line with the message of the top error and the messages of the root causes (e.g. `error: failed to parse config (root cause: permission denied)`).
+///
+/// The root causes are the leaves of the error tree, so every failed child of an aggregate is included (the first 3 root causes are listed).
+pub fn writeln_error_concise<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    writeln_concise_tree(&error_parsed_tree(error), writer)
+}
+
+pub(crate) fn writeln_concise_tree(tree: &ParsedTree, writer: &mut dyn Write) -> io::Result<()> {
+    const ROOT_CAUSES_MAX_LEN: usize = 3;
+    let first_line = |tree: &ParsedTree| tree.message.lines().next().unwrap_or_default().to_string();
+    write!(writer, "error: {}", first_line(tree))?;
+    let leaves = tree.leaves();
+    match leaves.as_slice() {
+        [leaf] if core::ptr::eq(*leaf, tree) => (),
+        [leaf] => write!(writer, " (root cause: {})", first_line(leaf))?,
+        leaves => {
+            let mut causes = leaves
+                .iter()
+                .take(ROOT_CAUSES_MAX_LEN)
+                .map(|leaf| first_line(leaf))
+                .collect::<Vec<_>>();
+            if leaves.len() > ROOT_CAUSES_MAX_LEN {
+                causes.push(format!("and {} more", leaves.len() - ROOT_CAUSES_MAX_LEN));
             }
-            Ok(())
+            write!(writer, " (root causes: {})", causes.join("; "))?
         }
-        Err(source) => {
-            map_err!(writeln!(writer, "{source:#?}"), WriteFailed)?;
-            Err(WriteToNamedTempFileFailed {
-                source,
-            })
+    }
+    writeln!(writer)
+}
+
+/// The size of the largest full error report (in bytes) that is written inline by [`writeln_error_to_writer_and_file`] (see [`writeln_error_with_inline_threshold`]).
+pub const REPORT_INLINE_THRESHOLD: usize = 2 * 1024;
+
+/// The longest time that [`writeln_error_to_writer_and_file`] waits for the full error report to be written to a temp file (see [`writeln_error_with_report_timeout`]).
+pub const REPORT_WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Like [`writeln_error_to_writer_and_file`], but with a custom timeout of the write of the full error report to a temp file.
+///
+/// The report is written on a helper thread. If the write doesn't complete within the timeout, a line like `(error report could not be written within 2s)` is written instead of the path, and the thread is detached (e.g. if the temp dir is on a hung network mount).
+/// A report that is written after the timeout is still valid, but nothing refers to it.
+pub fn writeln_error_with_report_timeout<E: Error + 'static>(error: &E, writer: &mut dyn Write, timeout: Duration) -> Result<(), WritelnErrorToWriterAndFileError> {
+    let settings = ReportSettings {
+        timeout,
+        ..ReportSettings::global()
+    };
+    writeln_error_to_writer_and_file_with_settings(error, writer, Verbosity::Normal, &settings)
+}
+
+/// Like [`writeln_error_with_verbosity`], but reads the error that is already rendered (see [`RenderedError`]).
+///
+/// This is useful if the same failure is also written elsewhere (e.g. as Markdown via [`render_rendered_error_markdown`](crate::render_rendered_error_markdown)): the error tree is traversed once, and every output contains the same text.
+pub fn writeln_rendered_error_with_verbosity(rendered: &RenderedError, writer: &mut dyn Write, verbosity: Verbosity) -> Result<(), WritelnErrorToWriterAndFileError> {
+    writeln_rendered_error_to_writer_and_file_with_settings(rendered, writer, verbosity, &ReportSettings::global())
+}
+
+/// Writes the full error report to a file via the provided closure and returns the path to the file
+type ReportFileWriter = fn(&mut dyn FnMut(&mut dyn Write) -> io::Result<()>) -> Result<PathBuf, WriteToNamedTempFileError>;
+
+/// The length of the chunks of the full error report that are sent to the helper thread (see [`write_report_file_with_timeout`])
+const REPORT_CHUNK_LEN: usize = 8 * 1024;
+
+/// The settings of the full error report (the globals are read once per call)
+struct ReportSettings {
+    build_info: Option<BuildInfo>,
+    scrubber: Option<PathScrubber>,
+    inline_threshold: usize,
+    timeout: Duration,
+    write_file: ReportFileWriter,
+}
+
+impl ReportSettings {
+    fn global() -> Self {
+        Self {
+            build_info: BuildInfo::global(),
+            scrubber: PathScrubber::global(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ReportSettings {
+    fn default() -> Self {
+        Self {
+            build_info: None,
+            scrubber: None,
+            inline_threshold: REPORT_INLINE_THRESHOLD,
+            timeout: REPORT_WRITE_TIMEOUT,
+            write_file: write_report_to_named_temp_file,
+        }
+    }
+}
+
+fn write_report_to_named_temp_file(write: &mut dyn FnMut(&mut dyn Write) -> io::Result<()>) -> Result<PathBuf, WriteToNamedTempFileError> {
+    write_to_named_temp_file(write).map(|(_file, path_buf)| path_buf)
+}
+
+/// The full error report: the header of the build info (if any) and the Debug output of the error, with the paths scrubbed (if the scrubber is set)
+struct ReportContents<'a> {
+    rendered: &'a RenderedError<'a>,
+    scrubber: Option<&'a PathScrubber>,
+    build_info: Option<&'a BuildInfo>,
+}
+
+impl Display for ReportContents<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Some(build_info) = self.build_info {
+            writeln!(f, "{}", build_info.header())?;
+        }
+        match self.scrubber {
+            Some(scrubber) => {
+                let mut writer = ScrubWriter::new(scrubber, f);
+                write!(writer, "{}", self.rendered.debug)?;
+                writer.finish()
+            }
+            None => write!(f, "{}", self.rendered.debug),
+        }
+    }
+}
+
+/// A [`fmt::Write`](core::fmt::Write) that collects the text until it exceeds the maximum length (then it returns an error, so the formatting stops early)
+struct CappedString {
+    string: String,
+    max_len: usize,
+}
+
+impl core::fmt::Write for CappedString {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if self.string.len() + s.len() > self.max_len {
+            return Err(core::fmt::Error);
+        }
+        self.string.push_str(s);
+        Ok(())
+    }
+}
+
+/// Returns the full error report if it's not longer than `max_len` (the report is not rendered past `max_len`)
+fn render_short_report(contents: &ReportContents, max_len: usize) -> Option<String> {
+    let mut report = CappedString {
+        string: String::new(),
+        max_len,
+    };
+    write!(report, "{contents}").ok().map(|()| report.string)
+}
+
+/// A [`fmt::Write`](core::fmt::Write) that sends the text in chunks of [`REPORT_CHUNK_LEN`] (it returns an error if the receiver is dropped, so the formatting stops early)
+struct ChunkSender {
+    chunk: String,
+    sender: mpsc::Sender<String>,
+}
+
+impl ChunkSender {
+    fn new(sender: mpsc::Sender<String>) -> Self {
+        Self {
+            chunk: String::with_capacity(REPORT_CHUNK_LEN),
+            sender,
         }
     }
+
+    fn send(&mut self) -> core::fmt::Result {
+        let chunk = mem::replace(&mut self.chunk, String::with_capacity(REPORT_CHUNK_LEN));
+        self.sender.send(chunk).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl core::fmt::Write for ChunkSender {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.chunk.push_str(s);
+        if self.chunk.len() >= REPORT_CHUNK_LEN { self.send() } else { Ok(()) }
+    }
+}
+
+/// Returns [`RecvTimeoutError::Timeout`] if the report wasn't written within the timeout (the helper thread keeps running), or [`RecvTimeoutError::Disconnected`] if `write_file` has panicked.
+///
+/// The report is rendered on the current thread and streamed to the helper thread in chunks, so the whole report is never built in memory at once (unless the helper thread is stuck).
+/// If the thread can't be spawned, the report is streamed into the file without the timeout.
+fn write_report_file_with_timeout(contents: &ReportContents, write_file: ReportFileWriter, timeout: Duration) -> Result<Result<PathBuf, WriteToNamedTempFileError>, RecvTimeoutError> {
+    let (chunk_sender, chunk_receiver) = mpsc::channel::<String>();
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let spawned = thread::Builder::new().spawn(move || {
+        let result = write_file(&mut |file| {
+            chunk_receiver
+                .iter()
+                .try_for_each(|chunk| file.write_all(chunk.as_bytes()))
+        });
+        // the receiver is dropped after the timeout, so the late result is discarded
+        let _ = sender.send(result);
+    });
+    match spawned {
+        Ok(_handle) => {
+            let mut chunks = ChunkSender::new(chunk_sender);
+            // the error means that the file writer has stopped (its result is received below)
+            let _ = write!(chunks, "{contents}").and_then(|()| chunks.send());
+            drop(chunks);
+            receiver.recv_timeout(timeout)
+        }
+        // the process may be out of threads, so the report is written without the timeout
+        Err(_) => Ok(write_file(&mut |file| write!(file, "{contents}"))),
+    }
+}
+
+fn writeln_error_to_writer_and_file_with_settings<E: Error + 'static>(error: &E, writer: &mut dyn Write, verbosity: Verbosity, settings: &ReportSettings) -> Result<(), WritelnErrorToWriterAndFileError> {
+    writeln_rendered_error_to_writer_and_file_with_settings(&RenderedError::new(error), writer, verbosity, settings)
+}
+
+fn writeln_rendered_error_to_writer_and_file_with_settings(rendered: &RenderedError, writer: &mut dyn Write, verbosity: Verbosity, settings: &ReportSettings) -> Result<(), WritelnErrorToWriterAndFileError> {
+    use WritelnErrorToWriterAndFileError::*;
+    let scrubber = settings.scrubber.as_ref();
+    let build_info = settings.build_info.as_ref();
+    let result = match scrubber {
+        None => rendered.writeln(writer, verbosity),
+        Some(scrubber) => {
+            let mut trace = Vec::new();
+            rendered
+                .writeln(&mut trace, verbosity)
+                .and_then(|()| writer.write_all(scrubber.scrub(&String::from_utf8_lossy(&trace)).as_bytes()))
+        }
+    };
+    // the trace must not be delayed by the report
+    map_err!(result.and_then(|()| writer.flush()), WriteFailed)?;
+    let contents = ReportContents {
+        rendered,
+        scrubber,
+        build_info: None,
+    };
+    if let Some(report) = render_short_report(&contents, settings.inline_threshold) {
+        map_err!(writeln!(writer, "\nFull error report:\n{report}"), WriteFailed)?;
+    } else {
+        let contents = ReportContents {
+            build_info,
+            ..contents
+        };
+        let result = match write_report_file_with_timeout(&contents, settings.write_file, settings.timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                map_err!(writeln!(writer, "\n(error report could not be written within {:?})", settings.timeout), WriteFailed)?;
+                return Ok(());
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                map_err!(writeln!(writer, "\n(error report could not be written because the report writer has panicked)"), WriteFailed)?;
+                return Ok(());
+            }
+        };
+        match result {
+            Ok(path_buf) => {
+                map_err!(writeln!(writer, "\nSee the full error report:"), WriteFailed)?;
+                if cfg!(windows) {
+                    map_err!(writeln!(writer, "{}", path_buf.display()), WriteFailed)?;
+                } else {
+                    // assuming `less` is available
+                    map_err!(writeln!(writer, "less {}", path_buf.display()), WriteFailed)?;
+                }
+            }
+            Err(source) => {
+                map_err!(writeln!(writer, "\n{source:#?}"), WriteFailed)?;
+                return Err(WriteToNamedTempFileFailed {
+                    source,
+                });
+            }
+        }
+    }
+    if let Some(build_info) = build_info {
+        map_err!(writeln!(writer, "{}", build_info.trailer()), WriteFailed)?;
+    }
+    Ok(())
 }
 
 /// Errors returned by [`writeln_error_to_writer_and_file`].
@@ -55,39 +447,66 @@ pub enum WritelnErrorToWriterAndFileError {
     WriteToNamedTempFileFailed { source: WriteToNamedTempFileError },
 }
 
+/// Writes the one-line message of the quiet error to stderr (see [`writeln_quiet_error_to_writer`]).
+///
+/// Unlike [`eprintln_error`], this function doesn't write the full error report to a temp file.
+pub fn eprintln_quiet_error<E: Quiet + ?Sized>(error: &E) {
+    let result = write_all_buffered(&mut stderr(), |buffer| writeln_quiet_error_to_writer(error, buffer));
+    if let Err(source) | Ok(Err(source)) = result {
+        eprintln!("failed to write the error to stderr: {source:#?}")
+    }
+}
+
 /// Writes an error trace to stderr and, if possible, includes a path to the full error report.
+///
+/// The trace is rendered (and the full error report is written) before stderr is locked, then the output is written with a single call (see [`write_all_buffered`]).
+/// This way, the traces of the concurrent calls are not interleaved, and an error whose `Display` impl calls this function doesn't deadlock.
 pub fn eprintln_error<E>(error: &E)
 where
-    E: Error,
+    E: Error + 'static,
+{
+    eprintln_error_with_verbosity(error, Verbosity::Normal)
+}
+
+/// Like [`eprintln_error`], but writes the trace according to the verbosity (see [`writeln_error_with_verbosity`]).
+pub fn eprintln_error_with_verbosity<E>(error: &E, verbosity: Verbosity)
+where
+    E: Error + 'static,
 {
     use WritelnErrorToWriterAndFileError::*;
-    let mut stderr = stderr().lock();
-    let result = writeln_error_to_writer_and_file(error, &mut stderr);
+    let result = write_all_buffered(&mut stderr(), |buffer| writeln_error_with_verbosity(error, buffer, verbosity));
     match result {
-        Ok(()) => (),
-        Err(WriteFailed {
+        Ok(Ok(())) => (),
+        Ok(Err(WriteFailed {
             source,
-        }) => eprintln!("failed to write the error to stderr: {source:#?}"),
-        Err(WriteToNamedTempFileFailed {
+        }))
+        | Err(source) => eprintln!("failed to write the error to stderr: {source:#?}"),
+        Ok(Err(WriteToNamedTempFileFailed {
             source,
-        }) => eprintln!("failed to write the error to the report file: {source:#?}"),
+        })) => eprintln!("failed to write the error to the report file: {source:#?}"),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::functions::writeln_error::tests::JsonSchemaNewError::{InvalidInput, InvalidValues};
-    use crate::{ErrVec, ErrorDisplayer};
+    use crate::{ErrVec, ErrorDisplayer, Quiet, error_parsed_tree, parse_error_trace, write_to_named_temp_file, writeln_error_concise, writeln_error_deduped, writeln_error_hyperlinked, writeln_error_numbered, writeln_error_strict, writeln_error_to_writer, writeln_error_with_hyperlinks, writeln_error_with_types, writeln_quiet_error_to_writer};
+    use BuildError::*;
     use CliRunError::*;
     use CommandRunError::*;
+    use CompileError::*;
     use I18nRequestError::*;
     use I18nUpdateRunError::*;
     use JsonValueNewError::*;
+    use LoadConfigError::*;
+    use TypeCheckError::*;
     use UpdateRowError::*;
     use pretty_assertions::assert_eq;
     use std::error::Error;
     use thiserror::Error;
 
+    type TestResult = Result<(), Box<dyn Error>>;
+
     #[test]
     fn must_write_error() {
         let error = CommandRunFailed {
@@ -142,7 +561,691 @@ mod tests {
         assert_write_eq(&error, expected);
     }
 
-    fn assert_write_eq<E: Error>(error: &E, expected: &str) {
+    #[test]
+    fn must_write_multiline_error_at_depth_0() {
+        let error = TypeMismatch {
+            expected: "u32".to_string(),
+            found: "String".to_string(),
+        };
+        let expected = include_str!("writeln_error/fixtures/must_write_multiline_error_at_depth_0.txt");
+        assert_write_eq(&error, expected);
+    }
+
+    #[test]
+    fn must_write_multiline_error_at_depth_2() {
+        let error = BuildFailed {
+            source: CompileFailed {
+                source: TypeMismatch {
+                    expected: "u32".to_string(),
+                    found: "String".to_string(),
+                },
+            },
+        };
+        let expected = include_str!("writeln_error/fixtures/must_write_multiline_error_at_depth_2.txt");
+        assert_write_eq(&error, expected);
+    }
+
+    #[test]
+    fn must_write_multiline_error_in_err_vec() {
+        let error = CompileAllFailed {
+            source: vec![
+                CompileFailed {
+                    source: TypeMismatch {
+                        expected: "u32".to_string(),
+                        found: "String".to_string(),
+                    },
+                },
+                CompileFailed {
+                    source: TypeMismatch {
+                        expected: "bool".to_string(),
+                        found: "i64".to_string(),
+                    },
+                },
+            ]
+            .into(),
+        };
+        let expected = include_str!("writeln_error/fixtures/must_write_multiline_error_in_err_vec.txt");
+        assert_write_eq(&error, expected);
+    }
+
+    #[test]
+    fn must_not_write_trailer_in_writeln_error_to_writer() -> TestResult {
+        let error = CompileFailed {
+            source: TypeMismatch {
+                expected: "u32".to_string(),
+                found: "String".to_string(),
+            },
+        };
+        let mut actual = Vec::new();
+        writeln_error_to_writer(&error, &mut actual)?;
+        let expected = include_str!("writeln_error/fixtures/must_write_multiline_error_at_depth_1.txt");
+        assert_eq!(String::from_utf8(actual)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_deduped_error() -> TestResult {
+        let failed_row = |name: &str| I18nRequestFailed {
+            source: JsonSchemaNewFailed {
+                source: InvalidInput {
+                    input: "foo".to_string(),
+                },
+            },
+            row: Row::new(name),
+        };
+        let error = CommandRunFailed {
+            source: I18nUpdateRunFailed {
+                source: UpdateRowsFailed {
+                    source: vec![failed_row("Foo"), failed_row("Bar"), failed_row("Foo")].into(),
+                },
+            },
+        };
+        let mut actual = Vec::new();
+        writeln_error_deduped(&error, &mut actual)?;
+        let expected = include_str!("writeln_error/fixtures/must_write_deduped_error.txt");
+        assert_eq!(String::from_utf8(actual)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_deduped_nested_error() -> TestResult {
+        let failed_values = || JsonSchemaNewFailed {
+            source: InvalidValues {
+                source: vec![
+                    InvalidKey {
+                        key: "zed".to_string(),
+                    },
+                    InvalidKey {
+                        key: "zed".to_string(),
+                    },
+                ]
+                .into(),
+            },
+        };
+        let error = UpdateRowsFailed {
+            source: vec![
+                I18nRequestFailed {
+                    source: failed_values(),
+                    row: Row::new("Foo"),
+                },
+                I18nRequestFailed {
+                    source: failed_values(),
+                    row: Row::new("Foo"),
+                },
+            ]
+            .into(),
+        };
+        let mut actual = Vec::new();
+        writeln_error_deduped(&error, &mut actual)?;
+        let expected = include_str!("writeln_error/fixtures/must_write_deduped_nested_error.txt");
+        assert_eq!(String::from_utf8(actual)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_numbered_error() -> TestResult {
+        let error = CommandRunFailed {
+            source: I18nUpdateRunFailed {
+                source: UpdateRowsFailed {
+                    source: vec![
+                        I18nRequestFailed {
+                            source: JsonSchemaNewFailed {
+                                source: InvalidValues {
+                                    source: vec![
+                                        InvalidKey {
+                                            key: "zed".to_string(),
+                                        },
+                                        InvalidKey {
+                                            key: "moo".to_string(),
+                                        },
+                                    ]
+                                    .into(),
+                                },
+                            },
+                            row: Row::new("Foo"),
+                        },
+                        I18nRequestFailed {
+                            source: JsonSchemaNewFailed {
+                                source: InvalidInput {
+                                    input: "bar".to_string(),
+                                },
+                            },
+                            row: Row::new("Bar"),
+                        },
+                    ]
+                    .into(),
+                },
+            },
+        };
+        let mut actual = Vec::new();
+        writeln_error_numbered(&error, &mut actual)?;
+        let expected = include_str!("writeln_error/fixtures/must_write_numbered_error.txt");
+        assert_eq!(String::from_utf8(actual)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_error_with_types() -> TestResult {
+        let error = CommandRunFailed {
+            source: I18nUpdateRunFailed {
+                source: UpdateRowsFailed {
+                    source: vec![I18nRequestFailed {
+                        source: JsonSchemaNewFailed {
+                            source: InvalidInput {
+                                input: "foo".to_string(),
+                            },
+                        },
+                        row: Row::new("Foo"),
+                    }]
+                    .into(),
+                },
+            },
+        };
+        let mut actual = Vec::new();
+        writeln_error_with_types(&error, &mut actual)?;
+        let expected = include_str!("writeln_error/fixtures/must_write_error_with_types.txt");
+        assert_eq!(String::from_utf8(actual)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_numbered_multiline_error() -> TestResult {
+        let error = BuildFailed {
+            source: CompileFailed {
+                source: TypeMismatch {
+                    expected: "u32".to_string(),
+                    found: "String".to_string(),
+                },
+            },
+        };
+        let mut actual = Vec::new();
+        writeln_error_numbered(&error, &mut actual)?;
+        let expected = include_str!("writeln_error/fixtures/must_write_numbered_multiline_error.txt");
+        assert_eq!(String::from_utf8(actual)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_debug_report_to_temp_file() -> TestResult {
+        let error = UpdateRowsFailed {
+            source: vec![I18nRequestFailed {
+                source: JsonSchemaNewFailed {
+                    source: InvalidValues {
+                        source: vec![InvalidKey {
+                            key: "zed".to_string(),
+                        }]
+                        .into(),
+                    },
+                },
+                row: Row::new("Foo"),
+            }]
+            .into(),
+        };
+        let (_file, path_buf) = write_to_named_temp_file(|file| write!(file, "{error:#?}"))?;
+        let actual = std::fs::read_to_string(&path_buf)?;
+        std::fs::remove_file(&path_buf)?;
+        assert_eq!(actual, format!("{error:#?}"));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_hyperlink_paths() -> TestResult {
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let mut actual = Vec::new();
+        writeln_error_with_hyperlinks(&error, &mut actual)?;
+        let actual = String::from_utf8(actual)?;
+        assert!(actual.contains("'\x1b]8;;file:///etc/errgonomic/config.toml\x1b\\/etc/errgonomic/config.toml\x1b]8;;\x1b\\'"), "{actual:?}");
+        let mut file = tempfile::tempfile()?;
+        writeln_error_hyperlinked(&error, &mut file)?;
+        let mut plain = String::new();
+        std::io::Seek::rewind(&mut file)?;
+        std::io::Read::read_to_string(&mut file, &mut plain)?;
+        assert_eq!(plain, ErrorDisplayer(&error).to_string() + "\n");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_hyperlink_paths_of_children() -> TestResult {
+        let error = LoadConfigsFailed {
+            source: ErrVec::new(["/etc/app.toml", "/etc/app.d/local.toml"].map(|path| crate::PathIoError::new(path, std::io::Error::from(std::io::ErrorKind::NotFound)))),
+        };
+        let mut actual = Vec::new();
+        writeln_error_with_hyperlinks(&error, &mut actual)?;
+        let actual = String::from_utf8(actual)?;
+        assert!(actual.contains("'\x1b]8;;file:///etc/app.toml\x1b\\/etc/app.toml\x1b]8;;\x1b\\'"), "{actual:?}");
+        assert!(actual.contains("'\x1b]8;;file:///etc/app.d/local.toml\x1b\\/etc/app.d/local.toml\x1b]8;;\x1b\\'"), "{actual:?}");
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum LoadConfigError {
+        #[error("failed to load the config")]
+        LoadConfigFailed { source: crate::PathIoError },
+        #[error("failed to load the configs")]
+        LoadConfigsFailed { source: ErrVec<crate::PathIoError> },
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_write_build_info() -> TestResult {
+        use super::{ReportSettings, writeln_error_to_writer_and_file_with_settings};
+        use crate::Verbosity;
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let write = |build_info: Option<&crate::BuildInfo>| {
+            let mut output = Vec::new();
+            let settings = ReportSettings {
+                build_info: build_info.cloned(),
+                inline_threshold: 0,
+                ..ReportSettings::default()
+            };
+            writeln_error_to_writer_and_file_with_settings(&error, &mut output, Verbosity::Normal, &settings)?;
+            let output = String::from_utf8(output)?;
+            let report_path = output
+                .lines()
+                .find_map(|line| line.strip_prefix("less "))
+                .ok_or("the report path must be written")?
+                .to_string();
+            let report = std::fs::read_to_string(&report_path)?;
+            std::fs::remove_file(report_path)?;
+            Ok::<_, Box<dyn Error>>((output, report))
+        };
+        let (output, report) = write(None)?;
+        assert!(
+            output
+                .lines()
+                .last()
+                .is_some_and(|line| line.starts_with("less ")),
+            "{output}"
+        );
+        assert!(report.starts_with("LoadConfigFailed"), "{report}");
+        let build_info = crate::BuildInfo::new("myapp", "1.4.2").with_git_hash("abc1234");
+        let (output, report) = write(Some(&build_info))?;
+        let trailer = output.lines().last().unwrap_or_default();
+        assert!(trailer.starts_with("myapp 1.4.2 (abc1234)"), "{trailer}");
+        assert!(trailer.ends_with(&format!(", pid {}", std::process::id())), "{trailer}");
+        assert!(report.starts_with("app: myapp\nversion: 1.4.2\ngit hash: abc1234\n"), "{report}");
+        assert!(report.contains(&format!("pid: {}\n\nLoadConfigFailed", std::process::id())), "{report}");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_scrub_paths_in_trace_and_report() -> TestResult {
+        use super::{ReportSettings, writeln_error_to_writer_and_file_with_settings};
+        use crate::{PathScrubber, ScrubLevel, Verbosity};
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/home/alice/.config/app.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let scrubber = PathScrubber::new(Some("/home/alice".to_string()), Some("alice".to_string()), ScrubLevel::Home);
+        let mut output = Vec::new();
+        let settings = ReportSettings {
+            scrubber: Some(scrubber),
+            inline_threshold: 0,
+            ..ReportSettings::default()
+        };
+        writeln_error_to_writer_and_file_with_settings(&error, &mut output, Verbosity::Normal, &settings)?;
+        let output = String::from_utf8(output)?;
+        let report_path = output
+            .lines()
+            .find_map(|line| line.strip_prefix("less "))
+            .ok_or("the report path must be written")?
+            .to_string();
+        let report = std::fs::read_to_string(&report_path)?;
+        std::fs::remove_file(report_path)?;
+        assert!(output.contains("'~/.config/app.toml'"), "{output}");
+        assert!(!output.contains("/home/alice"), "{output}");
+        assert!(report.contains("\"~/.config/app.toml\""), "{report}");
+        assert!(!report.contains("/home/alice"), "{report}");
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_small_report_inline() -> TestResult {
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let mut output = Vec::new();
+        super::writeln_error_with_inline_threshold(&error, &mut output, crate::REPORT_INLINE_THRESHOLD)?;
+        let output = String::from_utf8(output)?;
+        let (trace, report) = output
+            .split_once("\nFull error report:\n")
+            .ok_or("the report must be written inline")?;
+        assert_eq!(trace, ErrorDisplayer(&error).to_string() + "\n");
+        assert_eq!(report, format!("{error:#?}\n"));
+        assert!(!output.contains("See the full error report"), "{output}");
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_large_report_to_file() -> TestResult {
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let report_len = format!("{error:#?}").len();
+        let mut output = Vec::new();
+        super::writeln_error_with_inline_threshold(&error, &mut output, report_len - 1)?;
+        let output = String::from_utf8(output)?;
+        let (_trace, trailer) = output
+            .split_once("\nSee the full error report:\n")
+            .ok_or("the report path must be written")?;
+        let report_path = trailer
+            .trim_end()
+            .rsplit(' ')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let report = std::fs::read_to_string(&report_path)?;
+        std::fs::remove_file(report_path)?;
+        assert_eq!(report, format!("{error:#?}"));
+        assert!(!output.contains("Full error report:"), "{output}");
+        Ok(())
+    }
+
+    #[test]
+    fn must_report_panicking_report_writer() -> TestResult {
+        use super::{ReportSettings, writeln_error_to_writer_and_file_with_settings};
+        use crate::Verbosity;
+        use std::path::PathBuf;
+        fn write_panicking(_write: &mut dyn FnMut(&mut dyn std::io::Write) -> std::io::Result<()>) -> Result<PathBuf, crate::WriteToNamedTempFileError> {
+            panic!("the temp dir is gone")
+        }
+        let error = std::io::Error::other("disk full");
+        let settings = ReportSettings {
+            inline_threshold: 0,
+            write_file: write_panicking,
+            ..ReportSettings::default()
+        };
+        let mut output = Vec::new();
+        writeln_error_to_writer_and_file_with_settings(&error, &mut output, Verbosity::Normal, &settings)?;
+        assert_eq!(String::from_utf8(output)?, "- disk full\n\n(error report could not be written because the report writer has panicked)\n");
+        Ok(())
+    }
+
+    #[test]
+    fn must_not_wait_for_slow_report_writer() -> TestResult {
+        use super::{ReportSettings, writeln_error_to_writer_and_file_with_settings};
+        use crate::Verbosity;
+        use std::path::PathBuf;
+        use std::sync::{Mutex, PoisonError};
+        use std::time::Duration;
+        static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        static LATE_REPORT: Mutex<Option<PathBuf>> = Mutex::new(None);
+        fn push_event(event: &str) {
+            EVENTS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(event.to_string())
+        }
+        /// A writer that records the order of the writes and the flushes
+        struct EventWriter(Vec<u8>);
+        impl std::io::Write for EventWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                push_event("write");
+                self.0.write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                push_event("flush");
+                Ok(())
+            }
+        }
+        fn write_slowly(write: &mut dyn FnMut(&mut dyn std::io::Write) -> std::io::Result<()>) -> Result<PathBuf, crate::WriteToNamedTempFileError> {
+            push_event("report started");
+            std::thread::sleep(Duration::from_millis(200));
+            let (_file, path_buf) = write_to_named_temp_file(write)?;
+            *LATE_REPORT.lock().unwrap_or_else(PoisonError::into_inner) = Some(path_buf.clone());
+            Ok(path_buf)
+        }
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let settings = ReportSettings {
+            inline_threshold: 0,
+            timeout: Duration::from_millis(10),
+            write_file: write_slowly,
+            ..ReportSettings::default()
+        };
+        let mut writer = EventWriter(Vec::new());
+        writeln_error_to_writer_and_file_with_settings(&error, &mut writer, Verbosity::Normal, &settings)?;
+        let output = String::from_utf8(writer.0)?;
+        assert_eq!(output, format!("{}\n\n(error report could not be written within 10ms)\n", ErrorDisplayer(&error)));
+        let events = EVENTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let flush_index = events
+            .iter()
+            .position(|event| event == "flush")
+            .ok_or("the trace must be flushed")?;
+        let report_index = events
+            .iter()
+            .position(|event| event == "report started")
+            .ok_or("the report must be started")?;
+        assert!(flush_index < report_index, "{events:?}");
+        assert!(events[..flush_index].iter().all(|event| event == "write"), "{events:?}");
+        // the late report is still written in full
+        let late_report = (0..100)
+            .find_map(|_| {
+                std::thread::sleep(Duration::from_millis(10));
+                LATE_REPORT
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .take()
+            })
+            .ok_or("the late report must be written within 1s")?;
+        let report = std::fs::read_to_string(&late_report)?;
+        std::fs::remove_file(late_report)?;
+        assert_eq!(report, format!("{error:#?}"));
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_error_by_verbosity_env_var() -> TestResult {
+        use super::{verbosity_from_env_var, writeln_error_auto_with};
+        use crate::{RenderedError, Verbosity};
+        use std::sync::OnceLock;
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let write = |verbosity: Verbosity| {
+            let mut output = Vec::new();
+            RenderedError::new(&error)
+                .writeln(&mut output, verbosity)
+                .expect("always succeeds because writing to a Vec never fails");
+            String::from_utf8_lossy(&output).into_owned()
+        };
+        let write_with_env_var = |value: Option<&str>| write(verbosity_from_env_var(value));
+        let concise = "error: failed to load the config (root cause: entity not found)\n";
+        let normal = "- failed to load the config\n- I/O operation failed for '/etc/errgonomic/config.toml'\n- entity not found\n";
+        let detailed = "- [LoadConfigFailed] failed to load the config\n- [PathIoError] I/O operation failed for '/etc/errgonomic/config.toml'\n- [Kind] entity not found\n";
+        assert_eq!(write_with_env_var(None), concise);
+        assert_eq!(write_with_env_var(Some("0")), concise);
+        assert_eq!(write_with_env_var(Some("false")), concise);
+        assert_eq!(write_with_env_var(Some("1")), normal);
+        assert_eq!(write_with_env_var(Some("true")), normal);
+        assert_eq!(write_with_env_var(Some("2")), detailed);
+        // the variable is read once, so the output doesn't change after the first call
+        let verbosity = OnceLock::new();
+        let mut first = Vec::new();
+        writeln_error_auto_with(&error, &mut first, &verbosity, || verbosity_from_env_var(Some("2")))?;
+        let mut second = Vec::new();
+        writeln_error_auto_with(&error, &mut second, &verbosity, || verbosity_from_env_var(Some("0")))?;
+        assert_eq!(String::from_utf8(first)?, detailed);
+        assert_eq!(String::from_utf8(second)?, detailed);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_to_multiple_sinks() -> TestResult {
+        use crate::{TraceFormat, writeln_error_tee};
+        let error = LoadConfigFailed {
+            source: crate::PathIoError::new("/etc/errgonomic/config.toml", std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        let mut human = Vec::new();
+        let mut strict = Vec::new();
+        let mut human_copy = Vec::new();
+        writeln_error_tee(
+            &error,
+            &mut [
+                (&mut human, TraceFormat::Human),
+                (&mut strict, TraceFormat::Strict),
+                (&mut human_copy, TraceFormat::Human),
+            ],
+        )?;
+        let mut expected_human = Vec::new();
+        writeln_error_to_writer(&error, &mut expected_human)?;
+        let mut expected_strict = Vec::new();
+        writeln_error_strict(&error, &mut expected_strict)?;
+        assert_eq!(human, expected_human);
+        assert_eq!(human_copy, expected_human);
+        assert_eq!(strict, expected_strict);
+        assert_ne!(human, strict);
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_with_verbosity() -> TestResult {
+        use super::{ReportSettings, writeln_error_to_writer_and_file_with_settings};
+        use crate::Verbosity;
+        let error = CommandRunFailed {
+            source: I18nUpdateRunFailed {
+                source: UpdateRowsFailed {
+                    source: vec![
+                        I18nRequestFailed {
+                            source: JsonSchemaNewFailed {
+                                source: InvalidInput {
+                                    input: "foo".to_string(),
+                                },
+                            },
+                            row: Row::new("Foo"),
+                        },
+                        I18nRequestFailed {
+                            source: RequestSendFailed {
+                                source: tokio::io::Error::new(tokio::io::ErrorKind::AddrNotAvailable, "server at 239.143.73.1 did not respond"),
+                            },
+                            row: Row::new("Bar"),
+                        },
+                    ]
+                    .into(),
+                },
+            },
+        };
+        let write = |verbosity: Verbosity| {
+            let mut output = Vec::new();
+            let settings = ReportSettings {
+                inline_threshold: 0,
+                ..ReportSettings::default()
+            };
+            writeln_error_to_writer_and_file_with_settings(&error, &mut output, verbosity, &settings)?;
+            let output = String::from_utf8(output)?;
+            let (trace, trailer) = output
+                .split_once("\nSee the full error report:\n")
+                .ok_or("the report path must be written")?;
+            let report_path = trailer
+                .trim_end()
+                .rsplit(' ')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let report = std::fs::read_to_string(&report_path)?;
+            std::fs::remove_file(report_path)?;
+            assert_eq!(report, crate::RenderedError::new(&error).debug.to_string());
+            Ok::<_, Box<dyn Error>>(trace.to_string())
+        };
+        assert_eq!(write(Verbosity::Concise)?, "error: failed to run CLI command (root causes: input must be a JSON object; server at 239.143.73.1 did not respond)\n");
+        assert_eq!(write(Verbosity::Normal)?, include_str!("writeln_error/fixtures/must_write_error.txt"));
+        let mut detailed = Vec::new();
+        writeln_error_with_types(&error, &mut detailed)?;
+        assert_eq!(write(Verbosity::Detailed)?, String::from_utf8(detailed)?);
+        let mut concise = Vec::new();
+        writeln_error_concise(
+            &RequestSendFailed {
+                source: tokio::io::Error::other("timed out"),
+            },
+            &mut concise,
+        )?;
+        assert_eq!(String::from_utf8(concise)?, "error: failed to send a request (root cause: timed out)\n");
+        let mut concise = Vec::new();
+        writeln_error_concise(&std::io::Error::other("timed out"), &mut concise)?;
+        assert_eq!(String::from_utf8(concise)?, "error: timed out\n");
+        Ok(())
+    }
+
+    #[test]
+    fn must_round_trip_strict_trace() -> TestResult {
+        let update_rows_failed = || UpdateRowsFailed {
+            source: vec![
+                I18nRequestFailed {
+                    source: JsonSchemaNewFailed {
+                        source: InvalidInput {
+                            input: "- looks like a bullet\n* and another one".to_string(),
+                        },
+                    },
+                    row: Row::new("Foo"),
+                },
+                I18nRequestFailed {
+                    source: JsonSchemaNewFailed {
+                        source: InvalidValues {
+                            source: vec![InvalidKey {
+                                key: "zed".to_string(),
+                            }]
+                            .into(),
+                        },
+                    },
+                    row: Row::new("Bar"),
+                },
+            ]
+            .into(),
+        };
+        let compile_all_failed = CompileAllFailed {
+            source: vec![CompileFailed {
+                source: TypeMismatch {
+                    expected: "u32".to_string(),
+                    found: "String".to_string(),
+                },
+            }]
+            .into(),
+        };
+        let assert_round_trip = |error: &(dyn Error + 'static)| {
+            let mut text = Vec::new();
+            writeln_error_strict(error, &mut text)?;
+            let text = String::from_utf8(text)?;
+            assert_eq!(parse_error_trace(&text)?, error_parsed_tree(error), "{text}");
+            Ok::<_, Box<dyn Error>>(())
+        };
+        assert_round_trip(&CommandRunFailed {
+            source: I18nUpdateRunFailed {
+                source: update_rows_failed(),
+            },
+        })?;
+        assert_round_trip(&compile_all_failed)?;
+        Ok(())
+    }
+
+    #[test]
+    fn must_write_quiet_error_without_report() -> TestResult {
+        let error = LookupError::KeyNotFound {
+            key: "x".repeat(100),
+            renders: Default::default(),
+        };
+        let mut actual = Vec::new();
+        writeln_quiet_error_to_writer(&error, &mut actual)?;
+        assert_eq!(String::from_utf8(actual)?, format!("key not found: {}\n", "x".repeat(100)));
+        let LookupError::KeyNotFound {
+            renders,
+            ..
+        } = &error;
+        assert_eq!(renders.get(), 1);
+        Ok(())
+    }
+
+    fn assert_write_eq<E: Error + 'static>(error: &E, expected: &str) {
         use std::fmt::Write;
         let mut actual = String::new();
         let displayer = ErrorDisplayer(error);
@@ -197,6 +1300,39 @@ mod tests {
         InvalidKey { key: String },
     }
 
+    #[derive(Error, Debug)]
+    pub enum BuildError {
+        #[error("failed to build the project")]
+        BuildFailed { source: CompileError },
+        #[error("failed to compile {len} files", len = source.len())]
+        CompileAllFailed { source: ErrVec<CompileError> },
+    }
+
+    #[derive(Error, Debug)]
+    pub enum CompileError {
+        #[error("failed to compile the file")]
+        CompileFailed { source: TypeCheckError },
+    }
+
+    #[derive(Error, Debug)]
+    pub enum TypeCheckError {
+        #[error("mismatched types\nexpected: {expected}\nfound: {found}")]
+        TypeMismatch { expected: String, found: String },
+    }
+
+    #[derive(Error, Debug)]
+    pub enum LookupError {
+        #[error(fmt = fmt_key_not_found)]
+        KeyNotFound { key: String, renders: core::cell::Cell<usize> },
+    }
+
+    impl Quiet for LookupError {}
+
+    fn fmt_key_not_found(key: &str, renders: &core::cell::Cell<usize>, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        renders.set(renders.get() + 1);
+        write!(f, "key not found: {key}")
+    }
+
     #[derive(Debug)]
     pub struct Row {
         name: String,
@@ -0,0 +1,100 @@
+use crate::{AsDynError, ParsedTree, error_parsed_tree};
+use std::io;
+use std::io::Write;
+
+/// Writes one JSON object per line for every leaf error of the tree (JSON Lines).
+///
+/// Every object contains the `path` (the indexes of the children of the aggregates on the way from the top error to the leaf), the `message` of the leaf, and the `context` (the messages of the errors on the way from the top error to the leaf).
+/// This is useful for the aggregates with thousands of children: the output can be streamed and grepped.
+pub fn writeln_error_jsonl<E: AsDynError + ?Sized>(error: &E, writer: &mut dyn Write) -> io::Result<()> {
+    let tree = error_parsed_tree(error);
+    write_leaves(&tree, &mut Vec::new(), &mut Vec::new(), writer)
+}
+
+fn write_leaves<'a>(tree: &'a ParsedTree, path: &mut Vec<usize>, context: &mut Vec<&'a str>, writer: &mut dyn Write) -> io::Result<()> {
+    if tree.children.is_empty() && tree.source.is_none() {
+        let line = serde_json::json!({
+            "path": path,
+            "message": tree.message,
+            "context": context,
+        });
+        return writeln!(writer, "{line}");
+    }
+    context.push(&tree.message);
+    tree.children
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, child)| {
+            path.push(index);
+            let result = write_leaves(child, path, context, writer);
+            path.pop();
+            result
+        })?;
+    if let Some(source) = &tree.source {
+        write_leaves(source, path, context, writer)?;
+    }
+    context.pop();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrVecWithContext, writeln_error_jsonl};
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use thiserror::Error;
+
+    #[test]
+    fn must_write_leaves_with_paths() {
+        let error = SyncError::SyncFailed {
+            source: ErrVec::new([
+                ShardError::ShardFailed {
+                    source: ErrVec::new([
+                        io::Error::other("timed out"),
+                        io::Error::other("connection reset"),
+                    ]),
+                },
+                ShardError::ShardFailed {
+                    source: ErrVec::new([io::Error::other("disk full")]),
+                },
+            ]),
+        };
+        let mut actual = Vec::new();
+        writeln_error_jsonl(&error, &mut actual).unwrap();
+        let expected = [
+            r#"{"context":["failed to sync","encountered 2 errors","failed to sync the shard","encountered 2 errors"],"message":"timed out","path":[0,0]}"#,
+            r#"{"context":["failed to sync","encountered 2 errors","failed to sync the shard","encountered 2 errors"],"message":"connection reset","path":[0,1]}"#,
+            r#"{"context":["failed to sync","encountered 2 errors","failed to sync the shard","encountered 1 error"],"message":"disk full","path":[1,0]}"#,
+        ];
+        assert_eq!(String::from_utf8(actual).unwrap(), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn must_write_aggregate_context() {
+        let error = ImportError::ImportFailed {
+            source: ErrVec::new([io::Error::other("disk full")]).with_context([("dir", "/data/in"), ("tenant", "acme")]),
+        };
+        let mut actual = Vec::new();
+        writeln_error_jsonl(&error, &mut actual).unwrap();
+        let expected = r#"{"context":["failed to import","encountered 1 error [dir=/data/in, tenant=acme]"],"message":"disk full","path":[0]}"#;
+        assert_eq!(String::from_utf8(actual).unwrap(), format!("{expected}\n"));
+    }
+
+    #[derive(Error, Debug)]
+    enum ImportError {
+        #[error("failed to import")]
+        ImportFailed { source: ErrVecWithContext<io::Error> },
+    }
+
+    #[derive(Error, Debug)]
+    enum SyncError {
+        #[error("failed to sync")]
+        SyncFailed { source: ErrVec<ShardError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum ShardError {
+        #[error("failed to sync the shard")]
+        ShardFailed { source: ErrVec<io::Error> },
+    }
+}
@@ -132,7 +132,14 @@
 //! * [`handle!`] instead of [`Result::map_err`]
 //! * [`handle_opt!`] instead of [`Option::ok_or`] and [`Option::ok_or_else`]
 //! * [`handle_bool!`] instead of `if condition { return Err(...) }`
+//! * [`validate!`] instead of multiple [`handle_bool!`] calls when all failed checks must be reported at once
+//! * [`box_err!`] instead of `.map_err(|e| Box::new(e) as Box<dyn Error>)` (only in the functions that genuinely return boxed errors)
+//! * [`try_from_impl!`] instead of writing `impl TryFrom<A> for B` by hand (use [`convert!`] and [`convert_vec!`] in its body)
+//! * [`try_handle!`], [`try_handle_opt!`] and [`try_handle_bool!`] instead of [`handle!`], [`handle_opt!`] and [`handle_bool!`] in the closures passed to iterator adapters (they evaluate to a `Result` instead of returning early)
 //! * [`handle_iter!`] instead of code that handles errors in iterators
+//! * [`handle_iter_non_empty!`] instead of [`handle_iter!`] if the input must not be empty
+//! * [`handle_iter_unit!`] instead of [`handle_iter!`] for the results of the unit type (doesn't collect the `Ok` values)
+//! * [`handle_iter_first!`] instead of [`handle_iter!`] if only the first error is needed (stops consuming the iterator at the first error)
 //! * [`handle_iter_of_refs!`] instead of code that handles errors in iterators of references (where the values are still being owned by the underlying collection)
 //! * [`handle_into_iter!`] instead of code that handles errors in collections that implement [`IntoIterator`] (including [`Vec`] and [`HashMap`](std::collections::HashMap)
 //!
@@ -168,10 +175,40 @@ extern crate core;
 
 mod macros;
 
+/// Internal re-exports used by the macro expansions.
+///
+/// The macros refer to these items via `$crate::__private` instead of the prelude, so the expansions work in `#![no_implicit_prelude]` modules, in `no_std` crates without `extern crate alloc`, and in crates that shadow `Ok` / `Err` / `Some` / `None`.
+/// The expansions don't require a newer Rust version than the `rust-version` of this crate (checked by `mise run test:msrv`).
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+    pub use alloc::format;
+    pub use alloc::vec::Vec;
+    pub use core::convert::{From, Into, TryFrom};
+    pub use core::error::Error;
+    pub use core::fmt;
+    pub use core::iter::{IntoIterator, Iterator, zip};
+    pub use core::option::Option;
+    pub use core::option::Option::{None, Some};
+    pub use core::result::Result;
+    pub use core::result::Result::{Err, Ok};
+    pub use core::{compile_error, concat, file, line, stringify};
+    #[cfg(feature = "http")]
+    pub use http;
+    #[cfg(feature = "testing")]
+    pub use serde_json;
+    #[cfg(feature = "tonic")]
+    pub use tonic;
+}
+
 mod types;
 
 pub use types::*;
 
+mod traits;
+
+pub use traits::*;
+
 mod functions;
 
 pub use functions::*;
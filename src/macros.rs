@@ -4,26 +4,384 @@
 /// In addition, this macro captures the original error in the `source` variable, and sets it as the `source` key of the error variant
 ///
 /// Note: [`handle!`](crate::handle) assumes that your error variant is a struct variant
+///
+/// Use `Variant @ field` to put the original error into a field with a different name (e.g. `handle!(result, ReadFailed @ cause, path)` for the legacy enums that use `cause` or `inner` instead of `source`).
+/// The same syntax is supported by [`map_err!`](crate::map_err), [`handle_iter!`](crate::handle_iter) and [`handle_iter_of_refs!`](crate::handle_iter_of_refs).
 #[macro_export]
 macro_rules! handle {
+    ($result:expr, $variant:ident @ $source:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($variant {
+                $source: $crate::__private::Into::into(source),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $crate::handle!($result, $variant @ source $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but also sets the `location` field to the call site (e.g. `src/main.rs:42`).
+///
+/// The location is a `&'static str` built at compile time, so it's cheaper than a backtrace and always available (even in release builds).
+/// The error variant must have a `location: &'static str` field.
+#[macro_export]
+macro_rules! handle_loc {
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $crate::handle!($result, $variant @ source, location: $crate::__private::concat!($crate::__private::file!(), ":", $crate::__private::line!()) $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but sets the `source` field to the output of `$map` (a closure or a function that is called with the original error) instead of calling `.into()`.
+///
+/// `$map` is evaluated only if the result is [`Err`].
+#[macro_export]
+macro_rules! handle_map_source {
+    ($result:expr, $variant:ident, $map:expr $(, $arg:ident$(: $value:expr)?)*) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($variant {
+                source: ($map)(source),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// Like [`handle!`](crate::handle), but wraps the source in [`Deadlined`](crate::Deadlined), which records the time remaining until the ambient [`Deadline`](crate::Deadline).
+///
+/// The `source` field of the variant must have the [`Deadlined`](crate::Deadlined) type. Use [`deadline_exceeded`](crate::deadline_exceeded) to check whether the error occurred after the deadline had passed.
+#[macro_export]
+macro_rules! handle_deadline {
+    ($result:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle_map_source!($result, $variant, $crate::Deadlined::ambient $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but wraps the [`io::Error`](std::io::Error) in a [`PathIoError`](crate::PathIoError) with the provided path (which is converted only if the result is [`Err`]).
+///
+/// The `source` field of the variant must have the [`PathIoError`](crate::PathIoError) type: `handle_io!(fs::read_to_string(&path), ReadFailed, path)`.
+#[macro_export]
+macro_rules! handle_io {
+    ($result:expr, $variant:ident, $path:expr $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle_map_source!($result, $variant, |source| $crate::PathIoError::new($path, source) $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Calls [`collect_unique`](crate::collect_unique) and returns the `$variant` error if there are duplicate keys.
+///
+/// The `source` field of the variant must have the [`DuplicateKeysError<K, V>`](crate::DuplicateKeysError) type: `let users = handle_unique!(users.into_iter().map(|user| (user.email.clone(), user)), UsersInvalid);`.
+#[macro_export]
+macro_rules! handle_unique {
+    ($iter:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($crate::collect_unique($iter), $variant $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Calls [`walk_dir_collect`](crate::walk_dir_collect) and returns the `$variant` error if any entry has failed.
+///
+/// The `source` field of the variant must have the `ErrVec<WalkDirError<E>>` type (see [`WalkDirError`](crate::WalkDirError)): `handle_walk!(&root, |path| fs::read_to_string(path), WalkFailed, root)`.
+#[macro_export]
+macro_rules! handle_walk {
+    ($root:expr, $f:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($crate::walk_dir_collect($root, $f), $variant $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but wraps the source in [`Resumable`](crate::Resumable) with the state from `$state` (which is evaluated only if the result is [`Err`]).
+///
+/// The `source` field of the variant must have the `Resumable<E, S>` type: `handle_resumable!(import_item(item), ImportItemFailed, Checkpoint { index }, index)`.
+#[macro_export]
+macro_rules! handle_resumable {
+    ($result:expr, $variant:ident, $state:expr $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle_map_source!($result, $variant, |source| $crate::Resumable::new(source, $state) $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but flattens the result of awaiting a spawned task (`Result<Result<T, E>, JoinError>`) via [`TaskError::from_join_result`](crate::TaskError::from_join_result).
+///
+/// The `source` field of the variant must have the `TaskError<E>` type (see [`TaskError`](crate::TaskError)): `handle_join!(tokio::spawn(fetch(url)).await, FetchFailed, url)`.
+#[cfg(feature = "tokio")]
+#[macro_export]
+macro_rules! handle_join {
+    ($result:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($crate::TaskError::from_join_result($result), $variant $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but takes the error branch with the injected error if a fault is armed for the `$label` (see [`inject_fault`](crate::inject_fault)).
+///
+/// `$result` is not evaluated if a fault is armed. This is useful for exercising the rare error branches in tests: `handle_fi!("write_chunk", file.write_all(chunk), WriteChunkFailed, index)`.
+/// Without the `fault-injection` feature, this macro expands to [`handle!`](crate::handle) (the `$label` is not evaluated).
+#[cfg(feature = "fault-injection")]
+#[macro_export]
+macro_rules! handle_fi {
+    ($label:expr, $result:expr, $($rest:tt)*) => {
+        $crate::handle!(
+            match $crate::take_fault($label) {
+                $crate::__private::Some(error) => $crate::__private::Err(error),
+                $crate::__private::None => $result,
+            },
+            $($rest)*
+        )
+    };
+}
+
+/// Like [`handle!`](crate::handle), but takes the error branch with the injected error if a fault is armed for the `$label` (see `inject_fault` with the `fault-injection` feature).
+///
+/// Without the `fault-injection` feature, this macro expands to [`handle!`](crate::handle) (the `$label` is not evaluated).
+#[cfg(not(feature = "fault-injection"))]
+#[macro_export]
+macro_rules! handle_fi {
+    ($label:expr, $($rest:tt)*) => {
+        $crate::handle!($($rest)*)
+    };
+}
+
+/// Calls [`MustFinalize::finalize`](crate::MustFinalize::finalize) with `$f` and returns the `$variant` error if the finalization has failed.
+///
+/// The guard is consumed in both cases, so it is never recorded as [`NotFinalized`](crate::NotFinalized): `handle_finalize!(writer, |mut writer| writer.flush(), FlushFailed, path)`.
+#[macro_export]
+macro_rules! handle_finalize {
+    ($guard:expr, $f:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($crate::MustFinalize::finalize($guard, $f), $variant $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but wraps the [`anyhow::Error`] in [`AnyhowError`](crate::AnyhowError) (which implements `Error`) before calling `.into()`.
+///
+/// The `source` field of the variant may have the [`AnyhowError`](crate::AnyhowError) or `Box<dyn Error + Send + Sync>` type. If the field has the `anyhow::Error` type, use [`handle!`](crate::handle) instead.
+#[cfg(feature = "anyhow")]
+#[macro_export]
+macro_rules! handle_anyhow {
+    ($result:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle_map_source!($result, $variant, |source| $crate::__private::Into::into(<$crate::AnyhowError as $crate::__private::From<_>>::from(source)) $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but also sets the `seq: u64` field to the next number of the global sequence (see [`next_error_seq`](crate::next_error_seq)).
+///
+/// This is useful for sorting the errors from multiple threads by the order of their occurrence.
+#[macro_export]
+macro_rules! handle_seq {
+    ($result:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($result, $variant, seq: $crate::next_error_seq() $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but also sets the `request_id: Option<RequestId>` field to the ambient [`RequestId`](crate::RequestId).
+#[macro_export]
+macro_rules! handle_id {
+    ($result:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($result, $variant, request_id: $crate::RequestId::current() $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle!`](crate::handle), but also sets the `env: Vec<(String, Option<OsStrDisplay>)>` field (see [`OsStrDisplay`](crate::OsStrDisplay)) to the values of the named environment variables (which are read only if the result is [`Err`]).
+///
+/// This is useful for reproducing the failures that depend on the environment: `handle_env!(result, Variant, env: ["HOME", "PATH"], path)`.
+#[macro_export]
+macro_rules! handle_env {
+    ($result:expr, $variant:ident, env: [$($name:expr),* $(,)?] $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle!($result, $variant, env: $crate::read_env_vars(&[$($name),*]) $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Reads the environment variable via [`var_os`](std::env::var_os), returning the variant if the variable is not present or is not valid UTF-8.
+///
+/// Evaluates to the `String` value. The variant must have the `name: String` and `value: Option<OsStrDisplay>` fields (in addition to the fields passed as arguments): the `value` is `None` if the variable is not present, or the raw value if it is not valid UTF-8 (see [`OsStrDisplay`](crate::OsStrDisplay)).
+#[macro_export]
+macro_rules! handle_os_var {
+    ($name:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {{
+        let name = $name;
+        match $crate::read_os_var(&name) {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(value) => return $crate::__private::Err($variant {
+                name: $crate::__private::Into::into(name),
+                value,
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    }};
+}
+
+/// Handles the result of sending a message to a channel, moving the unsent message back into the `message` field of the variant.
+///
+/// The `source` field is set to [`ChannelClosed`](crate::ChannelClosed), because the payload of the send error is moved into the `message` field (so the message is not lost, and it doesn't have to be cloned before sending).
+/// Supports the channels from `std`, `tokio` (with the `tokio` feature) and `crossbeam-channel` (with the `crossbeam` feature) via [`RecoverMessage`](crate::RecoverMessage).
+#[macro_export]
+macro_rules! handle_send {
+    ($result:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(error) => return $crate::__private::Err($variant {
+                source: $crate::__private::Into::into($crate::ChannelClosed),
+                message: $crate::RecoverMessage::recover_message(error),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// Handles the result of receiving a message from a channel, returning `$empty_variant` if the channel is empty (or the receive timed out) and `$disconnected_variant` if the channel is disconnected.
+///
+/// Both variants receive the same arguments. Supports the receive errors that implement [`RecvErrorKind`](crate::RecvErrorKind).
+#[macro_export]
+macro_rules! handle_recv {
+    ($result:expr, $empty_variant:ident, $disconnected_variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(error) => {
+                if $crate::RecvErrorKind::is_disconnected(&error) {
+                    return $crate::__private::Err($disconnected_variant {
+                        $($arg: $crate::_into!($arg$(: $value)?)),*
+                    });
+                } else {
+                    return $crate::__private::Err($empty_variant {
+                        $($arg: $crate::_into!($arg$(: $value)?)),*
+                    });
+                }
+            }
+        }
+    };
+}
+
+/// Parses a [`Sourced<String>`](crate::Sourced) variable via [`FromStr`](core::str::FromStr), keeping the provenance.
+///
+/// Evaluates to `Sourced<T>` if the value is parsed successfully.
+/// Otherwise, returns the variant with the parse error as `source` and the original `Sourced<String>` in the field that has the same name as the variable (so the error message can report where the invalid value came from).
+#[macro_export]
+macro_rules! handle_sourced_parse {
+    ($sourced:ident, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        match $sourced.value.parse() {
+            $crate::__private::Ok(value) => $crate::Sourced {
+                value,
+                provenance: $sourced.provenance,
+            },
+            $crate::__private::Err(source) => return $crate::__private::Err($variant {
+                source: $crate::__private::Into::into(source),
+                $sourced: $crate::__private::Into::into($sourced),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// Handles a [`Result`] whose error is optional (e.g. `Result<T, Option<Box<dyn Error>>>` returned from FFI wrappers, where `None` means "unknown error").
+///
+/// The `source` field of the variant must be an [`Option`]: it is set to `Some(error.into())` if the error is known, and to `None` if the error is unknown.
+#[macro_export]
+macro_rules! handle_maybe {
     ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
         match $result {
-            Ok(value) => value,
-            Err(source) => return Err($variant {
-                source: source.into(),
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($variant {
+                source: $crate::__private::Option::map(source, $crate::__private::Into::into),
                 $($arg: $crate::_into!($arg$(: $value)?)),*
             }),
         }
     };
 }
 
+/// Like [`handle!`](crate::handle), but infers the variant from the type of the source error.
+///
+/// Without fields, the error is converted via [`From`] (which is implemented by the `#[from]` attribute of `thiserror`).
+/// With fields, the error is converted via [`FromSource`](crate::FromSource) (which is implemented by [`impl_from_source!`](crate::impl_from_source)), where the fields are passed as a tuple in the order of the arguments.
+///
+/// Use it only if the variant is unambiguous (there is exactly one variant that wraps the source error type).
+#[macro_export]
+macro_rules! handle_from {
+    ($result:expr$(,)?) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($crate::__private::From::from(source)),
+        }
+    };
+    ($result:expr, $($arg:ident$(: $value:expr)?),+) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($crate::FromSource::from_source(source, ($($crate::_value!($arg$(: $value)?),)+))),
+        }
+    };
+}
+
+/// Implements [`FromSource`](crate::FromSource) for the error enum variant.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{display_path, impl_from_source};
+/// # use std::io;
+/// # use std::path::PathBuf;
+/// # use thiserror::Error;
+/// #[derive(Error, Debug)]
+/// enum ReadConfigError {
+///     #[error("failed to read the config: {path}", path = display_path(path))]
+///     ReadToStringFailed { source: io::Error, path: PathBuf },
+/// }
+///
+/// impl_from_source!(ReadConfigError, ReadToStringFailed, io::Error, { path: PathBuf });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_from_source {
+    ($error:ident, $variant:ident, $source:ty, { $($field:ident: $field_ty:ty),* $(,)? }) => {
+        impl $crate::FromSource<$source, ($($field_ty,)*)> for $error {
+            fn from_source(source: $source, ($($field,)*): ($($field_ty,)*)) -> Self {
+                Self::$variant {
+                    source,
+                    $($field),*
+                }
+            }
+        }
+    };
+}
+
 /// See also: [`handle_opt_take!`](crate::handle_opt_take)
 #[macro_export]
 macro_rules! handle_opt {
     ($option:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
         match $option {
-            Some(value) => value,
-            None => return Err($variant {
+            $crate::__private::Some(value) => value,
+            $crate::__private::None => return $crate::__private::Err($variant {
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// Like [`handle_opt!`](crate::handle_opt), but computes the arguments from the `$subject` (e.g. the searched collection) only if the option is [`None`].
+///
+/// The `$subject` is bound to `$binding`, which can be used in the argument expressions: `handle_opt_with!(numbers.iter().find(|n| *n % 2 == 0), &numbers, EvenNotFound, |numbers| { len: numbers.len(), max: numbers.iter().max().copied() })`.
+/// The `$subject` is not evaluated (and so not moved) if the option is [`Some`].
+#[macro_export]
+macro_rules! handle_opt_with {
+    ($option:expr, $subject:expr, $variant:ident, |$binding:ident| { $($arg:ident$(: $value:expr)?),* $(,)? }) => {
+        match $option {
+            $crate::__private::Some(value) => value,
+            $crate::__private::None => {
+                // the subject is often the binding itself (e.g. `numbers, |numbers|`)
+                #[allow(clippy::redundant_locals)]
+                let $binding = $subject;
+                return $crate::__private::Err($variant {
+                    $($arg: $crate::_into!($arg$(: $value)?)),*
+                });
+            }
+        }
+    };
+}
+
+/// Like [`handle_opt!`](crate::handle_opt), but flattens the nested option: `Some(Some(value))` returns the `value`, while both `Some(None)` and `None` return the error.
+///
+/// This is useful for lookups of optional values (e.g. `map.get(key).map(|row| row.email.as_ref())`).
+#[macro_export]
+macro_rules! handle_opt_flat {
+    ($option:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        match $option {
+            $crate::__private::Some($crate::__private::Some(value)) => value,
+            $crate::__private::Some($crate::__private::None) | $crate::__private::None => return $crate::__private::Err($variant {
                 $($arg: $crate::_into!($arg$(: $value)?)),*
             }),
         }
@@ -37,9 +395,9 @@ macro_rules! handle_opt {
 #[macro_export]
 macro_rules! handle_opt_take {
     ($option:expr, $variant:ident, $some_value:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
-        if let Some($some_value) = $option.take() {
-            return Err($variant {
-                $some_value: $some_value.into(),
+        if let $crate::__private::Some($some_value) = $option.take() {
+            return $crate::__private::Err($variant {
+                $some_value: $crate::__private::Into::into($some_value),
                 $($arg: $crate::_into!($arg$(: $value)?)),*
             })
         }
@@ -53,66 +411,123 @@ macro_rules! handle_opt_take {
 macro_rules! handle_bool {
     ($condition:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
         if $condition {
-            return Err($variant {
+            return $crate::__private::Err($variant {
                 $($arg: $crate::_into!($arg$(: $value)?)),*
             });
         };
     };
 }
 
-/// Collects results from an iterator, returning a variant that wraps all errors.
+/// Like [`handle_bool!`](crate::handle_bool), but sets the `source` field to a static error (e.g. a constant generated by [`static_errors!`](crate::static_errors)).
 ///
-/// `$results` must be an `impl Iterator<Item = Result<T, E>>`.
+/// The `source` field must have the `&'static T` or `&'static dyn Error` type, so the error is not allocated, and the outer error stays small: `handle_bool_static!(bytes.is_empty(), &UNEXPECTED_EOF, HeaderInvalid, offset)`.
+/// The references to errors implement [`Error`](core::error::Error), so the traces are rendered in the same way as with the owned errors.
 #[macro_export]
-macro_rules! handle_iter {
-    ($results:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
-        {
-            match $crate::partition_result($results) {
-                Ok(oks) => oks,
-                Err(errors) => {
-                    return Err($variant {
-                        source: errors.into(),
-                        $($arg: $crate::_into!($arg$(: $value)?)),*
-                    });
+macro_rules! handle_bool_static {
+    ($condition:expr, $error:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        if $condition {
+            return $crate::__private::Err($variant {
+                source: $error,
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            });
+        };
+    };
+}
+
+/// Generates the data-free errors: a unit struct that implements `Error` and `Display` (with the provided message), and a constant instance of it for every entry.
+///
+/// The references to the constants are promoted to `&'static`, so they can be stored in the `source` fields without allocation (see [`handle_bool_static!`](crate::handle_bool_static)).
+///
+/// ```rust
+/// errgonomic::static_errors! {
+///     /// The input has ended before the token
+///     pub UnexpectedEof, UNEXPECTED_EOF, "unexpected end of input";
+///     pub TokenMissing, TOKEN_MISSING, "expected a token";
+/// }
+///
+/// assert_eq!(UNEXPECTED_EOF.to_string(), "unexpected end of input");
+/// ```
+#[macro_export]
+macro_rules! static_errors {
+    ($($(#[$meta:meta])* $vis:vis $name:ident, $constant:ident, $message:literal;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(::core::fmt::Debug, ::core::default::Default, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq, ::core::hash::Hash)]
+            $vis struct $name;
+
+            impl $crate::__private::fmt::Display for $name {
+                fn fmt(&self, f: &mut $crate::__private::fmt::Formatter<'_>) -> $crate::__private::fmt::Result {
+                    f.write_str($message)
                 }
             }
-        }
+
+            impl $crate::__private::Error for $name {}
+
+            $vis const $constant: $name = $name;
+        )*
     };
 }
 
-/// Collects results while keeping the corresponding input items, returning `(outputs, items)` on success.
+/// Runs every check (without short-circuiting) and returns the `$variant` error with the [`ValidationErrors`](crate::ValidationErrors) aggregate if any check has failed.
 ///
-/// This macro returns a tuple because the iteration consumes items that may be needed later.
-/// If there are no errors, `items.len() == outputs.len()`.
-/// If the results iterator terminates early, the returned `items` may be shorter than the original input.
+/// Every check is a `field => result` pair, where the error of the result is pushed with the field path. Use `field[index] for pattern in iter => result` to check every element of a collection (the `index` binding must be a `usize` bound by the pattern).
+/// The `source` field of the variant must have the [`ValidationErrors`](crate::ValidationErrors) type. The target must be a variable: the macro shadows it with a shared reference, so the checks read the same value and can't modify it.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{ValidationErrors, handle_validate};
+/// # use thiserror::Error;
+/// # struct Config { name: String, ports: Vec<u16> }
+/// # #[derive(Error, Debug)]
+/// # #[error("must not be empty")]
+/// # struct ValueEmpty;
+/// # #[derive(Error, Debug)]
+/// # #[error("must be greater than 0")]
+/// # struct ValueNotPositive;
+/// # fn validate_name(name: &str) -> Result<(), ValueEmpty> { if name.is_empty() { Err(ValueEmpty) } else { Ok(()) } }
+/// # fn validate_port(port: u16) -> Result<(), ValueNotPositive> { if port == 0 { Err(ValueNotPositive) } else { Ok(()) } }
+/// fn check_config(config: &Config) -> Result<(), CheckConfigError> {
+///     use CheckConfigError::*;
+///     handle_validate!(config, ConfigInvalid, {
+///         name => validate_name(&config.name),
+///         ports[i] for (i, port) in config.ports.iter().enumerate() => validate_port(*port),
+///     });
+///     Ok(())
+/// }
+///
+/// #[derive(Error, Debug)]
+/// enum CheckConfigError {
+///     #[error("invalid config")]
+///     ConfigInvalid { source: ValidationErrors },
+/// }
+/// # }
+/// ```
 #[macro_export]
-macro_rules! handle_iter_of_refs {
-    ($results:expr, $items:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+macro_rules! handle_validate {
+    (@checks $errors:ident;) => {};
+    (@checks $errors:ident; $field:ident [$index:ident] for $pattern:pat in $iter:expr => $check:expr $(, $($rest:tt)*)?) => {
+        for $pattern in $iter {
+            if let $crate::__private::Err(error) = $check {
+                $errors.push_at($crate::FieldPath::new([<$crate::FieldSegment as $crate::__private::From<_>>::from($crate::__private::stringify!($field)), <$crate::FieldSegment as $crate::__private::From<_>>::from($index)]), error);
+            }
+        }
+        $crate::handle_validate!(@checks $errors; $($($rest)*)?);
+    };
+    (@checks $errors:ident; $field:ident => $check:expr $(, $($rest:tt)*)?) => {
+        if let $crate::__private::Err(error) = $check {
+            $errors.push_at($crate::__private::stringify!($field), error);
+        }
+        $crate::handle_validate!(@checks $errors; $($($rest)*)?);
+    };
+    ($target:ident, $variant:ident, { $($checks:tt)* } $(, $arg:ident$(: $value:expr)?)*) => {
         {
-            use alloc::vec::Vec;
-            let (outputs, items, errors) = core::iter::zip($results, $items).fold(
-                (Vec::new(), Vec::new(), Vec::new()),
-                |(mut outputs, mut items, mut errors), (result, item)| {
-                    match result {
-                        Ok(output) => {
-                            outputs.push(output);
-                            items.push(item);
-                        }
-                        Err(source) => {
-                            errors.push($crate::ItemError {
-                                item,
-                                source,
-                            });
-                        }
-                    }
-                    (outputs, items, errors)
-                },
-            );
-            if errors.is_empty() {
-                (outputs, items)
-            } else {
-                return Err($variant {
-                    source: errors.into(),
+            let $target = &$target;
+            let mut errors = $crate::ValidationErrors::new();
+            $crate::handle_validate!(@checks errors; $($checks)*);
+            if let $crate::__private::Err(source) = errors.into_result(()) {
+                return $crate::__private::Err($variant {
+                    source: $crate::__private::Into::into(source),
                     $($arg: $crate::_into!($arg$(: $value)?)),*
                 });
             }
@@ -120,348 +535,2826 @@ macro_rules! handle_iter_of_refs {
     };
 }
 
-/// Collects results from any `IntoIterator`, wrapping all errors into one variant.
-#[macro_export]
-macro_rules! handle_into_iter {
-    ($results:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
-        $crate::handle_iter!($results.into_iter(), $variant $(, $arg$(: $value)?),*)
-    };
-}
-
-/// [`handle_discard`](crate::handle_discard) should only be used when you want to discard the source error. This is discouraged. Prefer other handle-family macros that preserve the source error.
+/// Like [`handle!`](crate::handle), but evaluates to a `Result` instead of returning early.
+///
+/// Use [`handle!`](crate::handle) in the function bodies and `try_handle!` in the closure bodies: `handle!` expands to `return Err(...)`, which returns from the closure (not from the enclosing function), so it requires the closure to return a `Result` of the same error type.
+/// `try_handle!` makes the closure body an expression of type `Result<T, Error>`, so the closure can be passed to an iterator adapter, and the results can be collected with [`handle_iter!`](crate::handle_iter).
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{ErrVec, handle_iter, try_handle};
+/// # use std::num::ParseIntError;
+/// # use thiserror::Error;
+/// fn parse_all(inputs: Vec<String>) -> Result<Vec<u32>, ParseAllError> {
+///     use ParseAllError::*;
+///     let results = inputs
+///         .into_iter()
+///         .map(|input| try_handle!(input.parse::<u32>(), ParseFailed, input));
+///     Ok(handle_iter!(results, ParseAllFailed))
+/// }
+///
+/// #[derive(Error, Debug)]
+/// enum ParseAllError {
+///     #[error("failed to parse '{input}'")]
+///     ParseFailed { source: ParseIntError, input: String },
+///     #[error("failed to parse {len} inputs", len = source.len())]
+///     ParseAllFailed { source: ErrVec<ParseAllError> },
+/// }
+///
+/// assert!(parse_all(vec!["1".to_string(), "x".to_string()]).is_err());
+/// # }
+/// ```
 #[macro_export]
-macro_rules! handle_discard {
-    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+macro_rules! try_handle {
+    ($result:expr, $variant:ident @ $source:ident $(, $arg:ident$(: $value:expr)?)*) => {
         match $result {
-            Ok(value) => value,
-            Err(_) => return Err($variant {
+            $crate::__private::Ok(value) => $crate::__private::Ok(value),
+            $crate::__private::Err(source) => $crate::__private::Err($variant {
+                $source: $crate::__private::Into::into(source),
                 $($arg: $crate::_into!($arg$(: $value)?)),*
             }),
         }
     };
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $crate::try_handle!($result, $variant @ source $(, $arg$(: $value)?)*)
+    };
 }
 
-/// [`map_err`](crate::map_err) should be used only when the error variant doesn't capture any owned variables (which is very rare), or exactly at the end of the block (in the position of returned expression).
+/// Like [`handle_opt!`](crate::handle_opt), but evaluates to a `Result` instead of returning early (see [`try_handle!`](crate::try_handle)).
 #[macro_export]
-macro_rules! map_err {
-    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
-        $result.map_err(|source| $variant {
-            source: source.into(),
-            $($arg: $crate::_into!($arg$(: $value)?)),*
-        })
+macro_rules! try_handle_opt {
+    ($option:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        match $option {
+            $crate::__private::Some(value) => $crate::__private::Ok(value),
+            $crate::__private::None => $crate::__private::Err($variant {
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
     };
 }
 
-/// Internal
-#[doc(hidden)]
+/// Like [`handle_bool!`](crate::handle_bool), but evaluates to a `Result<(), _>` instead of returning early (see [`try_handle!`](crate::try_handle)).
 #[macro_export]
-macro_rules! _into {
-    ($arg:ident) => {
-        $arg.into()
-    };
-    ($arg:ident: $value:expr) => {
-        $value.into()
+macro_rules! try_handle_bool {
+    ($condition:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        if $condition {
+            $crate::__private::Err($variant {
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            })
+        } else {
+            $crate::__private::Ok(())
+        }
     };
 }
 
-/// Internal
-#[doc(hidden)]
+/// Returns an error if the [`ExitStatus`](std::process::ExitStatus) of a child process is not successful.
+///
+/// The variant must have the `code: Option<i32>` and `signal: Option<i32>` fields (in addition to the fields passed as arguments).
+/// The `signal` field is set only on unix (see [`exit_status_signal`](crate::exit_status_signal)), so the same error enum can be used on every platform.
 #[macro_export]
-macro_rules! _index_err {
-    ($f:ident) => {
-        |(index, item)| $f(item).map_err(|err| (index, err))
+macro_rules! handle_status {
+    ($status:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {{
+        let status = $status;
+        if !status.success() {
+            return $crate::__private::Err($variant {
+                code: status.code(),
+                signal: $crate::exit_status_signal(&status),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            });
+        }
+    }};
+}
+
+/// Like [`handle_status!`](crate::handle_status), but accepts an [`Output`](std::process::Output) and returns it if the status is successful.
+///
+/// On failure, the variant must also have the `stderr: String` field, which is set to the end of the captured stderr (see [`decode_lossy_tail`](crate::decode_lossy_tail) and [`STDERR_MAX_LEN`](crate::STDERR_MAX_LEN)).
+#[macro_export]
+macro_rules! handle_output {
+    ($output:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {{
+        let output = $output;
+        if !output.status.success() {
+            return $crate::__private::Err($variant {
+                code: output.status.code(),
+                signal: $crate::exit_status_signal(&output.status),
+                stderr: $crate::decode_lossy_tail(&output.stderr, $crate::STDERR_MAX_LEN),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            });
+        }
+        output
+    }};
+}
+
+/// Checks every condition and returns a `Multiple` variant that wraps all failed checks.
+///
+/// Unlike [`handle_bool!`](crate::handle_bool), each condition describes the valid state (the variant is constructed when the condition is false), and the macro doesn't short-circuit on the first failed check.
+/// The error enum must have a `Multiple { source: ErrVec<Self> }` variant.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{validate, ErrVec};
+/// # use thiserror::Error;
+/// fn validate_user(name: &str, age: u32) -> Result<(), ValidateUserError> {
+///     validate!(ValidateUserError, [
+///         (!name.is_empty(), NameEmpty),
+///         (age < 150, AgeTooHigh, age),
+///     ]);
+///     Ok(())
+/// }
+///
+/// #[derive(Error, Debug)]
+/// enum ValidateUserError {
+///     #[error("name is empty")]
+///     NameEmpty,
+///     #[error("age is too high: {age}")]
+///     AgeTooHigh { age: u32 },
+///     #[error("user is invalid")]
+///     Multiple { source: ErrVec<ValidateUserError> },
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! validate {
+    ($error:ident, [$(($condition:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*)),* $(,)?]) => {
+        {
+            let mut errors = $crate::__private::Vec::new();
+            $(
+                if !$condition {
+                    errors.push($error::$variant {
+                        $($arg: $crate::_into!($arg$(: $value)?)),*
+                    });
+                }
+            )*
+            if !errors.is_empty() {
+                return $crate::__private::Err($error::Multiple {
+                    source: $crate::__private::Into::into(errors),
+                });
+            }
+        }
     };
 }
 
-/// Internal
-#[doc(hidden)]
+/// Collects results from an iterator, returning a variant that wraps all errors.
+///
+/// `$results` must be an `impl Iterator<Item = Result<T, E>>`.
+///
+/// The `@count` value sets the argument to the total number of the processed results (e.g. `handle_iter!(results, BatchFailed, total: @count)`).
+/// Use [`ErrVecWithTotal`](crate::ErrVecWithTotal) as the type of the `source` field to keep the total on the aggregate itself.
+///
+/// If `$results` is empty, this macro evaluates to an empty `Vec` (an empty input is not an error). Use [`handle_iter_non_empty!`](crate::handle_iter_non_empty) if the input must not be empty.
+///
+/// The optional trailing `context` block attaches the key-value pairs that describe the batch (e.g. `handle_iter!(results, SyncFailed, tenant, context: { dir: dir.display(), tenant: tenant_id })`).
+/// The values are formatted via `Display`. Use [`ErrVecWithContext`](crate::ErrVecWithContext) as the type of the `source` field in this case (the `context` block is not supported together with `@ $source`).
 #[macro_export]
-macro_rules! _index_err_async {
-    ($f:ident) => {
-        async |(index, item)| $f(item).await.map_err(|err| (index, err))
+macro_rules! handle_iter {
+    (@context $results:expr, $variant:ident [$($args:tt)*], context: { $($key:ident: $context:expr),* $(,)? }) => {
+        $crate::handle_iter!(@with_context { $($key: $context),* } $results, $variant $($args)*)
+    };
+    (@context $results:expr, $variant:ident [$($args:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::handle_iter!(@context $results, $variant [$($args)* $next] $($rest)*)
+    };
+    (@context $results:expr, $variant:ident [$($args:tt)*]) => {
+        $crate::handle_iter!($results, $variant @ source $($args)*)
+    };
+    (@with_context { $($key:ident: $context:expr),* } $results:expr, $variant:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            match $crate::partition_result_with_total($results) {
+                $crate::__private::Ok(oks) => oks,
+                $crate::__private::Err((errors, _total)) => {
+                    return $crate::__private::Err($variant {
+                        source: $crate::ErrVecWithContext::new(errors, [$(($crate::__private::stringify!($key), $crate::__private::format!("{}", $context))),*]),
+                        $($arg: $crate::_into_with_count!(_total; $arg$(: @$count)?$(: $value)?)),*
+                    });
+                }
+            }
+        }
+    };
+    ($results:expr, $variant:ident @ $source:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            match $crate::partition_result_with_total($results) {
+                $crate::__private::Ok(oks) => oks,
+                $crate::__private::Err((errors, total)) => {
+                    return $crate::__private::Err($variant {
+                        $source: $crate::FromErrors::from_errors(errors, total),
+                        $($arg: $crate::_into_with_count!(total; $arg$(: @$count)?$(: $value)?)),*
+                    });
+                }
+            }
+        }
+    };
+    // the arguments are scanned as tokens (not as expressions), so that the trailing `context` block can be detected without parsing it as an argument value
+    ($results:expr, $variant:ident, $($rest:tt)+) => {
+        $crate::handle_iter!(@context $results, $variant [] , $($rest)+)
+    };
+    ($results:expr, $variant:ident$(,)? $($arg:ident$(: @$count:ident)?$(: $value:expr)?),*) => {
+        $crate::handle_iter!($results, $variant @ source $(, $arg$(: @$count)?$(: $value)?)*)
     };
 }
 
-#[cfg(all(test, feature = "std"))]
-mod tests {
-    use crate::{ErrVec, ItemError, PathBufDisplay};
-    use futures::future::join_all;
-    use serde::{Deserialize, Serialize};
-    use std::io;
-    use std::path::{Path, PathBuf};
-    use std::str::FromStr;
-    use std::sync::{Arc, RwLock};
-    use thiserror::Error;
-    use tokio::fs::read_to_string;
-    use tokio::task::JoinSet;
+/// Like [`handle_iter!`](crate::handle_iter), but returns the `$empty_variant` error (a variant without fields) if `$results` is empty.
+///
+/// The arguments are passed to `$variant` only.
+#[macro_export]
+macro_rules! handle_iter_non_empty {
+    ($results:expr, $variant:ident, $empty_variant:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            let mut results = $crate::__private::Iterator::peekable($crate::__private::IntoIterator::into_iter($results));
+            if results.peek().is_none() {
+                return $crate::__private::Err($empty_variant {});
+            }
+            $crate::handle_iter!(results, $variant $(, $arg$(: @$count)?$(: $value)?)*)
+        }
+    };
+}
+
+/// Like [`handle_iter!`](crate::handle_iter), but collapses the errors with the same message into a single [`Deduped`](crate::Deduped) error with the count (see [`dedup_errors`](crate::dedup_errors)).
+///
+/// The `source` field of the variant must be convertible from `Vec<Deduped<E>>` (e.g. `ErrVec<Deduped<E>>`). The `@count` value works like in [`handle_iter!`](crate::handle_iter).
+#[macro_export]
+macro_rules! handle_iter_dedup {
+    ($results:expr, $variant:ident @ $source:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            match $crate::partition_result_with_total($results) {
+                $crate::__private::Ok(oks) => oks,
+                $crate::__private::Err((errors, total)) => {
+                    return $crate::__private::Err($variant {
+                        $source: $crate::FromErrors::from_errors($crate::dedup_errors(errors), total),
+                        $($arg: $crate::_into_with_count!(total; $arg$(: @$count)?$(: $value)?)),*
+                    });
+                }
+            }
+        }
+    };
+    ($results:expr, $variant:ident$(,)? $($arg:ident$(: @$count:ident)?$(: $value:expr)?),*) => {
+        $crate::handle_iter_dedup!($results, $variant @ source $(, $arg$(: @$count)?$(: $value)?)*)
+    };
+}
+
+/// Like [`handle_iter!`](crate::handle_iter), but for the results whose errors are [`ErrVec`](crate::ErrVec)s (e.g. the results of processing the chunks): the errors are flattened into a single `ErrVec` (see [`ErrVec::flatten`](crate::ErrVec::flatten)).
+///
+/// The `source` field of the variant must be convertible from `ErrVec<E>`.
+#[macro_export]
+macro_rules! handle_iter_flat {
+    ($results:expr, $variant:ident @ $source:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        {
+            match $crate::partition_result($results) {
+                $crate::__private::Ok(oks) => oks,
+                $crate::__private::Err(errors) => {
+                    return $crate::__private::Err($variant {
+                        $source: $crate::__private::Into::into($crate::ErrVec::new(errors).flatten()),
+                        $($arg: $crate::_into!($arg$(: $value)?)),*
+                    });
+                }
+            }
+        }
+    };
+    ($results:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $crate::handle_iter_flat!($results, $variant @ source $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Like [`handle_iter!`](crate::handle_iter), but for the results of the unit type: the `Ok` values are not collected, and the errors vec is allocated only when the first error appears.
+///
+/// `$results` must be an `impl IntoIterator<Item = Result<(), E>>`.
+#[macro_export]
+macro_rules! handle_iter_unit {
+    ($results:expr, $variant:ident @ $source:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            if let $crate::__private::Err((errors, total)) = $crate::partition_unit_result_with_total($results) {
+                return $crate::__private::Err($variant {
+                    $source: $crate::FromErrors::from_errors(errors, total),
+                    $($arg: $crate::_into_with_count!(total; $arg$(: @$count)?$(: $value)?)),*
+                });
+            }
+        }
+    };
+    ($results:expr, $variant:ident$(,)? $($arg:ident$(: @$count:ident)?$(: $value:expr)?),*) => {
+        $crate::handle_iter_unit!($results, $variant @ source $(, $arg$(: @$count)?$(: $value)?)*)
+    };
+}
+
+/// Like [`handle_iter!`](crate::handle_iter), but stops at the first error: the rest of `$results` is not consumed, and the `source` contains a single error.
+///
+/// This is useful for the very large iterators where only the first error is needed. The `@count` value is set to the number of the processed results (including the failed one).
+#[macro_export]
+macro_rules! handle_iter_first {
+    ($results:expr, $variant:ident @ $source:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            match $crate::partition_result_fail_fast_with_total($results) {
+                $crate::__private::Ok(oks) => oks,
+                $crate::__private::Err((errors, total)) => {
+                    return $crate::__private::Err($variant {
+                        $source: $crate::FromErrors::from_errors(errors, total),
+                        $($arg: $crate::_into_with_count!(total; $arg$(: @$count)?$(: $value)?)),*
+                    });
+                }
+            }
+        }
+    };
+    ($results:expr, $variant:ident$(,)? $($arg:ident$(: @$count:ident)?$(: $value:expr)?),*) => {
+        $crate::handle_iter_first!($results, $variant @ source $(, $arg$(: @$count)?$(: $value)?)*)
+    };
+}
+
+/// Collects results while keeping the corresponding input items, returning `(outputs, items)` on success.
+///
+/// This macro returns a tuple because the iteration consumes items that may be needed later.
+/// If there are no errors, `items.len() == outputs.len()`.
+/// If the results iterator terminates early, the returned `items` may be shorter than the original input.
+/// The `@count` value works like in [`handle_iter!`](crate::handle_iter).
+#[macro_export]
+macro_rules! handle_iter_of_refs {
+    ($results:expr, $items:expr, $variant:ident @ $source:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        {
+            let (outputs, items, errors, total) = $crate::__private::Iterator::fold(
+                $crate::__private::zip($results, $items),
+                ($crate::__private::Vec::new(), $crate::__private::Vec::new(), $crate::__private::Vec::new(), 0usize),
+                |(mut outputs, mut items, mut errors, total), (result, item)| {
+                    match result {
+                        $crate::__private::Ok(output) => {
+                            outputs.push(output);
+                            items.push(item);
+                        }
+                        $crate::__private::Err(source) => {
+                            errors.push($crate::ItemError {
+                                item,
+                                source,
+                            });
+                        }
+                    }
+                    (outputs, items, errors, total + 1)
+                },
+            );
+            if errors.is_empty() {
+                (outputs, items)
+            } else {
+                return $crate::__private::Err($variant {
+                    $source: $crate::FromErrors::from_errors(errors, total),
+                    $($arg: $crate::_into_with_count!(total; $arg$(: @$count)?$(: $value)?)),*
+                });
+            }
+        }
+    };
+    ($results:expr, $items:expr, $variant:ident $(, $arg:ident$(: @$count:ident)?$(: $value:expr)?)*) => {
+        $crate::handle_iter_of_refs!($results, $items, $variant @ source $(, $arg$(: @$count)?$(: $value)?)*)
+    };
+}
+
+/// Collects results from any `IntoIterator`, wrapping all errors into one variant.
+#[macro_export]
+macro_rules! handle_into_iter {
+    ($results:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle_iter!($crate::__private::IntoIterator::into_iter($results), $variant $(, $arg$(: $value)?),*)
+    };
+}
+
+/// Pushes the error into the accumulator and `continue`s the enclosing loop if the result is [`Err`], or evaluates to the `Ok` value otherwise.
+///
+/// This is useful for the manual loops that should process all items and report all errors: `let number = handle_push!(input.parse::<u32>(), &mut errors, |source| ParseFailed { source, input });`.
+/// The accumulator may be any expression with a `push` method (e.g. `&mut ErrVec<E>` or `&mut Vec<E>`). If `$map` is omitted, the error is converted via [`Into`].
+#[macro_export]
+macro_rules! handle_push {
+    ($result:expr, $errors:expr, $map:expr) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => {
+                $errors.push(($map)(source));
+                continue;
+            }
+        }
+    };
+    ($result:expr, $errors:expr) => {
+        $crate::handle_push!($result, $errors, $crate::__private::Into::into)
+    };
+}
+
+/// Handles the result of locking a [`Mutex`](std::sync::Mutex) or an [`RwLock`](std::sync::RwLock), converting the [`PoisonError`](std::sync::PoisonError) into an owned [`LockPoisonedError`](crate::LockPoisonedError).
+///
+/// Use it instead of [`handle_discard!`](crate::handle_discard) to keep the source error: `PoisonError<RwLockReadGuard<'_, T>>` can't be a `source` because it contains a lifetime.
+#[macro_export]
+macro_rules! handle_lock {
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($variant {
+                source: $crate::__private::Into::into(<$crate::LockPoisonedError as $crate::__private::From<_>>::from(source)),
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// [`handle_discard`](crate::handle_discard) should only be used when you want to discard the source error. This is discouraged. Prefer other handle-family macros that preserve the source error.
+#[macro_export]
+macro_rules! handle_discard {
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(_) => return $crate::__private::Err($variant {
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// [`map_err`](crate::map_err) should be used only when the error variant doesn't capture any owned variables (which is very rare), or exactly at the end of the block (in the position of returned expression).
+#[macro_export]
+macro_rules! map_err {
+    ($result:expr, $variant:ident @ $source:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $result.map_err(|source| $variant {
+            $source: $crate::__private::Into::into(source),
+            $($arg: $crate::_into!($arg$(: $value)?)),*
+        })
+    };
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $crate::map_err!($result, $variant @ source $(, $arg$(: $value)?)*)
+    };
+}
+
+/// [`wrap!`](crate::wrap) expands to a `move` closure that constructs the variant from the `source` error and the given fields, so it can be passed to [`map_err`](Result::map_err) before `?`.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{display_path, wrap};
+/// # use std::fs::read_to_string;
+/// # use std::io;
+/// # use std::path::PathBuf;
+/// # use thiserror::Error;
+/// fn read_config(path: PathBuf) -> Result<String, ReadConfigError> {
+///     use ReadConfigError::*;
+///     let contents = read_to_string(&path).map_err(wrap!(ReadToStringFailed, path))?;
+///     Ok(contents)
+/// }
+///
+/// #[derive(Error, Debug)]
+/// enum ReadConfigError {
+///     #[error("failed to read the config: {path}", path = display_path(path))]
+///     ReadToStringFailed { source: io::Error, path: PathBuf },
+/// }
+/// # }
+/// ```
+///
+/// The values of the arguments are evaluated when the closure is created (i.e. when the argument of `map_err` is evaluated, after the fallible expression), not when the error is constructed, and the closure captures only the named arguments.
+/// That means the values are computed and moved into the closure even if the result is [`Ok`], so `wrap!` should be used only if the arguments are not needed after the call.
+/// Prefer [`handle!`](crate::handle), which doesn't move the arguments if the result is [`Ok`].
+/// Cloning the arguments (`wrap!(ReadToStringFailed, path: path.clone())`) is acceptable only if the values are cheap to clone or the call is not on a hot path.
+#[macro_export]
+macro_rules! wrap {
+    ($variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        {
+            $(let $arg = $crate::_into!($arg$(: $value)?);)*
+            move |source| $variant {
+                source: $crate::__private::Into::into(source),
+                $($arg),*
+            }
+        }
+    };
+}
+
+/// Returns early with a boxed [`ContextError`](crate::ContextError) (the source error with a context message) if the result is `Err`.
+///
+/// This is an escape hatch for the functions that genuinely return boxed errors (e.g. `Result<T, Box<dyn Error + Send + Sync>>`), where [`handle!`](crate::handle) can't construct a variant.
+/// Prefer the typed error enums: the boxed errors can't be matched by the callers.
+/// The context is passed as [`format!`](alloc::format) arguments. The source error must be `Send + Sync`.
+#[macro_export]
+macro_rules! box_err {
+    ($result:expr, $($context:tt)+) => {
+        match $result {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($crate::__private::Into::into($crate::ContextError {
+                message: $crate::__private::format!($($context)+),
+                source: $crate::__private::Into::into(source),
+            })),
+        }
+    };
+}
+
+/// [`map_err_discard`](crate::map_err_discard) is the tail-position analog of [`handle_discard`](crate::handle_discard): it maps the error to a variant without a `source` field. This is discouraged. Prefer other macros that preserve the source error.
+#[macro_export]
+macro_rules! map_err_discard {
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $result.map_err(|_| $variant {
+            $($arg: $crate::_into!($arg$(: $value)?)),*
+        })
+    };
+}
+
+/// [`finish!`](crate::finish) maps the error of the closing step of a resource (e.g. `flush()`, `close()`, `sync_all()`) in the final position of the function.
+///
+/// It expands to the same code as [`map_err!`](crate::map_err), but the name signals that the fallible finalizer is handled instead of being dropped (the `Drop` impls of most resources ignore the errors of the closing step).
+/// The expression evaluates to a `Result`, so it can be returned directly or followed by `?`.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{display_path, finish, handle};
+/// # use std::fs::File;
+/// # use std::io;
+/// # use std::io::{BufWriter, Write};
+/// # use std::path::PathBuf;
+/// # use thiserror::Error;
+/// fn write_report(path: PathBuf, report: &str) -> Result<(), WriteReportError> {
+///     use WriteReportError::*;
+///     let file = handle!(File::create(&path), CreateFailed, path);
+///     let mut writer = BufWriter::new(file);
+///     handle!(writer.write_all(report.as_bytes()), WriteAllFailed, path);
+///     finish!(writer.flush(), FlushFailed, path)
+/// }
+///
+/// #[derive(Error, Debug)]
+/// enum WriteReportError {
+///     #[error("failed to create the report file: {path}", path = display_path(path))]
+///     CreateFailed { source: io::Error, path: PathBuf },
+///     #[error("failed to write the report: {path}", path = display_path(path))]
+///     WriteAllFailed { source: io::Error, path: PathBuf },
+///     #[error("failed to flush the report: {path}", path = display_path(path))]
+///     FlushFailed { source: io::Error, path: PathBuf },
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! finish {
+    ($result:expr, $variant:ident$(,)? $($arg:ident$(: $value:expr)?),*) => {
+        $crate::map_err!($result, $variant @ source $(, $arg$(: $value)?)*)
+    };
+}
+
+/// Implements [`TryFrom`] with the hand-written error enum that follows the naming convention (`Convert{A}To{B}Error`).
+///
+/// The variants of the error enum are brought into scope, so the body can use [`convert!`](crate::convert) and [`convert_vec!`](crate::convert_vec) (or any other handle-family macro) without the enum prefix.
+/// The name of the error enum must be passed explicitly because `macro_rules!` can't concatenate identifiers.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use errgonomic::{convert, try_from_impl};
+/// # use std::num::ParseIntError;
+/// # use thiserror::Error;
+/// pub struct RawPort(String);
+///
+/// pub struct Port(u16);
+///
+/// try_from_impl!(RawPort, Port, ConvertRawPortToPortError, |RawPort(input)| {
+///     let port = convert!(input.parse::<u16>(), InputParseFailed, input);
+///     Ok(Port(port))
+/// });
+///
+/// #[derive(Error, Debug)]
+/// pub enum ConvertRawPortToPortError {
+///     #[error("failed to parse port: '{input}'")]
+///     InputParseFailed { source: ParseIntError, input: String },
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! try_from_impl {
+    ($source:ty, $target:ty, $error:ident, |$value:pat_param| $body:block) => {
+        impl $crate::__private::TryFrom<$source> for $target {
+            type Error = $error;
+
+            fn try_from($value: $source) -> $crate::__private::Result<Self, Self::Error> {
+                #[allow(unused_imports)]
+                use $error::*;
+                $body
+            }
+        }
+    };
+}
+
+/// Calls `$f` with a reference to the `$input` variable, returning the variant with the original input if the result is [`Err`].
+///
+/// Use it inside [`try_from_impl!`](crate::try_from_impl): `TryFrom` consumes the input, so the error must carry it back (e.g. for a retry).
+/// The input is moved into the field that has the same name as the variable (e.g. `try_from_handle!(raw, |raw: &RawPort| raw.0.parse::<u16>(), PortParseFailed)` sets the `raw` field).
+/// The output of `$f` must not borrow from the input.
+#[macro_export]
+macro_rules! try_from_handle {
+    ($input:ident, $f:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        match ($f)(&$input) {
+            $crate::__private::Ok(value) => value,
+            $crate::__private::Err(source) => return $crate::__private::Err($variant {
+                source: $crate::__private::Into::into(source),
+                $input,
+                $($arg: $crate::_into!($arg$(: $value)?)),*
+            }),
+        }
+    };
+}
+
+/// An alias of [`handle!`](crate::handle) for use inside [`try_from_impl!`](crate::try_from_impl).
+#[macro_export]
+macro_rules! convert {
+    ($($tokens:tt)*) => {
+        $crate::handle!($($tokens)*)
+    };
+}
+
+/// Converts every element of the collection via `$f` (a closure or a function that returns a [`Result`]), collecting all errors together with the indexes of the elements.
+///
+/// The `source` field of the variant must be an `ErrVec<ItemError<usize, E>>`.
+#[macro_export]
+macro_rules! convert_vec {
+    ($collection:expr, $f:expr, $variant:ident $(, $arg:ident$(: $value:expr)?)*) => {
+        $crate::handle_iter!(
+            $crate::__private::Iterator::map(
+                $crate::__private::Iterator::enumerate($crate::__private::IntoIterator::into_iter($collection)),
+                |(index, item)| ($f)(item).map_err(|source| $crate::ItemError {
+                    item: index,
+                    source,
+                })
+            ),
+            $variant
+            $(, $arg$(: $value)?)*
+        )
+    };
+}
+
+/// Implements [`HttpError`](crate::HttpError) for the error enum, mapping the variants to status codes (the other variants are mapped to `500 Internal Server Error`).
+///
+/// ```rust,ignore
+/// impl_http_error!(GetUserError, {
+///     UserNotFound => StatusCode::NOT_FOUND,
+///     PermissionDenied => StatusCode::FORBIDDEN,
+/// });
+/// ```
+#[macro_export]
+macro_rules! impl_http_error {
+    ($error:ty, {$($variant:ident => $status:expr),* $(,)?}) => {
+        impl $crate::HttpError for $error {
+            fn status(&self) -> $crate::__private::http::StatusCode {
+                #[allow(unreachable_patterns)]
+                match self {
+                    $(Self::$variant { .. } => $status,)*
+                    _ => $crate::__private::http::StatusCode::INTERNAL_SERVER_ERROR,
+                }
+            }
+        }
+    };
+}
+
+/// Implements [`GrpcStatus`](crate::GrpcStatus) for the error enum, mapping the variants to gRPC codes (the other variants are mapped to `Internal`).
+///
+/// ```rust,ignore
+/// impl_grpc_status!(GetUserError, {
+///     UserNotFound => Code::NotFound,
+///     PermissionDenied => Code::PermissionDenied,
+/// });
+/// ```
+#[cfg(feature = "tonic")]
+#[macro_export]
+macro_rules! impl_grpc_status {
+    ($error:ty, {$($variant:ident => $code:expr),* $(,)?}) => {
+        impl $crate::GrpcStatus for $error {
+            fn grpc_code(&self) -> $crate::__private::tonic::Code {
+                #[allow(unreachable_patterns)]
+                match self {
+                    $(Self::$variant { .. } => $code,)*
+                    _ => $crate::__private::tonic::Code::Internal,
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that the shape of the error tree (see [`error_tree_shape`](crate::error_tree_shape)) is equal to the expected shape in JSON (see the `Serialize` impl of [`TreeShape`](crate::TreeShape)).
+///
+/// This assertion doesn't depend on the message text, so it doesn't break when the dependencies change the wording of their errors.
+/// Requires the `testing` feature.
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_same_shape {
+    ($error:expr, $expected_json:expr) => {{
+        let actual = $crate::__private::serde_json::to_value($crate::error_tree_shape($error)).expect("TreeShape is always serializable");
+        let expected: $crate::__private::serde_json::Value = $crate::__private::serde_json::from_str($expected_json).expect("the expected shape must be valid JSON");
+        if actual != expected {
+            let to_pretty = |value: &$crate::__private::serde_json::Value| $crate::__private::serde_json::to_string_pretty(value).expect("Value is always serializable");
+            panic!("error tree shapes are different\n\nactual:\n{}\n\nexpected:\n{}", to_pretty(&actual), to_pretty(&expected));
+        }
+    }};
+}
+
+/// Asserts at compile time that the size of the error type doesn't exceed the budget (in bytes).
+///
+/// Clippy's `result_large_err` lint fires only at 128 bytes, while every [`Result`] that contains the error pays for its size.
+///
+/// ```rust
+/// # use errgonomic::assert_error_size;
+/// # use thiserror::Error;
+/// #[derive(Error, Debug)]
+/// enum ParseError {
+///     #[error("invalid byte {byte} at offset {offset}")]
+///     InvalidByte { offset: usize, byte: u8 },
+/// }
+///
+/// assert_error_size!(ParseError, 16);
+/// ```
+#[macro_export]
+macro_rules! assert_error_size {
+    ($error:ty, $max_size:expr) => {
+        const _: () = ::core::assert!(::core::mem::size_of::<$error>() <= $max_size, ::core::concat!("the size of `", ::core::stringify!($error), "` exceeds ", ::core::stringify!($max_size), " bytes"));
+    };
+}
+
+/// Returns an [`ErrorSizeReport`](crate::ErrorSizeReport) that includes the payload sizes of the listed variants.
+///
+/// Every variant must be listed with the names and the types of its fields: `error_size_report!(MyError, { ReadFailed { source: io::Error, path: PathBuf }, NotFound })`.
+/// The list is checked against the real variants at compile time: a missing variant, an unknown variant, a missing field or a wrong field type is a compile error.
+/// The `source` fields are reported separately (see [`VariantSize::non_source_size`](crate::VariantSize::non_source_size)).
+///
+/// Only the variants with named fields and the unit variants are supported.
+#[macro_export]
+macro_rules! error_size_report {
+    ($error:ty, { $($variant:ident $({ $($field:ident: $field_ty:ty),* $(,)? })?),* $(,)? }) => {{
+        type Checked = $error;
+        // the closure is never called: it fails to compile if the list doesn't match the variants and their fields
+        let _check_variants = |error: &Checked| match error {
+            $(Checked::$variant $({ $($field),* })? => {
+                $($(let _: &$field_ty = $field;)*)?
+            })*
+        };
+        $crate::ErrorSizeReport {
+            variants: $crate::__private::Vec::from([$($crate::VariantSize {
+                name: ::core::stringify!($variant),
+                payload_size: ::core::mem::size_of::<($($($field_ty,)*)?)>(),
+                non_source_size: 0 $($(+ $crate::_non_source_size!($field: $field_ty))*)?,
+            }),*]),
+            ..$crate::error_size_report::<$error>()
+        }
+    }};
+}
+
+/// Internal: returns the size of the field if it is not the `source` field (see [`error_size_report!`](crate::error_size_report))
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _non_source_size {
+    (source: $field_ty:ty) => {
+        0
+    };
+    ($field:ident: $field_ty:ty) => {
+        ::core::mem::size_of::<$field_ty>()
+    };
+}
+
+/// Internal
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _into {
+    ($arg:ident) => {
+        $crate::__private::Into::into($arg)
+    };
+    ($arg:ident: $value:expr) => {
+        $crate::__private::Into::into($value)
+    };
+}
+
+/// Internal: like [`_into!`](crate::_into), but resolves the `@count` value to the total number of the processed items
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _into_with_count {
+    ($count:expr; $arg:ident: @count) => {
+        $crate::__private::Into::into($count)
+    };
+    ($count:expr; $arg:ident: @$other:ident) => {
+        $crate::__private::compile_error!($crate::__private::concat!("unknown value `@", $crate::__private::stringify!($other), "` (expected `@count`)"))
+    };
+    ($count:expr; $arg:ident) => {
+        $crate::__private::Into::into($arg)
+    };
+    ($count:expr; $arg:ident: $value:expr) => {
+        $crate::__private::Into::into($value)
+    };
+}
+
+/// Internal
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _value {
+    ($arg:ident) => {
+        $arg
+    };
+    ($arg:ident: $value:expr) => {
+        $value
+    };
+}
+
+/// Internal
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _index_err {
+    ($f:ident) => {
+        |(index, item)| $f(item).map_err(|err| (index, err))
+    };
+}
+
+/// Internal
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _index_err_async {
+    ($f:ident) => {
+        async |(index, item)| $f(item).await.map_err(|err| (index, err))
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{Deadline, Deadlined, ErrVec, ErrVecWithContext, ErrVecWithTotal, ErrorDisplayer, ItemError, LockPoisonedError, OsStrDisplay, PathBufDisplay, Provenance, RequestId, Sourced, deadline_exceeded, display_path};
+    use futures::future::join_all;
+    use serde::{Deserialize, Serialize};
+    use std::ffi::OsString;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+    use std::sync::{Arc, RwLock};
+    use thiserror::Error;
+    use tokio::fs::read_to_string;
+    use tokio::task::JoinSet;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[allow(dead_code)]
+    struct PrintNameCommand {
+        dir: PathBuf,
+        format: Format,
+    }
+
+    #[allow(dead_code)]
+    impl PrintNameCommand {
+        async fn run(self) -> Result<(), PrintNameCommandError> {
+            use PrintNameCommandError::*;
+            let Self {
+                dir,
+                format,
+            } = self;
+            let config = handle!(parse_config(&dir, format).await, ParseConfigFailed);
+            println!("{}", config.name);
+            Ok(())
+        }
+    }
+
+    /// This function tests the [`crate::handle!`] macro
+    #[allow(dead_code)]
+    async fn parse_config(dir: &Path, format: Format) -> Result<Config, ParseConfigError> {
+        use Format::*;
+        use ParseConfigError::*;
+        let path_buf = dir.join("config.json");
+        let contents = handle!(read_to_string(&path_buf).await, ReadFileFailed, path: path_buf);
+        match format {
+            Json => {
+                let config = handle!(serde_json::de::from_str(&contents), DeserializeFromJson, path: path_buf, contents);
+                Ok(config)
+            }
+            Toml => {
+                let config = handle!(toml::de::from_str(&contents), DeserializeFromToml, path: path_buf, contents);
+                Ok(config)
+            }
+        }
+    }
+
+    /// This function tests the [`crate::handle_opt!`] macro
+    #[allow(dead_code)]
+    fn find_even(numbers: Vec<u32>) -> Result<u32, FindEvenError> {
+        use FindEvenError::*;
+        let even = handle_opt!(numbers.iter().find(|x| *x % 2 == 0), NotFound);
+        Ok(*even)
+    }
+
+    /// This function tests the `Variant @ field` syntax of the handle-family macros
+    fn load_legacy(input: &str, inputs: Vec<String>) -> Result<Vec<u32>, LoadLegacyError> {
+        use LoadLegacyError::*;
+        let _first = handle!(input.parse::<u32>(), FirstParseFailed @ cause, input: input.to_string());
+        let results = inputs.iter().map(|input| input.parse::<u32>());
+        let (_numbers, _inputs) = handle_iter_of_refs!(results, inputs.iter().cloned(), RefsParseFailed @ inner);
+        let results = inputs.iter().map(|input| input.parse::<u32>());
+        let numbers = handle_iter!(results, AllParseFailed @ inner, total: @count);
+        let _last = map_err!(input.parse::<u32>(), InvalidLast)?;
+        Ok(numbers)
+    }
+
+    #[derive(Error, Debug)]
+    enum LoadLegacyError {
+        #[error("failed to parse '{input}'")]
+        FirstParseFailed {
+            #[source]
+            cause: std::num::ParseIntError,
+            input: String,
+        },
+        #[error("failed to parse {len} inputs", len = inner.len())]
+        RefsParseFailed {
+            #[source]
+            inner: ErrVec<ItemError<String, std::num::ParseIntError>>,
+        },
+        #[error("failed to parse {len} of {total} inputs", len = inner.len())]
+        AllParseFailed {
+            #[source]
+            inner: ErrVec<std::num::ParseIntError>,
+            total: usize,
+        },
+        #[error("failed to parse the last input")]
+        InvalidLast { source: std::num::ParseIntError },
+    }
+
+    #[test]
+    fn must_handle_custom_source_field() {
+        use LoadLegacyError::*;
+        let inputs = |inputs: &[&str]| inputs.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(matches!(load_legacy("x", inputs(&[])), Err(FirstParseFailed { input, .. }) if input == "x"));
+        assert!(matches!(load_legacy("1", inputs(&["1", "x"])), Err(RefsParseFailed { inner }) if inner.len() == 1));
+        assert!(matches!(load_legacy("1", inputs(&["1", "2"])), Ok(numbers) if numbers == vec![1, 2]));
+        let error = map_err!("x".parse::<u32>(), FirstParseFailed @ cause, input: "x".to_string()).unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to parse 'x'\n- invalid digit found in string");
+    }
+
+    /// This function tests the [`crate::handle_opt_with!`] macro
+    fn find_even_with(numbers: &[u32]) -> Result<u32, FindEvenWithError> {
+        use FindEvenWithError::*;
+        let even = handle_opt_with!(numbers.iter().find(|number| *number % 2 == 0), numbers, EvenNotFound, |numbers| {
+            len: numbers.len(),
+            min: numbers.iter().min().copied(),
+            max: numbers.iter().max().copied(),
+        });
+        Ok(*even)
+    }
+
+    fn find_even_with_lazily(numbers: &[u32]) -> Result<u32, FindEvenWithError> {
+        use FindEvenWithError::*;
+        let even = handle_opt_with!(numbers.iter().find(|number| *number % 2 == 0), numbers, EvenNotFound, |numbers| {
+            len: numbers.len(),
+            min: must_not_be_called(),
+            max: None
+        });
+        Ok(*even)
+    }
+
+    fn must_not_be_called() -> Option<u32> {
+        panic!("the arguments must not be evaluated on the Some path")
+    }
+
+    #[derive(Error, Debug)]
+    enum FindEvenWithError {
+        #[error("no even number found among {len} numbers")]
+        EvenNotFound { len: usize, min: Option<u32>, max: Option<u32> },
+    }
+
+    #[test]
+    fn must_handle_opt_with() -> TestResult {
+        let numbers = vec![3, 5, 99];
+        assert!(matches!(
+            find_even_with(&numbers),
+            Err(FindEvenWithError::EvenNotFound {
+                len: 3,
+                min: Some(3),
+                max: Some(99)
+            })
+        ));
+        assert_eq!(find_even_with(&[3, 4])?, 4);
+        assert_eq!(find_even_with_lazily(&[3, 4])?, 4);
+        assert_eq!(numbers.len(), 3);
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_opt_flat!`] macro
+    fn get_email(emails: &std::collections::HashMap<&str, Option<&str>>, username: &str) -> Result<String, GetEmailError> {
+        use GetEmailError::*;
+        let email = handle_opt_flat!(emails.get(username).copied(), EmailNotFound, username: username.to_string());
+        Ok(email.to_string())
+    }
+
+    #[derive(Error, Debug)]
+    enum GetEmailError {
+        #[error("email not found for user '{username}'")]
+        EmailNotFound { username: String },
+    }
+
+    #[test]
+    fn must_handle_opt_flat() -> TestResult {
+        let emails = std::collections::HashMap::from([("alice", Some("alice@example.com")), ("bob", None)]);
+        assert_eq!(get_email(&emails, "alice")?, "alice@example.com");
+        assert!(matches!(get_email(&emails, "bob"), Err(GetEmailError::EmailNotFound { username }) if username == "bob"));
+        assert!(matches!(get_email(&emails, "carol"), Err(GetEmailError::EmailNotFound { username }) if username == "carol"));
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_iter!`] macro
+    #[allow(dead_code)]
+    fn multiply_evens(numbers: Vec<u32>) -> Result<Vec<u32>, MultiplyEvensError> {
+        use MultiplyEvensError::*;
+        let results = numbers.into_iter().map(|number| {
+            use CheckEvenError::*;
+            if number % 2 == 0 {
+                Ok(number * 10)
+            } else {
+                Err(NumberNotEven {
+                    number,
+                })
+            }
+        });
+        Ok(handle_iter!(results, CheckEvensFailed))
+    }
+
+    /// This function tests the [`crate::handle_into_iter!`] macro
+    #[allow(dead_code)]
+    async fn read_files(paths: Vec<PathBuf>) -> Result<Vec<String>, ReadFilesError> {
+        use ReadFilesError::*;
+        let results = paths
+            .into_iter()
+            .map(check_file)
+            .collect::<JoinSet<_>>()
+            .join_all()
+            .await;
+        Ok(handle_into_iter!(results, CheckFilesFailed))
+    }
+
+    #[allow(dead_code)]
+    async fn read_files_ref(paths: Vec<PathBuf>) -> Result<Vec<String>, ReadFilesRefError> {
+        use ReadFilesRefError::*;
+        let iter = paths.iter().map(check_file_ref);
+        let results = join_all(iter).await;
+        let items = paths.into_iter().map(PathBufDisplay::from);
+        let (outputs, _items) = handle_iter_of_refs!(results.into_iter(), items, CheckFilesRefFailed);
+        Ok(outputs)
+    }
+
+    // async fn check_file(path: &Path)
+
+    /// This function exists to test error handling in async code
+    #[allow(dead_code)]
+    async fn process(number: u32) -> Result<u32, ProcessError> {
+        Ok(number)
+    }
+
+    #[derive(Error, Debug)]
+    enum PrintNameCommandError {
+        #[error("failed to parse config")]
+        ParseConfigFailed { source: ParseConfigError },
+    }
+
+    /// Variants don't have the `format` field because every variant already corresponds to a single specific format
+    /// Some variants have the `path` field because the `contents` depends on `path`
+    /// Some `source` field types are wrapped in `Box` according to suggestion from `result_large_err` lint
+    #[derive(Error, Debug)]
+    enum ParseConfigError {
+        #[error("failed to read the file: {path}")]
+        ReadFileFailed { path: PathBuf, source: io::Error },
+        #[error("failed to deserialize the file contents from JSON: {path}")]
+        DeserializeFromJson { path: PathBuf, contents: String, source: Box<serde_json::error::Error> },
+        #[error("failed to deserialize the file contents from TOML: {path}")]
+        DeserializeFromToml { path: PathBuf, contents: String, source: Box<toml::de::Error> },
+    }
+
+    #[allow(dead_code)]
+    #[derive(Error, Debug)]
+    enum ProcessError {}
+
+    #[allow(dead_code)]
+    #[derive(Copy, Clone, Debug)]
+    enum Format {
+        Json,
+        Toml,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    struct Config {
+        name: String,
+        timeout: u64,
+        parallel: bool,
+    }
+
+    #[allow(dead_code)]
+    fn parse_even_number(input: &str) -> Result<u32, ParseEvenNumberError> {
+        use ParseEvenNumberError::*;
+        let number = handle!(input.parse::<u32>(), InputParseFailed);
+        handle_bool!(number % 2 != 0, NumberNotEven, number);
+        Ok(number)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseEvenNumberError {
+        #[error("failed to parse input")]
+        InputParseFailed { source: <u32 as FromStr>::Err },
+        #[error("number is not even: {number}")]
+        NumberNotEven { number: u32 },
+    }
+
+    /// This function tests the [`crate::validate!`] macro
+    fn validate_user(name: &str, age: u32) -> Result<(), ValidateUserError> {
+        validate!(
+            ValidateUserError,
+            [
+                (!name.is_empty(), NameEmpty),
+                (age < 150, AgeTooHigh, age),
+                (age > 0, AgeIsZero)
+            ]
+        );
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum ValidateUserError {
+        #[error("name is empty")]
+        NameEmpty,
+        #[error("age is too high: {age}")]
+        AgeTooHigh { age: u32 },
+        #[error("age is zero")]
+        AgeIsZero,
+        #[error("user is invalid")]
+        Multiple { source: ErrVec<ValidateUserError> },
+    }
+
+    #[test]
+    fn must_validate_all_checks() {
+        use ValidateUserError::*;
+        assert!(validate_user("alice", 30).is_ok());
+        let error = validate_user("", 200).unwrap_err();
+        let Multiple {
+            source,
+        } = error
+        else {
+            panic!("expected Multiple, got {error:?}")
+        };
+        assert_eq!(source.len(), 2);
+        assert!(matches!(source[0], NameEmpty));
+        assert!(matches!(
+            source[1],
+            AgeTooHigh {
+                age: 200
+            }
+        ));
+    }
+
+    /// This function tests the [`crate::handle_maybe!`] macro
+    fn call_ffi(input: u32, ffi: impl FnOnce(u32) -> Result<u32, Option<Box<dyn std::error::Error + Send + Sync>>>) -> Result<u32, CallFfiError> {
+        use CallFfiError::*;
+        let output = handle_maybe!(ffi(input), FfiCallFailed, input);
+        Ok(output)
+    }
+
+    #[derive(Error, Debug)]
+    enum CallFfiError {
+        #[error("failed to call FFI function with input {input}")]
+        FfiCallFailed { source: Option<Box<dyn std::error::Error + Send + Sync>>, input: u32 },
+    }
+
+    #[test]
+    fn must_handle_maybe() {
+        use CallFfiError::*;
+        assert!(matches!(call_ffi(2, |input| Ok(input * 2)), Ok(4)));
+        let error = call_ffi(3, |_| Err(Some(io::Error::other("device busy").into()))).unwrap_err();
+        assert!(matches!(&error, FfiCallFailed { source: Some(source), input: 3 } if source.to_string() == "device busy"));
+        let error = call_ffi(5, |_| Err(None)).unwrap_err();
+        assert!(matches!(
+            error,
+            FfiCallFailed {
+                source: None,
+                input: 5
+            }
+        ));
+    }
+
+    /// This function tests the [`crate::wrap!`] macro in a sync function
+    fn parse_port(input: String) -> Result<u16, ParsePortError> {
+        use ParsePortError::*;
+        let port = input
+            .trim()
+            .parse::<u16>()
+            .map_err(wrap!(ParseFailed, input))?;
+        Ok(port)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParsePortError {
+        #[error("failed to parse port: '{input}'")]
+        ParseFailed { source: std::num::ParseIntError, input: String },
+    }
+
+    /// This function tests the [`crate::wrap!`] macro in an async function
+    async fn read_name(dir: &Path) -> Result<String, ReadNameError> {
+        use ReadNameError::*;
+        let path = dir.join("name.txt");
+        let name = read_to_string(&path)
+            .await
+            .map_err(wrap!(ReadToStringFailed, path: path.clone()))?;
+        Ok(name.trim().to_string())
+    }
+
+    #[derive(Error, Debug)]
+    enum ReadNameError {
+        #[error("failed to read the file: {path}", path = display_path(path))]
+        ReadToStringFailed { source: io::Error, path: PathBuf },
+    }
+
+    #[test]
+    fn must_wrap_in_sync_fn() {
+        assert!(matches!(parse_port(" 8080 ".to_string()), Ok(8080)));
+        let error = parse_port("http".to_string()).unwrap_err();
+        assert!(matches!(error, ParsePortError::ParseFailed { input, .. } if input == "http"));
+    }
+
+    #[test]
+    fn must_evaluate_wrap_args_when_creating_closure() {
+        use ParsePortError::*;
+        let mut events = Vec::new();
+        let result = "80"
+            .parse::<u16>()
+            .inspect(|_| events.push("parsed"))
+            .map_err(wrap!(ParseFailed, input: {
+                events.push("input evaluated");
+                "80"
+            }));
+        assert!(matches!(result, Ok(80)));
+        assert_eq!(events, ["parsed", "input evaluated"]);
+    }
+
+    #[tokio::test]
+    async fn must_wrap_in_async_fn() {
+        let dir = Path::new("/nonexistent-errgonomic-dir");
+        let error = read_name(dir).await.unwrap_err();
+        assert!(matches!(error, ReadNameError::ReadToStringFailed { path, .. } if path == dir.join("name.txt")));
+    }
+
+    /// This function tests the [`crate::handle_map_source!`] macro
+    fn load_schema(name: String, load: impl FnOnce(&str) -> Result<String, LoadError>) -> Result<String, LoadSchemaError> {
+        use LoadSchemaError::*;
+        let schema = handle_map_source!(load(&name), LoadFailed, |error: LoadError| error.inner, name);
+        Ok(schema)
+    }
+
+    #[derive(Error, Debug)]
+    #[error("failed to load: {inner}")]
+    struct LoadError {
+        inner: io::Error,
+    }
+
+    #[derive(Error, Debug)]
+    enum LoadSchemaError {
+        #[error("failed to load schema '{name}'")]
+        LoadFailed { source: io::Error, name: String },
+    }
+
+    #[test]
+    fn must_handle_map_source() -> TestResult {
+        let schema = load_schema("user".to_string(), |name| Ok(format!("schema for {name}")))?;
+        assert_eq!(schema, "schema for user");
+        let error = load_schema("user".to_string(), |_| {
+            Err(LoadError {
+                inner: io::Error::new(io::ErrorKind::NotFound, "schema not found"),
+            })
+        })
+        .unwrap_err();
+        assert!(matches!(error, LoadSchemaError::LoadFailed { source, name } if source.kind() == io::ErrorKind::NotFound && name == "user"));
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_deadline!`] macro
+    fn fetch_quota(input: &str) -> Result<u32, FetchQuotaError> {
+        use FetchQuotaError::*;
+        let quota = handle_deadline!(input.parse::<u32>(), QuotaParseFailed, input: input.to_string());
+        Ok(quota)
+    }
+
+    #[derive(Error, Debug)]
+    enum FetchQuotaError {
+        #[error("failed to parse quota '{input}'")]
+        QuotaParseFailed { source: Deadlined, input: String },
+    }
+
+    #[test]
+    fn must_handle_deadline() {
+        use FetchQuotaError::*;
+        use std::time::{Duration, Instant};
+        let error = fetch_quota("x").unwrap_err();
+        assert!(matches!(&error, QuotaParseFailed { source, .. } if source.remaining.is_none()));
+        assert!(!deadline_exceeded(&error));
+        let error = Deadline::scope(Deadline::after(Duration::from_secs(3600)), || fetch_quota("x")).unwrap_err();
+        assert!(matches!(&error, QuotaParseFailed { source, .. } if source.remaining.is_some_and(|remaining| !remaining.is_zero())));
+        assert!(!deadline_exceeded(&error));
+        let error = Deadline::scope(Deadline::new(Instant::now()), || fetch_quota("x")).unwrap_err();
+        assert!(matches!(&error, QuotaParseFailed { source, .. } if source.remaining == Some(Duration::ZERO) && source.source.is::<std::num::ParseIntError>()));
+        assert!(deadline_exceeded(&error));
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to parse quota 'x'\n- invalid digit found in string");
+    }
+
+    /// This function tests the `@count` value of the iterator macros
+    fn parse_batch(inputs: impl IntoIterator<Item = &'static str>) -> Result<Vec<u32>, ParseBatchError> {
+        use ParseBatchError::*;
+        let results = inputs.into_iter().map(|input| input.parse::<u32>());
+        let numbers = handle_iter!(results, ParseBatchFailed, total: @count);
+        Ok(numbers)
+    }
+
+    fn parse_batch_with_budget(inputs: Vec<&'static str>, total: usize) -> Result<Vec<u32>, ParseBatchError> {
+        use ParseBatchError::*;
+        let results = inputs.iter().map(|input| input.parse::<u32>());
+        let numbers = handle_iter!(results, ParseBatchFailed, total);
+        Ok(numbers)
+    }
+
+    fn parse_batch_with_total(inputs: Vec<&'static str>) -> Result<Vec<u32>, ParseBatchError> {
+        use ParseBatchError::*;
+        let results: Vec<_> = inputs.iter().map(|input| input.parse::<u32>()).collect();
+        let numbers = handle_into_iter!(results, InvalidInputs);
+        Ok(numbers)
+    }
+
+    fn parse_batch_of_refs(inputs: Vec<String>) -> Result<Vec<u32>, ParseBatchError> {
+        use ParseBatchError::*;
+        let results = inputs.iter().map(|input| input.parse::<u32>());
+        let (numbers, _inputs) = handle_iter_of_refs!(results, inputs.clone(), RefsParseFailed, total: @count);
+        Ok(numbers)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseBatchError {
+        #[error("failed to parse {len} of {total} inputs", len = source.len())]
+        ParseBatchFailed { source: ErrVec<std::num::ParseIntError>, total: usize },
+        #[error("failed to parse inputs")]
+        InvalidInputs { source: ErrVecWithTotal<std::num::ParseIntError> },
+        #[error("failed to parse {len} of {total} inputs", len = source.len())]
+        RefsParseFailed { source: ErrVec<ItemError<String, std::num::ParseIntError>>, total: usize },
+    }
+
+    #[test]
+    fn must_capture_total() {
+        use ParseBatchError::*;
+        let error = parse_batch(["1", "x", "3", "y", "5"]).unwrap_err();
+        assert_eq!(error.to_string(), "failed to parse 2 of 5 inputs");
+        // the iterator is consumed lazily, so the early-terminating adapter limits the total
+        let inputs = ["x", "2", "y", "4", "z", "6"];
+        let error = parse_batch(inputs.into_iter().take_while(|input| *input != "z")).unwrap_err();
+        assert!(matches!(error, ParseBatchFailed { source, total: 4 } if source.len() == 2));
+        let error = parse_batch_with_total(vec!["x", "2", "3"]).unwrap_err();
+        assert!(matches!(&error, InvalidInputs { source } if source.len() == 1 && source.total == 3));
+        let error = parse_batch_of_refs(vec!["x".to_string(), "2".to_string()]).unwrap_err();
+        assert!(matches!(error, RefsParseFailed { source, total: 2 } if source.len() == 1));
+        // the local variable named `total` is passed as is
+        let error = parse_batch_with_budget(vec!["x", "2"], 100).unwrap_err();
+        assert!(matches!(error, ParseBatchFailed { source, total: 100 } if source.len() == 1));
+    }
+
+    /// This function tests the `@count` value of the [`crate::handle_iter_unit!`] macro
+    fn check_ports(inputs: Vec<&'static str>) -> Result<(), CheckPortsError> {
+        use CheckPortsError::*;
+        let results = inputs.iter().map(|input| input.parse::<u16>().map(drop));
+        handle_iter_unit!(results, PortsInvalid, checked: @count);
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum CheckPortsError {
+        #[error("found {len} invalid ports among {checked}", len = source.len())]
+        PortsInvalid { source: ErrVec<std::num::ParseIntError>, checked: usize },
+    }
+
+    #[test]
+    fn must_count_unit_results() {
+        assert!(check_ports(vec!["80", "443"]).is_ok());
+        let error = check_ports(vec!["80", "http", "70000"]).unwrap_err();
+        assert_eq!(error.to_string(), "found 2 invalid ports among 3");
+    }
+
+    /// This function tests the [`crate::handle_id!`] macro
+    fn parse_page(input: &str) -> Result<u32, ParsePageError> {
+        use ParsePageError::*;
+        let page = handle_id!(input.parse::<u32>(), PageParseFailed, input: input.to_string());
+        Ok(page)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParsePageError {
+        #[error("failed to parse page '{input}' (request {request_id})", request_id = request_id.as_ref().map(ToString::to_string).unwrap_or_default())]
+        PageParseFailed { source: std::num::ParseIntError, request_id: Option<RequestId>, input: String },
+    }
+
+    #[test]
+    fn must_handle_id() {
+        let error = RequestId::scope(RequestId::new("req-42"), || parse_page("x")).unwrap_err();
+        assert_eq!(error.to_string(), "failed to parse page 'x' (request req-42)");
+        assert!(matches!(
+            parse_page("x"),
+            Err(ParsePageError::PageParseFailed {
+                request_id: None,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn must_handle_id_in_task() {
+        let result = crate::with_request_id("req-7", async {
+            tokio::task::yield_now().await;
+            parse_page("x")
+        })
+        .await;
+        assert!(matches!(result, Err(ParsePageError::PageParseFailed { request_id: Some(RequestId(id)), .. }) if id == "req-7"));
+    }
+
+    /// This function tests the [`crate::handle_env!`] macro
+    fn parse_threads(input: &str) -> Result<u32, ParseThreadsError> {
+        use ParseThreadsError::*;
+        let threads = handle_env!(input.parse::<u32>(), ThreadsParseFailed, env: ["CARGO_PKG_NAME", "ERRGONOMIC_TEST_UNSET"], input: input.to_string());
+        Ok(threads)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseThreadsError {
+        #[error("failed to parse threads: '{input}'")]
+        ThreadsParseFailed { source: std::num::ParseIntError, env: Vec<(String, Option<OsStrDisplay>)>, input: String },
+    }
+
+    #[test]
+    fn must_handle_env() -> TestResult {
+        // Cargo sets `CARGO_PKG_NAME` for the test process, so the environment doesn't have to be modified
+        assert_eq!(parse_threads("4")?, 4);
+        let error = parse_threads("many").unwrap_err();
+        let ParseThreadsError::ThreadsParseFailed {
+            env,
+            ..
+        } = error;
+        assert_eq!(
+            env,
+            vec![
+                ("CARGO_PKG_NAME".to_string(), Some(OsString::from("errgonomic").into())),
+                ("ERRGONOMIC_TEST_UNSET".to_string(), None)
+            ]
+        );
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_os_var!`] macro
+    fn read_log_dir(name: &str) -> Result<String, ReadLogDirError> {
+        use ReadLogDirError::*;
+        let dir = handle_os_var!(name, LogDirReadFailed);
+        Ok(dir)
+    }
+
+    #[derive(Error, Debug)]
+    enum ReadLogDirError {
+        #[error("environment variable '{name}' is not set or is not valid UTF-8")]
+        LogDirReadFailed { name: String, value: Option<OsStrDisplay> },
+    }
+
+    #[test]
+    fn must_handle_os_var() -> TestResult {
+        // Cargo sets `CARGO_PKG_NAME` for the test process, so the environment doesn't have to be modified (the non-UTF-8 values are tested in `read_env_vars`)
+        assert_eq!(read_log_dir("CARGO_PKG_NAME")?, "errgonomic");
+        assert!(matches!(
+            read_log_dir("ERRGONOMIC_TEST_LOG_DIR_UNSET"),
+            Err(ReadLogDirError::LogDirReadFailed {
+                value: None,
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_status!`] macro
+    fn run_shell(script: &str) -> Result<(), RunShellError> {
+        use RunShellError::*;
+        let status = handle!(shell(script).status(), StatusFailed, script: script.to_string());
+        handle_status!(status, ScriptFailed, script: script.to_string());
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_output!`] macro
+    fn run_shell_output(script: &str) -> Result<String, RunShellError> {
+        use RunShellError::*;
+        let output = handle!(shell(script).output(), StatusFailed, script: script.to_string());
+        let output = handle_output!(output, OutputInvalid, script: script.to_string());
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn shell(script: &str) -> std::process::Command {
+        let mut command = if cfg!(windows) { std::process::Command::new("cmd") } else { std::process::Command::new("sh") };
+        command
+            .arg(if cfg!(windows) { "/C" } else { "-c" })
+            .arg(script);
+        command
+    }
+
+    #[derive(Error, Debug)]
+    enum RunShellError {
+        #[error("failed to run script '{script}'")]
+        StatusFailed { source: io::Error, script: String },
+        #[error("script '{script}' failed with code {code:?} (signal {signal:?})")]
+        ScriptFailed { code: Option<i32>, signal: Option<i32>, script: String },
+        #[error("script '{script}' failed with code {code:?} (signal {signal:?}): {stderr}")]
+        OutputInvalid { code: Option<i32>, signal: Option<i32>, stderr: String, script: String },
+    }
+
+    #[test]
+    fn must_handle_status() {
+        use RunShellError::*;
+        assert!(run_shell("exit 0").is_ok());
+        assert!(matches!(
+            run_shell("exit 3"),
+            Err(ScriptFailed {
+                code: Some(3),
+                signal: None,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn must_handle_status_signal() {
+        use RunShellError::*;
+        assert!(matches!(
+            run_shell("false"),
+            Err(ScriptFailed {
+                code: Some(1),
+                signal: None,
+                ..
+            })
+        ));
+        // SIGTERM is 15 on every unix
+        assert!(matches!(
+            run_shell("kill -TERM $$"),
+            Err(ScriptFailed {
+                code: None,
+                signal: Some(15),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn must_handle_output() -> TestResult {
+        use RunShellError::*;
+        assert_eq!(run_shell_output("echo ok")?.trim_end(), "ok");
+        let error = run_shell_output("echo oops 1>&2 && exit 2").unwrap_err();
+        assert!(matches!(error, OutputInvalid { code: Some(2), stderr, .. } if stderr.trim_end() == "oops"));
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_sourced_parse!`] macro
+    fn parse_timeout(timeout: Sourced<String>) -> Result<Sourced<u64>, ParseTimeoutError> {
+        use ParseTimeoutError::*;
+        let timeout = handle_sourced_parse!(timeout, TimeoutInvalid);
+        Ok(timeout)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseTimeoutError {
+        #[error("invalid timeout: {timeout}")]
+        TimeoutInvalid { source: std::num::ParseIntError, timeout: Sourced<String> },
+    }
+
+    #[test]
+    fn must_handle_sourced_parse() -> TestResult {
+        let timeout = parse_timeout(Sourced::new("30".to_string(), Provenance::Default))?;
+        assert_eq!(timeout, Sourced::new(30, Provenance::Default));
+        let error = parse_timeout(Sourced::new("abc".to_string(), Provenance::EnvVar("APP_TIMEOUT".to_string()))).unwrap_err();
+        assert_eq!(error.to_string(), "invalid timeout: abc (from env var APP_TIMEOUT)");
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_from!`] macro without fields
+    fn parse_count(input: &str) -> Result<u32, ParseCountError> {
+        let count = handle_from!(input.parse::<u32>());
+        Ok(count)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseCountError {
+        #[error("failed to parse count")]
+        ParseFailed {
+            #[from]
+            source: std::num::ParseIntError,
+        },
+    }
+
+    /// This function tests the [`crate::handle_from!`] macro with fields
+    fn parse_limit(input: String, max: u32) -> Result<u32, ParseLimitError> {
+        use ParseLimitError::*;
+        let limit = handle_from!(input.parse::<u32>(), input);
+        handle_bool!(limit > max, LimitTooHigh, limit, max);
+        Ok(limit)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseLimitError {
+        #[error("failed to parse limit: '{input}'")]
+        ParseFailed { source: std::num::ParseIntError, input: String },
+        #[error("limit is too high: {limit} > {max}")]
+        LimitTooHigh { limit: u32, max: u32 },
+    }
+
+    impl_from_source!(ParseLimitError, ParseFailed, std::num::ParseIntError, { input: String });
+
+    #[test]
+    fn must_handle_from() {
+        assert!(matches!(parse_count("7"), Ok(7)));
+        assert!(matches!(parse_count("seven"), Err(ParseCountError::ParseFailed { .. })));
+        assert!(matches!(parse_limit("10".to_string(), 20), Ok(10)));
+        assert!(matches!(parse_limit("ten".to_string(), 20), Err(ParseLimitError::ParseFailed { input, .. }) if input == "ten"));
+    }
+
+    /// This struct tests the [`crate::try_from_impl!`] macro
+    #[derive(Debug)]
+    struct RawProfile {
+        name: String,
+        age: String,
+        scores: Vec<String>,
+    }
+
+    #[derive(Eq, PartialEq, Debug)]
+    struct Profile {
+        name: String,
+        age: u8,
+        scores: Vec<u32>,
+    }
+
+    try_from_impl!(RawProfile, Profile, ConvertRawProfileToProfileError, |RawProfile {
+                                                                              name,
+                                                                              age,
+                                                                              scores,
+                                                                          }| {
+        handle_bool!(name.trim().is_empty(), NameEmpty, name);
+        let age = convert!(age.parse::<u8>(), AgeParseFailed, age);
+        let scores = convert_vec!(scores, |score: String| score.parse::<u32>(), ScoresParseFailed);
+        Ok(Profile {
+            name,
+            age,
+            scores,
+        })
+    });
+
+    #[derive(Error, Debug)]
+    enum ConvertRawProfileToProfileError {
+        #[error("name is empty: '{name}'")]
+        NameEmpty { name: String },
+        #[error("failed to parse age: '{age}'")]
+        AgeParseFailed { source: std::num::ParseIntError, age: String },
+        #[error("failed to parse {len} scores", len = source.len())]
+        ScoresParseFailed { source: ErrVec<ItemError<usize, std::num::ParseIntError>> },
+    }
+
+    #[test]
+    fn must_try_from_impl() -> TestResult {
+        use ConvertRawProfileToProfileError::*;
+        let raw = |name: &str, age: &str, scores: &[&str]| RawProfile {
+            name: name.to_string(),
+            age: age.to_string(),
+            scores: scores.iter().map(ToString::to_string).collect(),
+        };
+        let profile = Profile::try_from(raw("alice", "30", &["1", "2"]))?;
+        assert_eq!(
+            profile,
+            Profile {
+                name: "alice".to_string(),
+                age: 30,
+                scores: vec![1, 2]
+            }
+        );
+        assert!(matches!(Profile::try_from(raw(" ", "30", &[])), Err(NameEmpty { .. })));
+        assert!(matches!(Profile::try_from(raw("alice", "300", &[])), Err(AgeParseFailed { age, .. }) if age == "300"));
+        let error = Profile::try_from(raw("alice", "30", &["1", "x", "3", "y"])).unwrap_err();
+        let ScoresParseFailed {
+            source,
+        } = error
+        else {
+            panic!("expected ScoresParseFailed, got {error:?}")
+        };
+        let indexes: Vec<_> = source.iter().map(|error| error.item).collect();
+        assert_eq!(indexes, vec![1, 3]);
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    struct RawPort {
+        host: String,
+        port: String,
+    }
+
+    #[derive(Debug)]
+    struct Port(u16);
+
+    try_from_impl!(RawPort, Port, ConvertRawPortToPortError, |raw| {
+        let port = try_from_handle!(raw, |raw: &RawPort| raw.port.parse::<u16>(), PortParseFailed);
+        Ok(Port(port))
+    });
+
+    #[derive(Error, Debug)]
+    enum ConvertRawPortToPortError {
+        #[error("failed to parse port '{port}' for host '{host}'", port = raw.port, host = raw.host)]
+        PortParseFailed { source: std::num::ParseIntError, raw: RawPort },
+    }
+
+    #[test]
+    fn must_keep_input_in_try_from_error() -> TestResult {
+        let raw = RawPort {
+            host: "localhost".to_string(),
+            port: "80x".to_string(),
+        };
+        let ConvertRawPortToPortError::PortParseFailed {
+            raw: mut retry,
+            ..
+        } = Port::try_from(raw).unwrap_err();
+        assert_eq!(retry.host, "localhost");
+        retry.port.pop();
+        assert_eq!(Port::try_from(retry)?.0, 80);
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum FindEvenError {
+        #[error("even number not found")]
+        NotFound,
+    }
+
+    #[derive(Error, Debug)]
+    enum MultiplyEvensError {
+        #[error("failed to check {len} numbers", len = source.len())]
+        CheckEvensFailed { source: ErrVec<CheckEvenError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum ReadFilesError {
+        #[error("failed to check {len} files", len = source.len())]
+        CheckFilesFailed { source: ErrVec<CheckFileError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum ReadFilesRefError {
+        #[error("failed to check {len} files", len = source.len())]
+        CheckFilesRefFailed { source: ErrVec<ItemError<PathBufDisplay, CheckFileRefError>> },
+    }
+
+    #[derive(Error, Debug)]
+    enum CheckEvenError {
+        #[error("number is not even: {number}")]
+        NumberNotEven { number: u32 },
+    }
+
+    async fn check_file(path: PathBuf) -> Result<String, CheckFileError> {
+        use CheckFileError::*;
+        let content = handle!(read_to_string(&path).await, ReadToStringFailed, path);
+        handle_bool!(content.is_empty(), FileIsEmpty, path);
+        Ok(content)
+    }
+
+    #[derive(Error, Debug)]
+    enum CheckFileError {
+        #[error("failed to read the file to string: {path}")]
+        ReadToStringFailed { path: PathBuf, source: io::Error },
+        #[error("file is empty: {path}")]
+        FileIsEmpty { path: PathBuf },
+    }
+
+    async fn check_file_ref(path: &PathBuf) -> Result<String, CheckFileRefError> {
+        use CheckFileRefError::*;
+        let content = handle!(read_to_string(&path).await, ReadToStringFailed);
+        handle_bool!(content.is_empty(), FileIsEmpty);
+        Ok(content)
+    }
+
+    #[derive(Error, Debug)]
+    enum CheckFileRefError {
+        #[error("failed to read the file to string")]
+        ReadToStringFailed { source: io::Error },
+        #[error("file is empty")]
+        FileIsEmpty,
+    }
+
+    #[derive(Clone, Debug)]
+    struct Db {
+        user: User,
+    }
+
+    #[derive(Clone, Debug)]
+    struct User {
+        username: String,
+    }
+
+    #[allow(dead_code)]
+    fn get_username(db: Arc<RwLock<Db>>) -> Result<String, GetUsernameError> {
+        use GetUsernameError::*;
+        // `db.read()` returns `LockResult` whose Err variant is `PoisonError<RwLockReadGuard<'_, T>>`, which contains an anonymous lifetime
+        // The error enum returned from this function must contain only owned fields, so it can't contain a `source` that has a lifetime
+        // Therefore, we have to use handle_discard!, although it is discouraged
+        let guard = handle_discard!(db.read(), AcquireReadLockFailed);
+        let username = guard.user.username.clone();
+        Ok(username)
+    }
+
+    #[derive(Error, Debug)]
+    pub enum GetUsernameError {
+        #[error("failed to acquire read lock")]
+        AcquireReadLockFailed,
+    }
+
+    /// This function tests the [`crate::handle_lock!`] macro
+    fn get_username_locked(db: &RwLock<Db>) -> Result<String, GetUsernameLockedError> {
+        use GetUsernameLockedError::*;
+        let guard = handle_lock!(db.read(), AcquireReadLockFailed);
+        Ok(guard.user.username.clone())
+    }
+
+    #[derive(Error, Debug)]
+    pub enum GetUsernameLockedError {
+        #[error("failed to acquire read lock")]
+        AcquireReadLockFailed { source: LockPoisonedError },
+    }
+
+    #[test]
+    fn must_handle_lock() -> TestResult {
+        let db = Arc::new(RwLock::new(Db {
+            user: User {
+                username: "alice".to_string(),
+            },
+        }));
+        assert_eq!(get_username_locked(&db)?, "alice");
+        let db_clone = db.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = db_clone.write();
+            panic!("poison the lock");
+        })
+        .join();
+        let error = get_username_locked(&db).unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to acquire read lock\n- read lock poisoned");
+        Ok(())
+    }
+
+    /// This function tests the [`crate::map_err_discard!`] macro in the tail position
+    fn get_user_for_update(db: &RwLock<Db>, username: String) -> Result<User, GetUserForUpdateError> {
+        use GetUserForUpdateError::*;
+        map_err_discard!(db.write().map(|guard| guard.user.clone()), AcquireWriteLockFailed, username)
+    }
+
+    #[derive(Error, Debug)]
+    pub enum GetUserForUpdateError {
+        #[error("failed to acquire write lock for user '{username}'")]
+        AcquireWriteLockFailed { username: String },
+    }
+
+    #[test]
+    fn must_map_err_discard() {
+        let db = Arc::new(RwLock::new(Db {
+            user: User {
+                username: "alice".to_string(),
+            },
+        }));
+        let user = get_user_for_update(&db, "alice".to_string()).expect("always succeeds because the lock is not poisoned");
+        assert_eq!(user.username, "alice");
+        let db_clone = db.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = db_clone.write();
+            panic!("poison the lock");
+        })
+        .join();
+        let error = get_user_for_update(&db, "alice".to_string()).unwrap_err();
+        assert!(matches!(error, GetUserForUpdateError::AcquireWriteLockFailed { username } if username == "alice"));
+    }
+
+    #[allow(dead_code)]
+    fn get_answer(prompt: String, get_response: &mut impl FnMut(String) -> Result<WeirdResponse, io::Error>) -> Result<String, GetAnswerError> {
+        use GetAnswerError::*;
+        // Since the `get_response` external API doesn't return the `prompt` in its error, we have to clone `prompt` before passing it as argument, so that we could pass it to the error enum variant
+        // Cloning may be necessary with external APIs that don't return arguments in errors, but it must not be necessary in our code
+        let mut response = handle!(get_response(prompt.clone()), GetResponseFailed, prompt);
+        handle_opt_take!(response.error, ResponseContainsError, error);
+        Ok(response.answer)
+    }
+
+    /// OpenAI Responses API returns a response with `error: Option<WeirdResponseError>` field, which is weird, but must still be handled
+    #[derive(Debug)]
+    pub struct WeirdResponse {
+        answer: String,
+        error: Option<WeirdResponseError>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Error, Debug)]
+    pub enum WeirdResponseError {
+        #[error("prompt is empty")]
+        PromptIsEmpty,
+        #[error("context limit reached")]
+        ContextLimitReached,
+    }
+
+    /// [`GetAnswerError::GetResponseFailed`] `error` attribute doesn't contain a reference to `{prompt}` because the prompt can be very long, so it would make the error message very long, which is undesirable
+    #[derive(Error, Debug)]
+    pub enum GetAnswerError {
+        #[error("failed to get response")]
+        GetResponseFailed { source: io::Error, prompt: String },
+        #[error("response contains an error")]
+        ResponseContainsError { error: WeirdResponseError },
+    }
+
+    fn read_port(input: &str) -> Result<u16, Box<dyn std::error::Error + Send + Sync>> {
+        let port = box_err!(input.parse::<u16>(), "failed to parse port '{input}'");
+        Ok(port)
+    }
+
+    #[test]
+    fn must_box_err() {
+        assert_eq!(read_port("8080").ok(), Some(8080));
+        let error = read_port("http").unwrap_err();
+        assert_eq!(ErrorDisplayer(error.as_ref()).to_string(), "- failed to parse port 'http'\n- invalid digit found in string");
+    }
+
+    fn parse_ports(inputs: Vec<&str>) -> Result<Vec<u16>, ListenersParseError> {
+        use ListenerParseError::*;
+        use ListenersParseError::*;
+        let results = inputs.into_iter().map(|input| {
+            let port = try_handle!(input.parse::<u16>(), PortParseFailed, input: input.to_string())?;
+            try_handle_bool!(port == 0, PortIsZero)?;
+            Ok(port)
+        });
+        Ok(handle_iter!(results, ListenersParseFailed))
+    }
+
+    #[test]
+    fn must_try_handle_in_closures() -> TestResult {
+        use ListenerParseError::*;
+        assert_eq!(parse_ports(vec!["80", "443"])?, vec![80, 443]);
+        let error = parse_ports(vec!["80", "http", "0"]).unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to parse 2 ports\n- encountered 2 errors\n  * - failed to parse port 'http'\n    - invalid digit found in string\n  * - port is zero");
+        let hosts = [Some("localhost"), None];
+        let results: Vec<Result<&str, ListenerParseError>> = hosts
+            .iter()
+            .map(|host| try_handle_opt!(*host, HostMissing))
+            .collect();
+        assert!(matches!(results.as_slice(), [Ok("localhost"), Err(HostMissing)]));
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum ListenersParseError {
+        #[error("failed to parse {len} ports", len = source.len())]
+        ListenersParseFailed { source: ErrVec<ListenerParseError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum ListenerParseError {
+        #[error("failed to parse port '{input}'")]
+        PortParseFailed { source: std::num::ParseIntError, input: String },
+        #[error("port is zero")]
+        PortIsZero,
+        #[error("host is missing")]
+        HostMissing,
+    }
+
+    fn sum_all(inputs: Vec<&str>) -> Result<u32, SumAllError> {
+        use SumAllError::*;
+        let numbers = handle_iter!(inputs.into_iter().map(str::parse::<u32>), ParseAllFailed);
+        Ok(numbers.into_iter().sum())
+    }
+
+    fn average_all(inputs: Vec<&str>) -> Result<u32, SumAllError> {
+        use SumAllError::*;
+        let numbers = handle_iter_non_empty!(inputs.into_iter().map(str::parse::<u32>), ParseAllFailed, InputsEmpty);
+        Ok(numbers.iter().sum::<u32>() / numbers.len() as u32)
+    }
+
+    #[test]
+    fn must_accept_empty_input_in_handle_iter() -> TestResult {
+        assert_eq!(sum_all(vec![])?, 0);
+        assert_eq!(sum_all(vec!["1", "2"])?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn must_reject_empty_input_in_handle_iter_non_empty() -> TestResult {
+        assert!(matches!(average_all(vec![]), Err(SumAllError::InputsEmpty {})));
+        assert!(matches!(average_all(vec!["x"]), Err(SumAllError::ParseAllFailed { source }) if source.len() == 1));
+        assert_eq!(average_all(vec!["1", "3"])?, 2);
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum SumAllError {
+        #[error("failed to parse {len} inputs", len = source.len())]
+        ParseAllFailed { source: ErrVec<std::num::ParseIntError> },
+        #[error("inputs are empty")]
+        InputsEmpty {},
+    }
+
+    /// A message that can't be cloned (so the recovery is guaranteed not to clone it)
+    #[derive(Eq, PartialEq, Debug)]
+    struct Job {
+        id: u64,
+    }
+
+    #[derive(Error, Debug)]
+    enum ChannelError {
+        #[error("failed to send job {id}", id = message.id)]
+        JobSendFailed { source: crate::ChannelClosed, message: Job },
+        #[error("queue '{queue}' is empty")]
+        QueueEmpty { queue: String },
+        #[error("queue '{queue}' is disconnected")]
+        QueueDisconnected { queue: String },
+    }
+
+    fn send_job(tx: &std::sync::mpsc::Sender<Job>, job: Job) -> Result<(), ChannelError> {
+        use ChannelError::*;
+        handle_send!(tx.send(job), JobSendFailed);
+        Ok(())
+    }
+
+    fn try_recv_job(rx: &std::sync::mpsc::Receiver<Job>) -> Result<Job, ChannelError> {
+        use ChannelError::*;
+        let job = handle_recv!(rx.try_recv(), QueueEmpty, QueueDisconnected, queue: "jobs".to_string());
+        Ok(job)
+    }
+
+    #[test]
+    fn must_recover_unsent_message() -> TestResult {
+        let (tx, rx) = std::sync::mpsc::channel();
+        send_job(
+            &tx,
+            Job {
+                id: 1,
+            },
+        )?;
+        assert_eq!(
+            try_recv_job(&rx)?,
+            Job {
+                id: 1
+            }
+        );
+        assert!(matches!(try_recv_job(&rx), Err(ChannelError::QueueEmpty { queue }) if queue == "jobs"));
+        drop(rx);
+        let error = send_job(
+            &tx,
+            Job {
+                id: 2,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            ChannelError::JobSendFailed {
+                message: Job {
+                    id: 2
+                },
+                ..
+            }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn must_detect_disconnected_channel() {
+        let (tx, rx) = std::sync::mpsc::channel::<Job>();
+        drop(tx);
+        assert!(matches!(try_recv_job(&rx), Err(ChannelError::QueueDisconnected { .. })));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn must_recover_unsent_message_from_tokio_channel() {
+        use ChannelError::*;
+        async fn send(tx: &tokio::sync::mpsc::Sender<Job>, job: Job) -> Result<(), ChannelError> {
+            handle_send!(tx.send(job).await, JobSendFailed);
+            Ok(())
+        }
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        drop(rx);
+        assert!(matches!(
+            send(
+                &tx,
+                Job {
+                    id: 3
+                }
+            )
+            .await,
+            Err(JobSendFailed {
+                message: Job {
+                    id: 3
+                },
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn must_recover_unsent_message_from_crossbeam_channel() {
+        use ChannelError::*;
+        fn send(tx: &crossbeam_channel::Sender<Job>, job: Job) -> Result<(), ChannelError> {
+            handle_send!(tx.send(job), JobSendFailed);
+            Ok(())
+        }
+        fn recv(rx: &crossbeam_channel::Receiver<Job>) -> Result<Job, ChannelError> {
+            let job = handle_recv!(rx.try_recv(), QueueEmpty, QueueDisconnected, queue: "jobs".to_string());
+            Ok(job)
+        }
+        let (tx, rx) = crossbeam_channel::unbounded();
+        assert!(matches!(recv(&rx), Err(QueueEmpty { .. })));
+        drop(rx);
+        assert!(matches!(
+            send(
+                &tx,
+                Job {
+                    id: 4
+                }
+            ),
+            Err(JobSendFailed {
+                message: Job {
+                    id: 4
+                },
+                ..
+            })
+        ));
+        let (tx, rx) = crossbeam_channel::unbounded::<Job>();
+        drop(tx);
+        assert!(matches!(recv(&rx), Err(QueueDisconnected { .. })));
+    }
+
+    /// This function tests the [`crate::handle_io!`] macro
+    fn read_manifest(path: PathBuf) -> Result<String, ReadManifestError> {
+        use ReadManifestError::*;
+        let contents = handle_io!(std::fs::read_to_string(&path), ReadFailed, path);
+        Ok(contents)
+    }
+
+    #[derive(Error, Debug)]
+    enum ReadManifestError {
+        #[error("failed to read the manifest")]
+        ReadFailed { source: crate::PathIoError },
+    }
+
+    #[test]
+    fn must_handle_io() {
+        let path = PathBuf::from("/nonexistent/errgonomic/Cargo.toml");
+        let error = read_manifest(path.clone()).unwrap_err();
+        let ReadManifestError::ReadFailed {
+            source,
+        } = &error;
+        assert_eq!(source.path, path);
+        assert_eq!(source.source.kind(), io::ErrorKind::NotFound);
+        assert!(
+            ErrorDisplayer(&error)
+                .to_string()
+                .starts_with("- failed to read the manifest\n- I/O operation failed for '/nonexistent/errgonomic/Cargo.toml'\n- ")
+        );
+    }
+
+    /// This function tests the [`crate::handle_seq!`] macro
+    fn parse_batch_size(input: &str) -> Result<u32, ParseBatchSizeError> {
+        use ParseBatchSizeError::*;
+        let batch_size = handle_seq!(input.parse::<u32>(), BatchSizeParseFailed, input: input.to_string());
+        Ok(batch_size)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseBatchSizeError {
+        #[error("failed to parse the batch size: '{input}'")]
+        BatchSizeParseFailed { source: std::num::ParseIntError, seq: u64, input: String },
+    }
+
+    #[test]
+    fn must_handle_seq() -> TestResult {
+        let get_seq = |input: &str| match parse_batch_size(input) {
+            Err(ParseBatchSizeError::BatchSizeParseFailed {
+                seq,
+                ..
+            }) => seq,
+            Ok(_) => panic!("expected an error for '{input}'"),
+        };
+        let first = get_seq("x");
+        assert_eq!(parse_batch_size("8")?, 8);
+        let second = get_seq("y");
+        assert!(first < second, "{first} < {second}");
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_iter_flat!`] macro
+    fn parse_chunks(chunks: &[&[&str]]) -> Result<Vec<Vec<u32>>, ParseChunksError> {
+        use ParseChunksError::*;
+        let numbers = handle_iter_flat!(chunks.iter().map(|chunk| parse_chunk(chunk)), ChunksParseFailed);
+        Ok(numbers)
+    }
+
+    fn parse_chunk(chunk: &[&str]) -> Result<Vec<u32>, ErrVec<std::num::ParseIntError>> {
+        let numbers = chunk.iter().map(|input| input.parse::<u32>());
+        crate::partition_result(numbers).map_err(ErrVec::from)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseChunksError {
+        #[error("failed to parse the chunks")]
+        ChunksParseFailed { source: ErrVec<std::num::ParseIntError> },
+    }
+
+    #[test]
+    fn must_flatten_chunk_errors() -> TestResult {
+        assert_eq!(parse_chunks(&[&["1", "2"], &["3"]])?, [vec![1, 2], vec![3]]);
+        let ParseChunksError::ChunksParseFailed {
+            source,
+        } = parse_chunks(&[&["a", "1"], &["2"], &["", "b"]]).unwrap_err();
+        let messages = source.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "invalid digit found in string",
+                "cannot parse integer from empty string",
+                "invalid digit found in string"
+            ]
+        );
+        Ok(())
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    struct Checkpoint {
+        next_index: usize,
+    }
+
+    /// This function tests the [`crate::handle_resumable!`] macro
+    fn import_items(items: &[&str], checkpoint: Option<Checkpoint>, imported: &mut Vec<usize>) -> Result<(), ImportItemsError> {
+        use ImportItemsError::*;
+        let start = checkpoint
+            .map(|checkpoint| checkpoint.next_index)
+            .unwrap_or_default();
+        for (index, item) in items.iter().enumerate().skip(start) {
+            let number = handle_resumable!(
+                item.parse::<u32>(),
+                ItemImportFailed,
+                Checkpoint {
+                    next_index: index
+                },
+                index
+            );
+            imported.push(number as usize);
+        }
+        Ok(())
+    }
+
+    #[derive(Error, Debug)]
+    enum ImportItemsError {
+        #[error("failed to import the item #{index}")]
+        ItemImportFailed { source: crate::Resumable<std::num::ParseIntError, Checkpoint>, index: usize },
+    }
+
+    #[derive(Error, Debug)]
+    enum RunImportError {
+        #[error("failed to run the import")]
+        ImportFailed { source: ImportItemsError },
+    }
+
+    #[test]
+    fn must_resume_from_checkpoint() -> TestResult {
+        let mut items = ["0", "1", "2", "3", "4", "5", "x", "7", "8", "9"];
+        let mut imported = Vec::new();
+        let source = import_items(&items, None, &mut imported).unwrap_err();
+        let error = RunImportError::ImportFailed {
+            source,
+        };
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to run the import\n- failed to import the item #6\n- invalid digit found in string");
+        let checkpoint = *crate::find_resume_state::<std::num::ParseIntError, Checkpoint>(&error).ok_or("the checkpoint must be attached to the error")?;
+        assert_eq!(checkpoint.next_index, 6);
+        items[6] = "6";
+        import_items(&items, Some(checkpoint), &mut imported)?;
+        assert_eq!(imported, (0..10).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_push!`] macro
+    fn parse_ports_each(inputs: &[&str]) -> Result<Vec<u16>, ErrVec<ParsePortsError>> {
+        use ParsePortsError::*;
+        let mut ports = Vec::new();
+        let mut errors = ErrVec::new([]);
+        for input in inputs {
+            let port = handle_push!(input.parse::<u16>(), &mut errors, |source| PortsParseFailed {
+                source,
+                input: input.to_string()
+            });
+            ports.push(port);
+        }
+        errors.into_result(ports)
+    }
+
+    #[derive(Error, Debug)]
+    enum ParsePortsError {
+        #[error("failed to parse the port '{input}'")]
+        PortsParseFailed { source: std::num::ParseIntError, input: String },
+    }
 
-    #[allow(dead_code)]
-    struct PrintNameCommand {
-        dir: PathBuf,
-        format: Format,
+    #[test]
+    fn must_push_errors_and_continue() -> TestResult {
+        assert_eq!(parse_ports_each(&["80", "443"])?, [80, 443]);
+        let errors = parse_ports_each(&["x", "80", "70000", "443"]).unwrap_err();
+        let inputs = errors
+            .iter()
+            .map(
+                |ParsePortsError::PortsParseFailed {
+                     input,
+                     ..
+                 }| input.as_str(),
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(inputs, ["x", "70000"]);
+        Ok(())
     }
 
-    #[allow(dead_code)]
-    impl PrintNameCommand {
-        async fn run(self) -> Result<(), PrintNameCommandError> {
-            use PrintNameCommandError::*;
-            let Self {
-                dir,
-                format,
-            } = self;
-            let config = handle!(parse_config(&dir, format).await, ParseConfigFailed);
-            println!("{}", config.name);
-            Ok(())
-        }
+    /// This function tests the [`crate::handle_iter_dedup!`] macro
+    fn read_configs(dir: &std::path::Path, names: &[&str]) -> Result<Vec<String>, ReadConfigsError> {
+        use ReadConfigsError::*;
+        let contents = handle_iter_dedup!(
+            names
+                .iter()
+                .map(|name| std::fs::read_to_string(dir.join(name))),
+            ConfigsReadFailed,
+            total: @count
+        );
+        Ok(contents)
     }
 
-    /// This function tests the [`crate::handle!`] macro
-    #[allow(dead_code)]
-    async fn parse_config(dir: &Path, format: Format) -> Result<Config, ParseConfigError> {
-        use Format::*;
-        use ParseConfigError::*;
-        let path_buf = dir.join("config.json");
-        let contents = handle!(read_to_string(&path_buf).await, ReadFileFailed, path: path_buf);
-        match format {
-            Json => {
-                let config = handle!(serde_json::de::from_str(&contents), DeserializeFromJson, path: path_buf, contents);
-                Ok(config)
-            }
-            Toml => {
-                let config = handle!(toml::de::from_str(&contents), DeserializeFromToml, path: path_buf, contents);
-                Ok(config)
-            }
-        }
+    #[derive(Error, Debug)]
+    enum ReadConfigsError {
+        #[error("failed to read {total} configs")]
+        ConfigsReadFailed { source: ErrVec<crate::Deduped<io::Error>>, total: usize },
     }
 
-    /// This function tests the [`crate::handle_opt!`] macro
-    #[allow(dead_code)]
-    fn find_even(numbers: Vec<u32>) -> Result<u32, FindEvenError> {
-        use FindEvenError::*;
-        let even = handle_opt!(numbers.iter().find(|x| *x % 2 == 0), NotFound);
-        Ok(*even)
+    #[test]
+    fn must_dedup_identical_errors() {
+        let error = read_configs(std::path::Path::new("/nonexistent/errgonomic"), &["a.toml", "b.toml", "c.toml"]).unwrap_err();
+        let ReadConfigsError::ConfigsReadFailed {
+            source,
+            total,
+        } = &error;
+        assert_eq!(*total, 3);
+        assert_eq!(source.len(), 1);
+        assert_eq!(source[0].count, 3);
+        assert!(
+            ErrorDisplayer(&error)
+                .to_string()
+                .ends_with("(occurred 3 times)"),
+            "{}",
+            ErrorDisplayer(&error)
+        );
     }
 
-    /// This function tests the [`crate::handle_iter!`] macro
-    #[allow(dead_code)]
-    fn multiply_evens(numbers: Vec<u32>) -> Result<Vec<u32>, MultiplyEvensError> {
-        use MultiplyEvensError::*;
-        let results = numbers.into_iter().map(|number| {
-            use CheckEvenError::*;
-            if number % 2 == 0 {
-                Ok(number * 10)
-            } else {
-                Err(NumberNotEven {
-                    number,
-                })
+    /// This function tests the [`crate::handle_join!`] macro
+    #[cfg(feature = "tokio")]
+    async fn run_job(id: u32, job: impl Future<Output = Result<u32, io::Error>> + Send + 'static) -> Result<u32, RunJobError> {
+        use RunJobError::*;
+        let output = handle_join!(tokio::spawn(job).await, JobFailed, id);
+        Ok(output)
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(Error, Debug)]
+    enum RunJobError {
+        #[error("job {id} failed")]
+        JobFailed { source: crate::TaskError<io::Error>, id: u32 },
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn must_flatten_join_result() {
+        use crate::TaskError;
+        use RunJobError::*;
+        assert!(matches!(run_job(1, async { Ok(42) }).await, Ok(42)));
+        let error = run_job(2, async { Err(io::Error::other("disk full")) })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            JobFailed {
+                source: TaskError::TaskFailed { .. },
+                id: 2
             }
-        });
-        Ok(handle_iter!(results, CheckEvensFailed))
+        ));
+        let error = run_job(3, async { panic!("job panicked") })
+            .await
+            .unwrap_err();
+        assert!(matches!(&error, JobFailed { source: TaskError::JoinFailed { source }, id: 3 } if source.is_panic()));
     }
 
-    /// This function tests the [`crate::handle_into_iter!`] macro
-    #[allow(dead_code)]
-    async fn read_files(paths: Vec<PathBuf>) -> Result<Vec<String>, ReadFilesError> {
-        use ReadFilesError::*;
-        let results = paths
-            .into_iter()
-            .map(check_file)
-            .collect::<JoinSet<_>>()
-            .join_all()
-            .await;
-        Ok(handle_into_iter!(results, CheckFilesFailed))
+    /// This function tests the [`crate::handle_finalize!`] macro
+    fn write_report(path: &str, data: &[u8], fail: bool) -> Result<Vec<u8>, WriteReportError> {
+        use WriteReportError::*;
+        let mut writer = crate::MustFinalize::new(Vec::new());
+        writer.extend_from_slice(data);
+        let bytes = handle_finalize!(writer, |bytes| if fail { Err(io::Error::other("upload aborted")) } else { Ok(bytes) }, UploadCompleteFailed, path);
+        Ok(bytes)
     }
 
-    #[allow(dead_code)]
-    async fn read_files_ref(paths: Vec<PathBuf>) -> Result<Vec<String>, ReadFilesRefError> {
-        use ReadFilesRefError::*;
-        let iter = paths.iter().map(check_file_ref);
-        let results = join_all(iter).await;
-        let items = paths.into_iter().map(PathBufDisplay::from);
-        let (outputs, _items) = handle_iter_of_refs!(results.into_iter(), items, CheckFilesRefFailed);
-        Ok(outputs)
+    #[derive(Error, Debug)]
+    enum WriteReportError {
+        #[error("failed to complete the upload of '{path}'")]
+        UploadCompleteFailed { source: io::Error, path: String },
     }
 
-    // async fn check_file(path: &Path)
+    #[test]
+    fn must_handle_finalize() -> TestResult {
+        assert_eq!(write_report("report.txt", b"ok", false)?, b"ok");
+        assert!(matches!(write_report("report.txt", b"ok", true), Err(WriteReportError::UploadCompleteFailed { path, .. }) if path == "report.txt"));
+        Ok(())
+    }
 
-    /// This function exists to test error handling in async code
-    #[allow(dead_code)]
-    async fn process(number: u32) -> Result<u32, ProcessError> {
-        Ok(number)
+    /// This function tests the [`crate::MapErrIntoExt::map_err_into`] method with the [`crate::handle_iter!`] macro
+    fn parse_hosts(inputs: &[&str]) -> Result<Vec<std::net::IpAddr>, ParseHostsError> {
+        use crate::MapErrIntoExt;
+        use ParseHostsError::*;
+        let hosts = handle_iter!(
+            inputs
+                .iter()
+                .map(|input| input.parse::<std::net::IpAddr>())
+                .map_err_into::<HostError>(),
+            HostsInvalid
+        );
+        Ok(hosts)
     }
 
     #[derive(Error, Debug)]
-    enum PrintNameCommandError {
-        #[error("failed to parse config")]
-        ParseConfigFailed { source: ParseConfigError },
+    #[error("invalid host")]
+    struct HostError {
+        source: std::net::AddrParseError,
+    }
+
+    impl From<std::net::AddrParseError> for HostError {
+        fn from(source: std::net::AddrParseError) -> Self {
+            Self {
+                source,
+            }
+        }
     }
 
-    /// Variants don't have the `format` field because every variant already corresponds to a single specific format
-    /// Some variants have the `path` field because the `contents` depends on `path`
-    /// Some `source` field types are wrapped in `Box` according to suggestion from `result_large_err` lint
     #[derive(Error, Debug)]
-    enum ParseConfigError {
-        #[error("failed to read the file: {path}")]
-        ReadFileFailed { path: PathBuf, source: io::Error },
-        #[error("failed to deserialize the file contents from JSON: {path}")]
-        DeserializeFromJson { path: PathBuf, contents: String, source: Box<serde_json::error::Error> },
-        #[error("failed to deserialize the file contents from TOML: {path}")]
-        DeserializeFromToml { path: PathBuf, contents: String, source: Box<toml::de::Error> },
+    enum ParseHostsError {
+        #[error("invalid hosts")]
+        HostsInvalid { source: ErrVec<HostError> },
+    }
+
+    #[test]
+    fn must_map_err_into_before_handle_iter() -> TestResult {
+        assert_eq!(parse_hosts(&["127.0.0.1", "::1"])?.len(), 2);
+        let error = parse_hosts(&["127.0.0.1", "localhost", "x"]).unwrap_err();
+        assert!(matches!(&error, ParseHostsError::HostsInvalid { source } if source.len() == 2));
+        Ok(())
+    }
+
+    crate::static_errors! {
+        /// The input has ended before the header was complete
+        HeaderTruncated, HEADER_TRUNCATED, "the header is truncated";
+        MagicMissing, MAGIC_MISSING, "the magic bytes are missing";
+    }
+
+    /// This function tests the [`crate::handle_bool_static!`] and [`crate::static_errors!`] macros
+    fn parse_header(bytes: &[u8]) -> Result<u8, ParseHeaderError> {
+        use ParseHeaderError::*;
+        handle_bool_static!(bytes.len() < 4, &HEADER_TRUNCATED, HeaderTruncatedAt, offset: bytes.len());
+        handle_bool_static!(&bytes[..3] != b"ERR", &MAGIC_MISSING, HeaderInvalid);
+        Ok(bytes[3])
     }
 
-    #[allow(dead_code)]
     #[derive(Error, Debug)]
-    enum ProcessError {}
+    enum ParseHeaderError {
+        #[error("failed to parse the header at offset {offset}")]
+        HeaderTruncatedAt { source: &'static HeaderTruncated, offset: usize },
+        #[error("invalid header")]
+        HeaderInvalid { source: &'static (dyn core::error::Error + Send + Sync) },
+    }
 
     #[allow(dead_code)]
-    #[derive(Copy, Clone, Debug)]
-    enum Format {
-        Json,
-        Toml,
+    #[derive(Error, Debug)]
+    enum LargeHeaderError {
+        #[error("failed to parse the header")]
+        HeaderInvalid { source: io::Error, bytes: [u8; 200] },
     }
 
-    #[derive(Serialize, Deserialize, Clone, Debug)]
-    struct Config {
+    #[test]
+    fn must_render_static_errors() -> TestResult {
+        assert_eq!(parse_header(b"ERR\x07")?, 7);
+        let error = parse_header(b"ER").unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to parse the header at offset 2\n- the header is truncated");
+        let error = parse_header(b"PNG\x07").unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- invalid header\n- the magic bytes are missing");
+        assert_eq!(core::mem::size_of::<HeaderTruncated>(), 0);
+        assert!(core::mem::size_of::<Result<u8, ParseHeaderError>>() <= 3 * core::mem::size_of::<usize>());
+        assert!(core::mem::size_of::<Result<u8, LargeHeaderError>>() > 200);
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_validate!`] macro
+    fn check_server(server: &ServerConfig) -> Result<(), CheckServerError> {
+        use CheckServerError::*;
+        handle_validate!(server, ServerConfigInvalid, {
+            name => check_not_empty(&server.name),
+            listeners[i] for (i, listener) in server.listeners.iter().enumerate() => check_port(listener.port),
+            host => check_not_empty(&server.host),
+        }, name: server.name.clone());
+        Ok(())
+    }
+
+    fn check_not_empty(value: &str) -> Result<(), FieldValueError> {
+        if value.is_empty() { Err(FieldValueError::ValueEmpty) } else { Ok(()) }
+    }
+
+    fn check_port(port: u16) -> Result<(), FieldValueError> {
+        if port == 0 { Err(FieldValueError::ValueNotPositive) } else { Ok(()) }
+    }
+
+    struct ServerConfig {
         name: String,
-        timeout: u64,
-        parallel: bool,
+        host: String,
+        listeners: Vec<ListenerConfig>,
     }
 
-    #[allow(dead_code)]
-    fn parse_even_number(input: &str) -> Result<u32, ParseEvenNumberError> {
-        use ParseEvenNumberError::*;
-        let number = handle!(input.parse::<u32>(), InputParseFailed);
-        handle_bool!(number % 2 != 0, NumberNotEven, number);
-        Ok(number)
+    struct ListenerConfig {
+        port: u16,
     }
 
     #[derive(Error, Debug)]
-    enum ParseEvenNumberError {
-        #[error("failed to parse input")]
-        InputParseFailed { source: <u32 as FromStr>::Err },
-        #[error("number is not even: {number}")]
-        NumberNotEven { number: u32 },
+    enum FieldValueError {
+        #[error("must not be empty")]
+        ValueEmpty,
+        #[error("must be greater than 0")]
+        ValueNotPositive,
     }
 
     #[derive(Error, Debug)]
-    enum FindEvenError {
-        #[error("even number not found")]
-        NotFound,
+    enum CheckServerError {
+        #[error("invalid server config '{name}'")]
+        ServerConfigInvalid { source: crate::ValidationErrors, name: String },
+    }
+
+    #[test]
+    fn must_validate_fields() {
+        let listeners = |ports: &[u16]| {
+            ports
+                .iter()
+                .map(|&port| ListenerConfig {
+                    port,
+                })
+                .collect::<Vec<_>>()
+        };
+        let server = ServerConfig {
+            name: "main".to_string(),
+            host: "localhost".to_string(),
+            listeners: listeners(&[80, 443]),
+        };
+        assert!(check_server(&server).is_ok());
+        let server = ServerConfig {
+            name: String::new(),
+            host: "localhost".to_string(),
+            listeners: listeners(&[0, 80, 443, 0]),
+        };
+        let error = check_server(&server).unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- invalid server config ''\n- found 3 invalid fields\n  * - name: must not be empty\n  * - listeners[0]: must be greater than 0\n  * - listeners[3]: must be greater than 0");
+    }
+
+    /// This function tests the [`crate::finish!`] macro
+    fn save_rows(writer: &mut impl std::io::Write, rows: &[&str], name: &str) -> Result<(), SaveRowsError> {
+        use SaveRowsError::*;
+        for row in rows {
+            handle!(writeln!(writer, "{row}"), WriteRowFailed, name: name.to_string());
+        }
+        finish!(writer.flush(), FlushFailed, name: name.to_string())
     }
 
     #[derive(Error, Debug)]
-    enum MultiplyEvensError {
-        #[error("failed to check {len} numbers", len = source.len())]
-        CheckEvensFailed { source: ErrVec<CheckEvenError> },
+    enum SaveRowsError {
+        #[error("failed to write a row to '{name}'")]
+        WriteRowFailed { source: io::Error, name: String },
+        #[error("failed to flush '{name}'")]
+        FlushFailed { source: io::Error, name: String },
+    }
+
+    /// A writer that accepts every write but fails to flush
+    struct UnflushableWriter(Vec<u8>);
+
+    impl std::io::Write for UnflushableWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::other("disk is full"))
+        }
+    }
+
+    #[test]
+    fn must_finish_with_failed_flush() {
+        let mut writer = UnflushableWriter(Vec::new());
+        let error = save_rows(&mut writer, &["a", "b"], "rows.txt").unwrap_err();
+        assert_eq!(writer.0, b"a\nb\n");
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to flush 'rows.txt'\n- disk is full");
+        assert!(save_rows(&mut Vec::new(), &["a"], "rows.txt").is_ok());
+    }
+
+    /// This function tests the [`crate::handle_loc!`] macro
+    fn parse_port_with_location(input: &str) -> Result<u16, ParsePortLocError> {
+        use ParsePortLocError::*;
+        let port = handle_loc!(input.parse::<u16>(), PortParseFailed, input: input.to_string());
+        Ok(port)
     }
 
+    const PARSE_PORT_LOC_LINE: u32 = line!() - 4;
+
     #[derive(Error, Debug)]
-    enum ReadFilesError {
-        #[error("failed to check {len} files", len = source.len())]
-        CheckFilesFailed { source: ErrVec<CheckFileError> },
+    enum ParsePortLocError {
+        #[error("failed to parse port '{input}' at {location}")]
+        PortParseFailed { source: std::num::ParseIntError, input: String, location: &'static str },
+    }
+
+    #[test]
+    fn must_capture_location() -> TestResult {
+        assert_eq!(parse_port_with_location("80")?, 80);
+        let ParsePortLocError::PortParseFailed {
+            location,
+            ..
+        } = parse_port_with_location("x").unwrap_err();
+        assert_eq!(location, format!("{}:{PARSE_PORT_LOC_LINE}", file!()));
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_iter!`] macro with the `context` block
+    fn import_rows(dir: &Path, tenant: &str, rows: &[&str]) -> Result<Vec<u32>, ImportRowsError> {
+        use ImportRowsError::*;
+        let results = rows.iter().map(|row| row.parse::<u32>());
+        let ids = handle_iter!(results, ImportRowsFailed, total: @count, context: { dir: dir.display(), tenant: tenant });
+        Ok(ids)
     }
 
     #[derive(Error, Debug)]
-    enum ReadFilesRefError {
-        #[error("failed to check {len} files", len = source.len())]
-        CheckFilesRefFailed { source: ErrVec<ItemError<PathBufDisplay, CheckFileRefError>> },
+    enum ImportRowsError {
+        #[error("failed to import {total} rows")]
+        ImportRowsFailed { source: ErrVecWithContext<std::num::ParseIntError>, total: usize },
+    }
+
+    #[test]
+    fn must_attach_context() -> TestResult {
+        assert_eq!(import_rows(Path::new("/data/in"), "acme", &["1", "2"])?, [1, 2]);
+        let error = import_rows(Path::new("/data/in"), "acme=corp", &["1", "x", "y"]).unwrap_err();
+        let ImportRowsError::ImportRowsFailed {
+            source,
+            total,
+        } = &error;
+        assert_eq!(*total, 3);
+        assert_eq!(
+            source.context,
+            [
+                ("dir".to_string(), "/data/in".to_string()),
+                ("tenant".to_string(), "acme=corp".to_string())
+            ]
+        );
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to import 3 rows\n- encountered 2 errors [dir=/data/in, tenant=acme\\=corp]\n  * - invalid digit found in string\n  * - invalid digit found in string");
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_iter!`] macro with the argument values that span several tokens
+    fn parse_labeled(label: &str, inputs: &[&str], with_context: bool) -> Result<Vec<u32>, ParseLabeledError> {
+        use ParseLabeledError::*;
+        let results = inputs.iter().map(|input| input.parse::<u32>());
+        if with_context {
+            Ok(handle_iter!(results, LabeledWithContextFailed, name: label.to_uppercase(), context: { label: label.trim() }))
+        } else {
+            Ok(handle_iter!(results, LabeledFailed, name: label.to_uppercase()))
+        }
     }
 
     #[derive(Error, Debug)]
-    enum CheckEvenError {
-        #[error("number is not even: {number}")]
-        NumberNotEven { number: u32 },
+    enum ParseLabeledError {
+        #[error("failed to parse {name}")]
+        LabeledFailed { source: ErrVec<std::num::ParseIntError>, name: String },
+        #[error("failed to parse {name}")]
+        LabeledWithContextFailed { source: ErrVecWithContext<std::num::ParseIntError>, name: String },
     }
 
-    async fn check_file(path: PathBuf) -> Result<String, CheckFileError> {
-        use CheckFileError::*;
-        let content = handle!(read_to_string(&path).await, ReadToStringFailed, path);
-        handle_bool!(content.is_empty(), FileIsEmpty, path);
-        Ok(content)
+    #[test]
+    fn must_accept_multi_token_values() {
+        use ParseLabeledError::*;
+        assert_eq!(parse_labeled("ports", &["1"], false).ok(), Some(vec![1]));
+        let error = parse_labeled("ports", &["x"], false);
+        assert!(matches!(error, Err(LabeledFailed { name, .. }) if name == "PORTS"));
+        let error = parse_labeled(" ports ", &["x"], true);
+        assert!(matches!(error, Err(LabeledWithContextFailed { name, source }) if name == " PORTS " && source.context == [("label".to_string(), "ports".to_string())]));
+    }
+
+    /// This function tests the [`crate::handle_iter_first!`] macro
+    fn parse_first_failure(inputs: impl IntoIterator<Item = String>) -> Result<Vec<u32>, ParseFirstError> {
+        use ParseFirstError::*;
+        let results = inputs.into_iter().map(|input| input.parse::<u32>());
+        let numbers = handle_iter_first!(results, ParseFirstFailed, total: @count);
+        Ok(numbers)
     }
 
     #[derive(Error, Debug)]
-    enum CheckFileError {
-        #[error("failed to read the file to string: {path}")]
-        ReadToStringFailed { path: PathBuf, source: io::Error },
-        #[error("file is empty: {path}")]
-        FileIsEmpty { path: PathBuf },
+    enum ParseFirstError {
+        #[error("failed to parse input {total}")]
+        ParseFirstFailed { source: ErrVec<std::num::ParseIntError>, total: usize },
     }
 
-    async fn check_file_ref(path: &PathBuf) -> Result<String, CheckFileRefError> {
-        use CheckFileRefError::*;
-        let content = handle!(read_to_string(&path).await, ReadToStringFailed);
-        handle_bool!(content.is_empty(), FileIsEmpty);
-        Ok(content)
+    #[test]
+    fn must_stop_at_first_error() -> TestResult {
+        assert_eq!(parse_first_failure(["1", "2"].map(String::from))?, [1, 2]);
+        let mut consumed = 0;
+        let inputs = (0..1_000_000)
+            .inspect(|_| consumed += 1)
+            .map(|number| if number == 2 { "x".to_string() } else { number.to_string() });
+        let error = parse_first_failure(inputs).unwrap_err();
+        let ParseFirstError::ParseFirstFailed {
+            source,
+            total,
+        } = &error;
+        assert_eq!(source.len(), 1);
+        assert_eq!(*total, 3);
+        assert_eq!(consumed, 3);
+        Ok(())
+    }
+
+    /// This function tests the [`crate::handle_fi!`] macro
+    #[cfg(feature = "fault-injection")]
+    fn write_chunks(writer: &mut Vec<u8>, chunks: &[&str]) -> Result<(), WriteChunksError> {
+        use WriteChunksError::*;
+        use std::io::Write;
+        for (index, chunk) in chunks.iter().enumerate() {
+            handle_fi!("write_chunk", writer.write_all(chunk.as_bytes()), WriteChunkFailed, index);
+        }
+        Ok(())
     }
 
     #[derive(Error, Debug)]
-    enum CheckFileRefError {
-        #[error("failed to read the file to string")]
-        ReadToStringFailed { source: io::Error },
-        #[error("file is empty")]
-        FileIsEmpty,
+    enum WriteChunksError {
+        #[error("failed to write chunk {index}")]
+        WriteChunkFailed { source: io::Error, index: usize },
     }
 
-    #[derive(Clone, Debug)]
-    struct Db {
-        user: User,
+    #[cfg(feature = "fault-injection")]
+    #[test]
+    fn must_inject_fault_into_second_write() -> TestResult {
+        crate::inject_faults("write_chunk", [None, Some(io::Error::other("disk full"))]);
+        let mut writer = Vec::new();
+        let error = write_chunks(&mut writer, &["a", "b", "c"]).unwrap_err();
+        assert_eq!(writer, b"a");
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to write chunk 1\n- disk full");
+        assert_eq!(crate::pending_faults("write_chunk"), 0);
+        let mut writer = Vec::new();
+        write_chunks(&mut writer, &["a", "b", "c"])?;
+        assert_eq!(writer, b"abc");
+        Ok(())
     }
 
-    #[derive(Clone, Debug)]
-    struct User {
-        username: String,
+    #[cfg(not(feature = "fault-injection"))]
+    #[test]
+    fn must_not_evaluate_fault_label() -> TestResult {
+        fn write(writer: &mut Vec<u8>) -> Result<(), WriteChunksError> {
+            use WriteChunksError::*;
+            use std::io::Write;
+            handle_fi!(unreachable!("the label must not be evaluated"), writer.write_all(b"a"), WriteChunkFailed, index: 0usize);
+            Ok(())
+        }
+        let mut writer = Vec::new();
+        write(&mut writer)?;
+        assert_eq!(writer, b"a");
+        Ok(())
     }
 
-    #[allow(dead_code)]
-    fn get_username(db: Arc<RwLock<Db>>) -> Result<String, GetUsernameError> {
-        use GetUsernameError::*;
-        // `db.read()` returns `LockResult` whose Err variant is `PoisonError<RwLockReadGuard<'_, T>>`, which contains an anonymous lifetime
-        // The error enum returned from this function must contain only owned fields, so it can't contain a `source` that has a lifetime
-        // Therefore, we have to use handle_discard!, although it is discouraged
-        let guard = handle_discard!(db.read(), AcquireReadLockFailed);
-        let username = guard.user.username.clone();
-        Ok(username)
+    /// This function tests the [`crate::handle_anyhow!`] macro
+    #[cfg(feature = "anyhow")]
+    fn read_settings() -> anyhow::Result<String> {
+        use anyhow::Context;
+        let contents = Err(io::Error::other("permission denied")).context("failed to read '/etc/app/settings.toml'")?;
+        Ok(contents)
     }
 
-    #[derive(Error, Debug)]
-    pub enum GetUsernameError {
-        #[error("failed to acquire read lock")]
-        AcquireReadLockFailed,
+    #[cfg(feature = "anyhow")]
+    fn load_settings() -> Result<String, LoadSettingsError> {
+        use LoadSettingsError::*;
+        let settings = handle_anyhow!(read_settings(), ReadSettingsFailed, profile: "default");
+        Ok(settings)
     }
 
-    #[allow(dead_code)]
-    fn get_answer(prompt: String, get_response: &mut impl FnMut(String) -> Result<WeirdResponse, io::Error>) -> Result<String, GetAnswerError> {
-        use GetAnswerError::*;
-        // Since the `get_response` external API doesn't return the `prompt` in its error, we have to clone `prompt` before passing it as argument, so that we could pass it to the error enum variant
-        // Cloning may be necessary with external APIs that don't return arguments in errors, but it must not be necessary in our code
-        let mut response = handle!(get_response(prompt.clone()), GetResponseFailed, prompt);
-        handle_opt_take!(response.error, ResponseContainsError, error);
-        Ok(response.answer)
+    #[cfg(feature = "anyhow")]
+    fn load_settings_boxed() -> Result<String, LoadSettingsError> {
+        use LoadSettingsError::*;
+        let settings = handle_anyhow!(read_settings(), BoxedReadFailed);
+        Ok(settings)
     }
 
-    /// OpenAI Responses API returns a response with `error: Option<WeirdResponseError>` field, which is weird, but must still be handled
-    #[derive(Debug)]
-    pub struct WeirdResponse {
-        answer: String,
-        error: Option<WeirdResponseError>,
+    #[cfg(feature = "anyhow")]
+    fn load_settings_anyhow() -> Result<String, LoadSettingsError> {
+        use LoadSettingsError::*;
+        let settings = handle!(read_settings(), SettingsUnreadable);
+        Ok(settings)
     }
 
-    #[allow(dead_code)]
+    #[cfg(feature = "anyhow")]
     #[derive(Error, Debug)]
-    pub enum WeirdResponseError {
-        #[error("prompt is empty")]
-        PromptIsEmpty,
-        #[error("context limit reached")]
-        ContextLimitReached,
+    enum LoadSettingsError {
+        #[error("failed to load settings for profile '{profile}'")]
+        ReadSettingsFailed { source: crate::AnyhowError, profile: String },
+        #[error("failed to load settings")]
+        BoxedReadFailed { source: Box<dyn std::error::Error + Send + Sync> },
+        #[error("failed to load settings")]
+        SettingsUnreadable { source: anyhow::Error },
     }
 
-    /// [`GetAnswerError::GetResponseFailed`] `error` attribute doesn't contain a reference to `{prompt}` because the prompt can be very long, so it would make the error message very long, which is undesirable
-    #[derive(Error, Debug)]
-    pub enum GetAnswerError {
-        #[error("failed to get response")]
-        GetResponseFailed { source: io::Error, prompt: String },
-        #[error("response contains an error")]
-        ResponseContainsError { error: WeirdResponseError },
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn must_handle_anyhow() {
+        let expected_chain = "- failed to read '/etc/app/settings.toml'\n- permission denied";
+        let error = load_settings().unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), format!("- failed to load settings for profile 'default'\n{expected_chain}"));
+        let error = load_settings_boxed().unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), format!("- failed to load settings\n{expected_chain}"));
+        let error = load_settings_anyhow().unwrap_err();
+        assert_eq!(ErrorDisplayer(&error).to_string(), format!("- failed to load settings\n{expected_chain}"));
     }
 }
@@ -0,0 +1,45 @@
+mod as_dyn_error;
+mod error_children;
+mod from_errors;
+mod from_source;
+mod map_err_into_ext;
+mod quiet;
+
+pub use as_dyn_error::*;
+pub use error_children::*;
+pub use from_errors::*;
+pub use from_source::*;
+pub use map_err_into_ext::*;
+pub use quiet::*;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "std")] {
+        mod error_path;
+        mod lock_guard;
+        mod notification_sink;
+        mod recover_message;
+        mod recv_error_kind;
+
+        pub use error_path::*;
+        pub use lock_guard::*;
+        pub use notification_sink::*;
+        pub use recover_message::*;
+        pub use recv_error_kind::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "http")] {
+        mod http_error;
+
+        pub use http_error::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tonic")] {
+        mod grpc_status;
+
+        pub use grpc_status::*;
+    }
+}
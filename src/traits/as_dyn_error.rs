@@ -0,0 +1,39 @@
+use core::error::Error;
+
+/// Converts the error into `&(dyn Error + 'static)`, so that the renderer can inspect it (e.g. find the children of an aggregate via [`ErrorRegistry`](crate::ErrorRegistry)).
+///
+/// This trait is implemented for every sized `'static` error and for the `dyn Error` trait objects (including the `Send` and `Sync` ones), so the rendering functions accept both `&MyError` and `&dyn Error`.
+/// The method is not called `as_dyn_error`, because the `source` impls that are derived by `thiserror` call a method with that name (the calls would be ambiguous in the modules that import this trait).
+pub trait AsDynError {
+    fn as_dyn(&self) -> &(dyn Error + 'static);
+}
+
+impl<T: Error + 'static> AsDynError for T {
+    fn as_dyn(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + 'static {
+    fn as_dyn(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + Send + 'static {
+    fn as_dyn(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + Sync + 'static {
+    fn as_dyn(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + Send + Sync + 'static {
+    fn as_dyn(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
@@ -0,0 +1,192 @@
+use crate::ErrorChild;
+use alloc::boxed::Box;
+use core::fmt::Formatter;
+
+/// An aggregate error that contains multiple child errors (e.g. [`ErrVec`](crate::ErrVec)).
+///
+/// Implement this trait for your own aggregate types and register them via [`ErrorRegistry::register_children`](crate::ErrorRegistry::register_children) to get the same nested rendering as [`ErrVec`](crate::ErrVec).
+/// The renderer writes the message of the aggregate via [`fmt_message`](ErrorChildren::fmt_message) and walks the children via [`children`](ErrorChildren::children), so the `Display` impl of the aggregate is not called.
+/// Call [`fmt_message`](ErrorChildren::fmt_message) and [`write_error_children_to_formatter`](crate::write_error_children_to_formatter) from the `Display` impl, so that `to_string()` includes the children too.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use core::fmt::{Display, Formatter};
+/// # use errgonomic::{ErrorChild, ErrorChildren, ErrorDisplayer, ErrorRegistry, write_error_children_to_formatter};
+/// # use std::collections::BTreeMap;
+/// # use std::error::Error;
+/// # use std::io;
+/// #[derive(Debug)]
+/// struct PerShardErrors(BTreeMap<u32, io::Error>);
+///
+/// impl ErrorChildren for PerShardErrors {
+///     fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+///         write!(f, "encountered errors in {len} shards", len = self.0.len())
+///     }
+///
+///     fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+///         Box::new(self.0.iter().map(|(shard, error)| ErrorChild::labeled(format!("shard {shard}"), error)))
+///     }
+/// }
+///
+/// impl Display for PerShardErrors {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+///         self.fmt_message(f)?;
+///         write_error_children_to_formatter(self, f)
+///     }
+/// }
+///
+/// impl Error for PerShardErrors {}
+///
+/// // once at startup
+/// ErrorRegistry::register_children::<PerShardErrors>();
+///
+/// let errors = PerShardErrors(BTreeMap::from([(3, io::Error::other("connection refused"))]));
+/// assert_eq!(ErrorDisplayer(&errors).to_string(), "- encountered errors in 1 shards\n  * [shard 3] - connection refused");
+/// # }
+/// ```
+pub trait ErrorChildren {
+    /// Writes the message of the aggregate itself (without the children).
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result;
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_>;
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ErrVec, ErrorChild, ErrorChildren, ErrorDisplayer, ErrorRegistry, error_parsed_tree, write_error_children_to_formatter};
+    use core::fmt::{Display, Formatter};
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+    use std::error::Error;
+    use thiserror::Error;
+
+    #[test]
+    fn must_write_labeled_children() {
+        let error = SyncShardsFailed {
+            source: PerShardErrors(BTreeMap::from([
+                (3, ShardError::ConnectionRefused),
+                (
+                    7,
+                    ShardError::ReplicasFailed {
+                        source: vec![ReplicaError::Timeout, ReplicaError::Timeout].into(),
+                    },
+                ),
+            ])),
+        };
+        let expected = include_str!("error_children/fixtures/must_write_labeled_children.txt");
+        assert_eq!(format!("{}\n", ErrorDisplayer(&error)), expected);
+    }
+
+    #[test]
+    fn must_collect_labeled_children() {
+        let error = SyncShardsFailed {
+            source: PerShardErrors(BTreeMap::from([(3, ShardError::ConnectionRefused)])),
+        };
+        let children = error_parsed_tree(&error)
+            .source
+            .map(|source| source.children)
+            .unwrap_or_default();
+        let labels: Vec<_> = children
+            .iter()
+            .map(|child| (child.label.as_deref(), child.message.as_str()))
+            .collect();
+        assert_eq!(labels, vec![(Some("shard 3"), "connection refused")]);
+    }
+
+    #[test]
+    fn must_walk_children_without_display() {
+        ErrorRegistry::register_children::<SilentShardErrors>();
+        let error = ShardError::ReplicasFailed {
+            source: vec![ReplicaError::Timeout].into(),
+        };
+        let errors = SilentShardErrors(vec![error]);
+        let tree = error_parsed_tree(&errors);
+        assert_eq!(tree.message, "shards failed");
+        assert_eq!(tree.children[0].label.as_deref(), Some("shard 0"));
+        assert_eq!(tree.children[0].message, "failed to sync 1 replicas");
+    }
+
+    #[test]
+    fn must_list_err_vec_children() {
+        let err_vec: ErrVec<ReplicaError> = vec![ReplicaError::Timeout, ReplicaError::Timeout].into();
+        let messages: Vec<_> = err_vec
+            .children()
+            .map(|child| (child.label, child.error.to_string()))
+            .collect();
+        assert_eq!(messages, vec![(None, "timeout".to_string()), (None, "timeout".to_string())]);
+    }
+
+    use SyncError::*;
+
+    #[derive(Error, Debug)]
+    enum SyncError {
+        #[error("failed to sync shards")]
+        SyncShardsFailed { source: PerShardErrors },
+    }
+
+    #[derive(Debug)]
+    struct PerShardErrors(BTreeMap<u32, ShardError>);
+
+    impl ErrorChildren for PerShardErrors {
+        fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            write!(f, "encountered errors in {len} shards", len = self.0.len())
+        }
+
+        fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+            Box::new(
+                self.0
+                    .iter()
+                    .map(|(shard, error)| ErrorChild::labeled(format!("shard {shard}"), error as &(dyn Error + 'static))),
+            )
+        }
+    }
+
+    impl Display for PerShardErrors {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            self.fmt_message(f)?;
+            write_error_children_to_formatter(self, f)
+        }
+    }
+
+    impl Error for PerShardErrors {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            ErrorRegistry::register_children::<Self>();
+            None
+        }
+    }
+
+    /// An aggregate that doesn't write its children in the `Display` impl (the renderer must get them via [`ErrorChildren`])
+    #[derive(Error, Debug)]
+    #[error("shards failed")]
+    struct SilentShardErrors(Vec<ShardError>);
+
+    impl ErrorChildren for SilentShardErrors {
+        fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            f.write_str("shards failed")
+        }
+
+        fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+            Box::new(
+                self.0
+                    .iter()
+                    .enumerate()
+                    .map(|(shard, error)| ErrorChild::labeled(format!("shard {shard}"), error as &(dyn Error + 'static))),
+            )
+        }
+    }
+
+    #[derive(Error, Debug)]
+    enum ShardError {
+        #[error("connection refused")]
+        ConnectionRefused,
+        #[error("failed to sync {len} replicas", len = source.len())]
+        ReplicasFailed { source: ErrVec<ReplicaError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum ReplicaError {
+        #[error("timeout")]
+        Timeout,
+    }
+}
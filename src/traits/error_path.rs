@@ -0,0 +1,9 @@
+use std::path::Path;
+
+/// An error that concerns a path (e.g. [`PathIoError`](crate::PathIoError)).
+///
+/// Implement this trait for your own errors and register them via [`ErrorRegistry::register_path`](crate::ErrorRegistry::register_path) to make their paths available to [`error_paths`](crate::error_paths) and [`writeln_error_with_hyperlinks`](crate::writeln_error_with_hyperlinks).
+/// The path is returned as is, so the paths that are not valid UTF-8 are preserved.
+pub trait ErrorPath {
+    fn path(&self) -> Option<&Path>;
+}
@@ -0,0 +1,14 @@
+use alloc::vec::Vec;
+
+/// Constructs the `source` of the variant from the errors collected by the iterator macros ([`handle_iter!`](crate::handle_iter), [`handle_into_iter!`](crate::handle_into_iter), [`handle_iter_of_refs!`](crate::handle_iter_of_refs)) and the total number of the processed items.
+///
+/// This trait is implemented for every type that implements `From<Vec<E>>` (ignoring the total), so the existing `source` types keep working.
+pub trait FromErrors<E> {
+    fn from_errors(errors: Vec<E>, total: usize) -> Self;
+}
+
+impl<E, T: From<Vec<E>>> FromErrors<E> for T {
+    fn from_errors(errors: Vec<E>, _total: usize) -> Self {
+        Self::from(errors)
+    }
+}
@@ -0,0 +1,7 @@
+/// Constructs the error variant that wraps the source error of type `S` with the additional fields `F` (a tuple).
+///
+/// This trait allows [`handle_from!`](crate::handle_from) to infer the variant from the type of the source error.
+/// Implement it with [`impl_from_source!`](crate::impl_from_source).
+pub trait FromSource<S, F> {
+    fn from_source(source: S, fields: F) -> Self;
+}
@@ -0,0 +1,11 @@
+use core::error::Error;
+use tonic::Code;
+
+/// An error that can be returned from a gRPC handler (see [`to_status`](crate::to_status)).
+///
+/// The default code is `Internal`. Use [`impl_grpc_status!`](crate::impl_grpc_status) to map the enum variants to codes.
+pub trait GrpcStatus: Error {
+    fn grpc_code(&self) -> Code {
+        Code::Internal
+    }
+}
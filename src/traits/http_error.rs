@@ -0,0 +1,25 @@
+use core::error::Error;
+use http::StatusCode;
+
+/// An error that can be returned from an HTTP handler.
+///
+/// The default impls are safe for public responses: the status is `500 Internal Server Error`, and the public message is the first line of the [`Display`](core::fmt::Display) output of the top error only (never the chain, never the Debug output).
+/// Use [`impl_http_error!`](crate::impl_http_error) to map the enum variants to status codes.
+pub trait HttpError: Error {
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    fn public_message(&self) -> String {
+        self.to_string()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Returns a stable machine-readable error code (if available).
+    fn code(&self) -> Option<&'static str> {
+        None
+    }
+}
@@ -0,0 +1,19 @@
+use crate::LockKind;
+use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+/// A guard of a lock that may be poisoned.
+pub trait LockGuard {
+    const KIND: LockKind;
+}
+
+impl<T: ?Sized> LockGuard for RwLockReadGuard<'_, T> {
+    const KIND: LockKind = LockKind::Read;
+}
+
+impl<T: ?Sized> LockGuard for RwLockWriteGuard<'_, T> {
+    const KIND: LockKind = LockKind::Write;
+}
+
+impl<T: ?Sized> LockGuard for MutexGuard<'_, T> {
+    const KIND: LockKind = LockKind::Mutex;
+}
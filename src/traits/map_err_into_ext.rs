@@ -0,0 +1,15 @@
+use crate::MapErrInto;
+
+/// Converts the errors of an iterator of results via [`Into`] (lazily, item by item).
+///
+/// This is useful if the per-item error type differs from the error type that is collected by [`handle_iter!`](crate::handle_iter): `handle_iter!(inputs.iter().map(parse).map_err_into::<ItemError>(), ItemsInvalid)`.
+pub trait MapErrIntoExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    fn map_err_into<E2>(self) -> MapErrInto<Self, E2>
+    where
+        E: Into<E2>,
+    {
+        MapErrInto::new(self)
+    }
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E> MapErrIntoExt<T, E> for I {}
@@ -0,0 +1,29 @@
+use crate::Notification;
+use std::sync::mpsc::{Sender, SyncSender};
+
+/// A receiver of the user-facing notifications (see [`notify_error`](crate::notify_error) and [`DedupingSink`](crate::DedupingSink)).
+///
+/// The trait is object-safe, so the applications can pass `&mut dyn NotificationSink`.
+pub trait NotificationSink {
+    fn notify(&mut self, notification: Notification);
+}
+
+/// Sends the notifications to the channel (e.g. to the UI thread). The notifications are dropped if the receiver is disconnected.
+impl NotificationSink for Sender<Notification> {
+    fn notify(&mut self, notification: Notification) {
+        let _ = self.send(notification);
+    }
+}
+
+/// Sends the notifications to the channel (e.g. to the UI thread), blocking if the channel is full. The notifications are dropped if the receiver is disconnected.
+impl NotificationSink for SyncSender<Notification> {
+    fn notify(&mut self, notification: Notification) {
+        let _ = self.send(notification);
+    }
+}
+
+impl NotificationSink for Vec<Notification> {
+    fn notify(&mut self, notification: Notification) {
+        self.push(notification)
+    }
+}
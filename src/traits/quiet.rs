@@ -0,0 +1,7 @@
+use core::error::Error;
+
+/// A marker trait for the errors that represent an expected control flow (e.g. `NotFound` that the caller handles).
+///
+/// The quiet errors are reported with a one-line message and without the full error report via [`writeln_quiet_error_to_writer`](crate::writeln_quiet_error_to_writer) and [`eprintln_quiet_error`](crate::eprintln_quiet_error), which require this trait.
+/// The functions that accept any error (e.g. [`eprintln_error`](crate::eprintln_error)) always write the full error report, so call the quiet functions where the error type is known to be quiet.
+pub trait Quiet: Error {}
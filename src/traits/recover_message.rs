@@ -0,0 +1,34 @@
+use std::sync::mpsc;
+
+/// Returns the unsent message from the error of a failed send (see [`handle_send!`](crate::handle_send)).
+pub trait RecoverMessage {
+    type Message;
+
+    fn recover_message(self) -> Self::Message;
+}
+
+impl<T> RecoverMessage for mpsc::SendError<T> {
+    type Message = T;
+
+    fn recover_message(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> RecoverMessage for tokio::sync::mpsc::error::SendError<T> {
+    type Message = T;
+
+    fn recover_message(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> RecoverMessage for crossbeam_channel::SendError<T> {
+    type Message = T;
+
+    fn recover_message(self) -> T {
+        self.into_inner()
+    }
+}
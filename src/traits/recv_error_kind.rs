@@ -0,0 +1,54 @@
+use std::sync::mpsc;
+
+/// Distinguishes the receive errors that mean "the channel is disconnected" from the errors that mean "the channel is empty right now" (see [`handle_recv!`](crate::handle_recv)).
+///
+/// A timeout is treated as "empty", because the channel may receive a message later.
+pub trait RecvErrorKind {
+    fn is_disconnected(&self) -> bool;
+}
+
+impl RecvErrorKind for mpsc::RecvError {
+    fn is_disconnected(&self) -> bool {
+        true
+    }
+}
+
+impl RecvErrorKind for mpsc::TryRecvError {
+    fn is_disconnected(&self) -> bool {
+        matches!(self, mpsc::TryRecvError::Disconnected)
+    }
+}
+
+impl RecvErrorKind for mpsc::RecvTimeoutError {
+    fn is_disconnected(&self) -> bool {
+        matches!(self, mpsc::RecvTimeoutError::Disconnected)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl RecvErrorKind for tokio::sync::mpsc::error::TryRecvError {
+    fn is_disconnected(&self) -> bool {
+        matches!(self, tokio::sync::mpsc::error::TryRecvError::Disconnected)
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl RecvErrorKind for crossbeam_channel::RecvError {
+    fn is_disconnected(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl RecvErrorKind for crossbeam_channel::TryRecvError {
+    fn is_disconnected(&self) -> bool {
+        self.is_disconnected()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl RecvErrorKind for crossbeam_channel::RecvTimeoutError {
+    fn is_disconnected(&self) -> bool {
+        self.is_disconnected()
+    }
+}
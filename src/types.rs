@@ -1,19 +1,193 @@
+mod aggregate_message;
+mod context_error;
 mod debug_as_display;
+mod debug_dump;
+mod deduped;
+mod detailed;
 mod display_as_debug;
+mod display_debug_pair;
+mod error_child;
+mod error_detail_level;
+mod error_size_report;
+mod first_of_many;
+mod fmt_prefixer;
+mod fnv1a_hasher;
 mod item_error;
+mod lock_kind;
+mod map_err_into;
+mod plural;
+mod redacted;
+mod resumable;
+mod severity;
+mod variant_size;
+mod verbosity;
+mod with_severity;
 
+pub use aggregate_message::*;
+pub use context_error::*;
 pub use debug_as_display::*;
+pub use debug_dump::*;
+pub use deduped::*;
+pub use detailed::*;
 pub use display_as_debug::*;
+pub use display_debug_pair::*;
+pub use error_child::*;
+pub use error_detail_level::*;
+pub use error_size_report::*;
+pub use first_of_many::*;
+pub use fmt_prefixer::*;
+pub use fnv1a_hasher::*;
 pub use item_error::*;
+pub use lock_kind::*;
+pub use map_err_into::*;
+pub use plural::*;
+pub use redacted::*;
+pub use resumable::*;
+pub use severity::*;
+pub use variant_size::*;
+pub use verbosity::*;
+pub use with_severity::*;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "std")] {
+        mod build_info;
+        mod canonical_key;
+        mod channel_closed;
+        mod deadline;
+        mod deadlined;
+        mod deduping_sink;
+        mod deferred_errors;
         mod err_vec;
+        mod err_vec_with_context;
+        mod err_vec_with_total;
+        mod error_batch;
+        mod duplicate_key;
+        mod duplicate_keys_error;
+        mod error_batcher;
+        mod error_bin;
+        mod error_registry;
+        mod field_error;
+        mod field_path;
+        mod field_segment;
+        mod finalize_policy;
+        mod os_str_display;
+        mod parsed_tree;
         mod path_buf_display;
+        mod path_io_error;
+        mod path_scrubber;
+        mod prefixer;
         mod error_displayer;
+        mod error_style;
+        mod lock_poisoned_error;
+        mod must_finalize;
+        mod not_finalized;
+        mod notification;
+        mod provenance;
+        mod remote_error;
+        mod rendered_error;
+        mod request_id;
+        mod retry_after;
+        mod retry_delay;
+        mod scrub_level;
+        mod scrub_writer;
+        mod sourced;
+        mod styled_error_displayer;
+        mod symlink_policy;
+        mod trace_format;
+        mod tree_shape;
+        mod validation_errors;
+        mod walk_dir_error;
 
+        pub use build_info::*;
+        pub use canonical_key::*;
+        pub use channel_closed::*;
+        pub use deadline::*;
+        pub use deadlined::*;
+        pub use deduping_sink::*;
+        pub use deferred_errors::*;
         pub use err_vec::*;
+        pub use err_vec_with_context::*;
+        pub use err_vec_with_total::*;
+        pub use error_batch::*;
+        pub use duplicate_key::*;
+        pub use duplicate_keys_error::*;
+        pub use error_batcher::*;
+        pub use error_bin::*;
+        pub use error_registry::*;
+        pub use field_error::*;
+        pub use field_path::*;
+        pub use field_segment::*;
+        pub use finalize_policy::*;
+        pub use os_str_display::*;
+        pub use parsed_tree::*;
         pub use path_buf_display::*;
+        pub use path_io_error::*;
+        pub use path_scrubber::*;
+        pub use prefixer::*;
         pub use error_displayer::*;
+        pub use error_style::*;
+        pub use lock_poisoned_error::*;
+        pub use must_finalize::*;
+        pub use not_finalized::*;
+        pub use notification::*;
+        pub use provenance::*;
+        pub use remote_error::*;
+        pub use rendered_error::*;
+        pub use request_id::*;
+        pub use retry_after::*;
+        pub use retry_delay::*;
+        pub use scrub_level::*;
+        pub use scrub_writer::*;
+        pub use sourced::*;
+        pub use styled_error_displayer::*;
+        pub use symlink_policy::*;
+        pub use trace_format::*;
+        pub use tree_shape::*;
+        pub use validation_errors::*;
+        pub use walk_dir_error::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "testing")] {
+        mod message_finding;
+        mod message_finding_kind;
+
+        pub use message_finding::*;
+        pub use message_finding_kind::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "axum")] {
+        mod error_response;
+
+        pub use error_response::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tokio")] {
+        mod batching_receiver;
+        mod task_error;
+
+        pub use batching_receiver::*;
+        pub use task_error::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "anyhow")] {
+        mod anyhow_error;
+
+        pub use anyhow_error::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tonic")] {
+        mod remote_grpc_error;
+
+        pub use remote_grpc_error::*;
     }
 }
@@ -0,0 +1,11 @@
+use crate::ErrorChildren;
+use core::fmt::{Display, Formatter};
+
+/// Displays the message of the aggregate without its children (see [`ErrorChildren::fmt_message`]).
+pub struct AggregateMessage<'a>(pub &'a dyn ErrorChildren);
+
+impl Display for AggregateMessage<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt_message(f)
+    }
+}
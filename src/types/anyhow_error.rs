@@ -0,0 +1,38 @@
+use core::fmt::{Debug, Display, Formatter};
+use std::error::Error;
+
+/// An [`anyhow::Error`] that implements [`Error`], so it can be stored in the fields that require it (e.g. `ErrVec<AnyhowError>` or `Box<dyn Error + Send + Sync>`), see [`handle_anyhow!`](crate::handle_anyhow).
+///
+/// The `Display` impl writes only the outermost message (the context), and [`source`](Error::source) returns the next error in the chain of the `anyhow::Error`, so the trace contains every context as a separate line.
+/// If the `source` field of the variant has the `anyhow::Error` type, use [`handle!`](crate::handle) instead (thiserror supports `anyhow::Error` sources directly).
+pub struct AnyhowError(pub anyhow::Error);
+
+impl AnyhowError {
+    pub fn into_inner(self) -> anyhow::Error {
+        self.0
+    }
+}
+
+impl From<anyhow::Error> for AnyhowError {
+    fn from(error: anyhow::Error) -> Self {
+        Self(error)
+    }
+}
+
+impl Display for AnyhowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for AnyhowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Error for AnyhowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{ErrorBatch, ErrorBatcher};
+use core::error::Error;
+use tokio::sync::mpsc::Receiver;
+use tokio::time::{Instant, timeout_at};
+
+/// Receives the errors from a channel and emits them in batches (see [`ErrorBatcher`]).
+#[derive(Debug)]
+pub struct BatchingReceiver<E: Error> {
+    pub receiver: Receiver<E>,
+    pub batcher: ErrorBatcher<E>,
+}
+
+impl<E: Error> BatchingReceiver<E> {
+    pub fn new(receiver: Receiver<E>, batcher: ErrorBatcher<E>) -> Self {
+        Self {
+            receiver,
+            batcher,
+        }
+    }
+
+    /// Waits until the batch is full or until its oldest error is older than `max_age`.
+    ///
+    /// Returns the remaining errors when the channel is closed, and [`None`] after that.
+    pub async fn next_batch(&mut self) -> Option<ErrorBatch<E>> {
+        loop {
+            if let Some(batch) = self.batcher.poll_flush(std::time::Instant::now()) {
+                return Some(batch);
+            }
+            let received = match self.batcher.deadline() {
+                Some(deadline) => match timeout_at(Instant::from_std(deadline), self.receiver.recv()).await {
+                    Ok(received) => received,
+                    Err(_elapsed) => continue,
+                },
+                None => self.receiver.recv().await,
+            };
+            match received {
+                Some(error) => self.batcher.push(error),
+                None => return self.batcher.flush(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BatchingReceiver, ErrorBatcher};
+    use std::io;
+    use std::time::Duration;
+    use tokio::sync::mpsc::channel;
+
+    #[tokio::test]
+    async fn must_emit_batches() {
+        let (sender, receiver) = channel(16);
+        let mut receiver = BatchingReceiver::new(receiver, ErrorBatcher::new(2, Duration::from_millis(20)));
+        sender.send(io::Error::other("a")).await.unwrap();
+        sender.send(io::Error::other("b")).await.unwrap();
+        assert_eq!(receiver.next_batch().await.unwrap().len(), 2);
+        // the batch is emitted by the timer
+        sender.send(io::Error::other("c")).await.unwrap();
+        assert_eq!(receiver.next_batch().await.unwrap().len(), 1);
+        // the remaining errors are drained when the channel is closed
+        sender.send(io::Error::other("d")).await.unwrap();
+        drop(sender);
+        assert_eq!(receiver.next_batch().await.unwrap().len(), 1);
+        assert!(receiver.next_batch().await.is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use core::fmt::{Display, Formatter};
+use std::sync::{PoisonError, RwLock};
+
+static GLOBAL: RwLock<Option<BuildInfo>> = RwLock::new(None);
+
+/// The name and the version of the application, which are written after the error report (see [`set_global_build_info`](crate::set_global_build_info)).
+///
+/// The application provides the values (typically via `env!("CARGO_PKG_VERSION")` or `vergen`). The hostname and the pid are gathered when the report is written.
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+pub struct BuildInfo {
+    pub name: String,
+    pub version: String,
+    pub git_hash: Option<String>,
+}
+
+impl BuildInfo {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            git_hash: None,
+        }
+    }
+
+    pub fn with_git_hash(self, git_hash: impl Into<String>) -> Self {
+        Self {
+            git_hash: Some(git_hash.into()),
+            ..self
+        }
+    }
+
+    /// Returns the global build info (if it was set).
+    pub fn global() -> Option<Self> {
+        GLOBAL
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sets the global build info (`None` clears it).
+    pub fn store_global(info: Option<Self>) {
+        *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = info;
+    }
+
+    /// Returns the compact line that is written after the error report (e.g. `myapp 1.4.2 (abc1234) on host01, pid 4242`).
+    pub fn trailer(&self) -> String {
+        let mut trailer = self.to_string();
+        if let Some(hostname) = read_hostname() {
+            trailer.push_str(" on ");
+            trailer.push_str(&hostname);
+        }
+        trailer.push_str(&format!(", pid {}", std::process::id()));
+        trailer
+    }
+
+    /// Returns the block of lines that is written at the beginning of the full error report file.
+    pub fn header(&self) -> String {
+        let mut header = format!("app: {}\nversion: {}\n", self.name, self.version);
+        if let Some(git_hash) = &self.git_hash {
+            header.push_str(&format!("git hash: {git_hash}\n"));
+        }
+        if let Some(hostname) = read_hostname() {
+            header.push_str(&format!("host: {hostname}\n"));
+        }
+        header.push_str(&format!("pid: {}\n", std::process::id()));
+        header
+    }
+}
+
+impl Display for BuildInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.name, self.version)?;
+        match &self.git_hash {
+            Some(git_hash) => write!(f, " ({git_hash})"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the hostname from the environment or from `/etc/hostname` (`std` doesn't provide a portable API for it)
+fn read_hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .into_iter()
+        .find_map(|name| std::env::var(name).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
@@ -0,0 +1,18 @@
+use core::fmt::{Display, Formatter};
+
+/// A stable key of an error (see [`canonical_key`](crate::canonical_key)).
+///
+/// The keys are ordered by the fingerprint first, so the order doesn't depend on the order of the errors (e.g. the arrival order of the workers).
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Debug)]
+pub struct CanonicalKey {
+    /// The FNV-1a hash of the error tree in the strict trace format (see [`ParsedTree`](crate::ParsedTree))
+    pub fingerprint: u64,
+    /// The first line of the message of the error
+    pub first_line: String,
+}
+
+impl Display for CanonicalKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:016x} {}", self.fingerprint, self.first_line)
+    }
+}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+/// The `source` of the errors returned by [`handle_send!`](crate::handle_send): the message couldn't be sent because the receiver was dropped.
+///
+/// The original send error is not kept because its payload (the unsent message) is moved into the `message` field of the variant.
+#[derive(Error, Eq, PartialEq, Copy, Clone, Debug)]
+#[error("channel is closed")]
+pub struct ChannelClosed;
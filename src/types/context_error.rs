@@ -0,0 +1,25 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// A boxed source error with a context message (see [`box_err!`](crate::box_err)).
+///
+/// This is an escape hatch for the functions that return boxed errors. Prefer the typed error enums with [`handle!`](crate::handle).
+#[derive(Debug)]
+pub struct ContextError {
+    pub message: String,
+    pub source: Box<dyn Error + Send + Sync>,
+}
+
+impl Display for ContextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ContextError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
@@ -0,0 +1,101 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static CURRENT: Cell<Option<Deadline>> = const { Cell::new(None) };
+}
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    static TASK_CURRENT: Deadline;
+}
+
+/// A point in time by which the current request must be completed.
+///
+/// Set the ambient deadline with [`Deadline::scope`] (or [`Deadline::scope_async`] with the `tokio` feature), so that [`handle_deadline!`](crate::handle_deadline) can record how much time remained when the error occurred.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+pub struct Deadline(pub Instant);
+
+impl Deadline {
+    pub fn new(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    pub fn after(duration: Duration) -> Self {
+        Self(Instant::now() + duration)
+    }
+
+    /// Returns [`Duration::ZERO`] if the deadline has already passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Calls `f` with `deadline` set as the ambient deadline of the current thread (the previous ambient deadline is restored afterwards, even if `f` panics).
+    pub fn scope<R>(deadline: Self, f: impl FnOnce() -> R) -> R {
+        let _guard = RestoreOnDrop(CURRENT.replace(Some(deadline)));
+        f()
+    }
+
+    /// Polls `future` with `deadline` set as the ambient deadline of the current task.
+    #[cfg(feature = "tokio")]
+    pub async fn scope_async<F: Future>(deadline: Self, future: F) -> F::Output {
+        TASK_CURRENT.scope(deadline, future).await
+    }
+
+    /// Returns the innermost ambient deadline.
+    pub fn current() -> Option<Self> {
+        let current = CURRENT.get();
+        #[cfg(feature = "tokio")]
+        let current = current.or_else(|| TASK_CURRENT.try_with(|deadline| *deadline).ok());
+        current
+    }
+
+    /// Returns the time remaining until the ambient deadline (or [`None`] if the ambient deadline is not set).
+    pub fn current_remaining() -> Option<Duration> {
+        Self::current().map(|deadline| deadline.remaining())
+    }
+}
+
+struct RestoreOnDrop(Option<Deadline>);
+
+impl Drop for RestoreOnDrop {
+    fn drop(&mut self) {
+        CURRENT.set(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Deadline;
+    use std::time::Duration;
+
+    #[test]
+    fn must_restore_previous_deadline() {
+        let outer = Deadline::after(Duration::from_secs(60));
+        let inner = Deadline::after(Duration::from_secs(1));
+        assert_eq!(Deadline::current(), None);
+        Deadline::scope(outer, || {
+            assert_eq!(Deadline::current(), Some(outer));
+            Deadline::scope(inner, || assert_eq!(Deadline::current(), Some(inner)));
+            assert_eq!(Deadline::current(), Some(outer));
+        });
+        assert_eq!(Deadline::current(), None);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn must_set_task_deadline() {
+        let deadline = Deadline::after(Duration::from_secs(60));
+        let current = Deadline::scope_async(deadline, async {
+            tokio::task::yield_now().await;
+            Deadline::current()
+        })
+        .await;
+        assert_eq!(current, Some(deadline));
+        assert_eq!(Deadline::current(), None);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::Deadline;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// An error together with the time that remained until the ambient [`Deadline`] when this error occurred.
+///
+/// The source is boxed, so that [`deadline_exceeded`](crate::deadline_exceeded) can find this wrapper in any error chain via [`downcast_ref`](https://doc.rust-lang.org/std/error/trait.Error.html#method.downcast_ref).
+/// The wrapper is transparent: it displays the wrapped error and returns its source.
+///
+/// See also: [`handle_deadline!`](crate::handle_deadline)
+#[derive(Debug)]
+pub struct Deadlined {
+    pub source: Box<dyn Error + Send + Sync + 'static>,
+    pub remaining: Option<Duration>,
+}
+
+impl Deadlined {
+    pub fn new(source: impl Into<Box<dyn Error + Send + Sync + 'static>>, remaining: Option<Duration>) -> Self {
+        Self {
+            source: source.into(),
+            remaining,
+        }
+    }
+
+    /// Records the time remaining until the ambient deadline (see [`Deadline::current_remaining`]).
+    pub fn ambient(source: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+        Self::new(source, Deadline::current_remaining())
+    }
+
+    pub fn is_deadline_exceeded(&self) -> bool {
+        self.remaining == Some(Duration::ZERO)
+    }
+}
+
+impl Display for Deadlined {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.source, f)
+    }
+}
+
+impl Error for Deadlined {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.source()
+    }
+}
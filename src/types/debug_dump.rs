@@ -0,0 +1,14 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// Displays the pretty-printed `Debug` output of the error (the full error report of [`RenderedError`](crate::RenderedError)).
+///
+/// The output is written on every use instead of being stored, so the report can be streamed into a file.
+#[derive(Copy, Clone, Debug)]
+pub struct DebugDump<'a>(pub &'a (dyn Error + 'static));
+
+impl Display for DebugDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:#?}", self.0)
+    }
+}
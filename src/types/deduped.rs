@@ -0,0 +1,27 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// An error that represents `count` errors with the same message (see [`dedup_errors`](crate::dedup_errors)).
+///
+/// The message of the error is suffixed with the count if the count is greater than 1 (e.g. `file not found (occurred 3 times)`).
+/// The message of the inner error is inlined into the message of this error, so [`source`](Error::source) returns the source of the inner error.
+#[derive(Clone, Debug)]
+pub struct Deduped<E> {
+    pub error: E,
+    pub count: usize,
+}
+
+impl<E: Display> Display for Deduped<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.count {
+            1 => self.error.fmt(f),
+            count => write!(f, "{} (occurred {count} times)", self.error),
+        }
+    }
+}
+
+impl<E: Error> Error for Deduped<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{CanonicalKey, Notification, NotificationSink};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A [`NotificationSink`] that suppresses the repeated notifications (with the same [`key`](Notification::key)) within the time window.
+///
+/// The first notification is forwarded immediately. If more notifications with the same key arrive within the window, a single summary notification (the latest one with the total [`count`](Notification::count)) is forwarded when the window closes.
+/// The windows are checked on every notification and on every [`flush_expired`](DedupingSink::flush_expired) call (e.g. from a timer of the UI loop).
+/// The clock is injectable for tests.
+pub struct DedupingSink<S, C = fn() -> Instant> {
+    pub inner: S,
+    pub window: Duration,
+    clock: C,
+    windows: HashMap<CanonicalKey, Window>,
+}
+
+struct Window {
+    opened_at: Instant,
+    latest: Option<Notification>,
+}
+
+impl<S: NotificationSink> DedupingSink<S> {
+    pub fn new(inner: S, window: Duration) -> Self {
+        Self::with_clock(inner, window, Instant::now)
+    }
+}
+
+impl<S: NotificationSink, C: Fn() -> Instant> DedupingSink<S, C> {
+    pub fn with_clock(inner: S, window: Duration, clock: C) -> Self {
+        Self {
+            inner,
+            window,
+            clock,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Forwards the summaries of the closed windows (the windows without the suppressed notifications are closed silently).
+    pub fn flush_expired(&mut self) {
+        let now = (self.clock)();
+        let window = self.window;
+        let mut expired = Vec::new();
+        self.windows.retain(|_key, state| {
+            let is_open = now.saturating_duration_since(state.opened_at) < window;
+            if !is_open {
+                expired.extend(state.latest.take());
+            }
+            is_open
+        });
+        expired.sort_by(|a, b| a.key.cmp(&b.key));
+        expired
+            .into_iter()
+            .for_each(|notification| self.inner.notify(notification));
+    }
+}
+
+impl<S: NotificationSink, C: Fn() -> Instant> NotificationSink for DedupingSink<S, C> {
+    fn notify(&mut self, notification: Notification) {
+        self.flush_expired();
+        match self.windows.get_mut(&notification.key) {
+            Some(state) => {
+                let count = state
+                    .latest
+                    .as_ref()
+                    .map_or(notification.count + 1, |latest| latest.count + notification.count);
+                state.latest = Some(Notification {
+                    count,
+                    ..notification
+                });
+            }
+            None => {
+                self.windows.insert(
+                    notification.key.clone(),
+                    Window {
+                        opened_at: (self.clock)(),
+                        latest: None,
+                    },
+                );
+                self.inner.notify(notification)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DedupingSink, Notification, Severity, notify_error};
+    use std::cell::Cell;
+    use std::io;
+    use std::rc::Rc;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+    use thiserror::Error;
+
+    #[test]
+    fn must_suppress_burst_and_forward_summary() {
+        let start = Instant::now();
+        let now = Rc::new(Cell::new(start));
+        let clock = {
+            let now = now.clone();
+            move || now.get()
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut sink = DedupingSink::with_clock(tx, Duration::from_secs(10), clock);
+        (0..50).for_each(|index| {
+            now.set(start + Duration::from_millis(index * 100));
+            notify_error(&sync_failed("connection reset"), &mut sink);
+        });
+        let immediate = rx.try_iter().collect::<Vec<Notification>>();
+        assert_eq!(immediate.len(), 1);
+        assert_eq!(immediate[0].title, "failed to sync");
+        assert_eq!(immediate[0].body.as_deref(), Some("connection reset"));
+        assert_eq!(immediate[0].severity, Severity::Error);
+        assert_eq!(immediate[0].count, 1);
+        now.set(start + Duration::from_secs(10));
+        sink.flush_expired();
+        let summary = rx.try_iter().collect::<Vec<Notification>>();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].count, 50);
+        assert_eq!(summary[0].to_string(), "[ERROR] failed to sync: connection reset (occurred 50 times)");
+        sink.flush_expired();
+        assert_eq!(rx.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn must_forward_distinct_errors() {
+        let mut sink = DedupingSink::new(Vec::new(), Duration::from_secs(60));
+        notify_error(&sync_failed("connection reset"), &mut sink);
+        notify_error(&sync_failed("timed out"), &mut sink);
+        notify_error(&crate::WithSeverity::warning(io::Error::other("cache is stale")), &mut sink);
+        let notifications = &sink.inner;
+        assert_eq!(notifications.len(), 3);
+        assert_eq!(notifications[2].title, "cache is stale");
+        assert_eq!(notifications[2].body, None);
+        assert_eq!(notifications[2].severity, Severity::Warning);
+    }
+
+    fn sync_failed(message: &str) -> SyncError {
+        SyncError::SyncFailed {
+            source: io::Error::other(message.to_string()),
+        }
+    }
+
+    #[derive(Error, Debug)]
+    enum SyncError {
+        #[error("failed to sync")]
+        SyncFailed { source: io::Error },
+    }
+}
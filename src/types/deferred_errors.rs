@@ -0,0 +1,37 @@
+use crate::{ErrorChild, ErrorChildren, ErrorRegistry, Plural, write_error_children_to_formatter};
+use core::fmt::{Display, Formatter};
+use std::error::Error;
+
+/// The errors taken from the [`ErrorBin`](crate::ErrorBin).
+#[derive(Debug)]
+pub struct DeferredErrors {
+    pub errors: Vec<Box<dyn Error + Send + Sync>>,
+}
+
+impl Display for DeferredErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl Error for DeferredErrors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl ErrorChildren for DeferredErrors {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "encountered {errors}", errors = Plural::new(self.errors.len(), "deferred error"))
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        Box::new(
+            self.errors
+                .iter()
+                .map(|error| ErrorChild::new(error.as_ref())),
+        )
+    }
+}
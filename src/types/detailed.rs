@@ -0,0 +1,143 @@
+use crate::ErrorDetailLevel;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// A field value that is stored only if the [`ErrorDetailLevel`] is [`Full`](ErrorDetailLevel::Full) (otherwise, only its length in bytes is stored).
+///
+/// This is useful for the expensive fields (e.g. the contents of the file that failed to deserialize): `DeserializeFailed { source: serde_json::Error, contents: Detailed<String> }`.
+/// The value is converted via `From`, so the `handle!(result, DeserializeFailed, contents)` syntax works unchanged.
+/// Pass a borrowed value (`&str` or `&[u8]`) if possible: it's copied only if the level is [`Full`](ErrorDetailLevel::Full) (see [`Detailed::from_borrowed`]).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Detailed<T> {
+    Full(T),
+    Omitted { len: usize },
+}
+
+impl<T> Detailed<T> {
+    /// Copies the value only if the [`ErrorDetailLevel`] is [`Full`](ErrorDetailLevel::Full).
+    pub fn from_borrowed<B: ToOwned<Owned = T> + AsRef<[u8]> + ?Sized>(value: &B) -> Self {
+        match ErrorDetailLevel::current() {
+            ErrorDetailLevel::Full => Detailed::Full(value.to_owned()),
+            ErrorDetailLevel::Minimal => Detailed::Omitted {
+                len: value.as_ref().len(),
+            },
+        }
+    }
+
+    /// Returns the value if it was stored.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Detailed::Full(value) => Some(value),
+            Detailed::Omitted {
+                ..
+            } => None,
+        }
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Detailed::Full(value) => Some(value),
+            Detailed::Omitted {
+                ..
+            } => None,
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> From<T> for Detailed<T> {
+    fn from(value: T) -> Self {
+        match ErrorDetailLevel::current() {
+            ErrorDetailLevel::Full => Detailed::Full(value),
+            ErrorDetailLevel::Minimal => Detailed::Omitted {
+                len: value.as_ref().len(),
+            },
+        }
+    }
+}
+
+impl From<&str> for Detailed<String> {
+    fn from(value: &str) -> Self {
+        Self::from_borrowed(value)
+    }
+}
+
+impl From<&[u8]> for Detailed<Vec<u8>> {
+    fn from(value: &[u8]) -> Self {
+        Self::from_borrowed(value)
+    }
+}
+
+impl<T: Display> Display for Detailed<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Detailed::Full(value) => value.fmt(f),
+            Detailed::Omitted {
+                len,
+            } => {
+                f.write_str("(contents omitted, ")?;
+                write_byte_size(*len, f)?;
+                f.write_str("; re-run with full error detail)")
+            }
+        }
+    }
+}
+
+fn write_byte_size(len: usize, f: &mut Formatter<'_>) -> core::fmt::Result {
+    const KIB: usize = 1024;
+    const MIB: usize = KIB * 1024;
+    match len {
+        len if len < KIB => write!(f, "{len} B"),
+        len if len < MIB => write!(f, "{} KiB", len / KIB),
+        len => write!(f, "{} MiB", len / MIB),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{Detailed, ErrorDetailLevel, handle, lock_error_detail_level_for_test};
+    use core::mem::size_of;
+    use core::num::ParseIntError;
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_omit_value_at_minimal_level() {
+        let _guard = lock_error_detail_level_for_test(ErrorDetailLevel::Minimal);
+        let contents = "x".repeat(14 * 1024 + 100);
+        let minimal = Detailed::from(contents.clone());
+        assert_eq!(minimal.value(), None);
+        assert_eq!(minimal.to_string(), "(contents omitted, 14 KiB; re-run with full error detail)");
+        assert_eq!(Detailed::from("{}".to_string()).to_string(), "(contents omitted, 2 B; re-run with full error detail)");
+        assert!(matches!(parse(&contents), Err(ParseError::ParseFailed { contents: Detailed::Omitted { len }, .. }) if len == contents.len()));
+    }
+
+    #[test]
+    fn must_store_value_at_full_level() {
+        let _guard = lock_error_detail_level_for_test(ErrorDetailLevel::Full);
+        let contents = "x".repeat(14 * 1024 + 100);
+        let full = Detailed::from(contents.clone());
+        assert_eq!(full.value(), Some(&contents));
+        assert_eq!(full.to_string(), contents);
+        assert!(matches!(parse(&contents), Err(ParseError::ParseFailed { contents: Detailed::Full(value), .. }) if value == contents));
+    }
+
+    /// Passes the borrowed contents, so they are copied only at the full level
+    fn parse(contents: &str) -> Result<u32, ParseError> {
+        use ParseError::*;
+        let number = handle!(contents.parse::<u32>(), ParseFailed, contents);
+        Ok(number)
+    }
+
+    #[test]
+    fn must_not_be_larger_than_option_plus_word() {
+        assert!(size_of::<Detailed<String>>() <= size_of::<Option<String>>() + size_of::<usize>());
+    }
+
+    #[derive(Error, Debug)]
+    enum ParseError {
+        #[error("failed to parse the contents: {contents}")]
+        ParseFailed { source: ParseIntError, contents: Detailed<String> },
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::string::{String, ToString};
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+
+/// A value together with its `Display` string captured at construction.
+///
+/// The `Display` impl writes the captured string, and the `Debug` impl delegates to the value.
+/// If the value is an error, the pair is an error too, and its [`source`](Error::source) delegates to the value, so the chain of a foreign error is preserved while its message is snapshotted.
+#[derive(Clone)]
+pub struct DisplayDebugPair<T> {
+    pub display: String,
+    pub debug: T,
+}
+
+impl<T: Display> DisplayDebugPair<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            display: value.to_string(),
+            debug: value,
+        }
+    }
+}
+
+impl<T> Display for DisplayDebugPair<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl<T: Debug> Debug for DisplayDebugPair<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.debug, f)
+    }
+}
+
+impl<T: Error> Error for DisplayDebugPair<T> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.debug.source()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{DisplayDebugPair, get_root_source};
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use thiserror::Error;
+
+    #[test]
+    fn must_preserve_source_chain() {
+        let error = DisplayDebugPair::new(ConnectError::HandshakeFailed {
+            source: io::Error::other("connection reset by peer"),
+        });
+        assert_eq!(error.to_string(), "failed to complete the handshake");
+        assert_eq!(get_root_source(&error).to_string(), "connection reset by peer");
+    }
+
+    #[derive(Error, Debug)]
+    enum ConnectError {
+        #[error("failed to complete the handshake")]
+        HandshakeFailed { source: io::Error },
+    }
+}
@@ -0,0 +1,22 @@
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+
+/// A key that occurs more than once in the input of [`collect_unique`](crate::collect_unique), together with the values of its first two occurrences.
+///
+/// The key is displayed via `Debug`, so the keys with the same `Display` representation (e.g. `"1"` and `1`) can be told apart.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct DuplicateKey<K, V> {
+    pub key: K,
+    pub first: V,
+    pub second: V,
+    /// The number of the occurrences of the key (at least 2)
+    pub count: usize,
+}
+
+impl<K: Debug, V> Display for DuplicateKey<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "found duplicate key {key:?} ({count} occurrences)", key = self.key, count = self.count)
+    }
+}
+
+impl<K: Debug, V: Debug> Error for DuplicateKey<K, V> {}
@@ -0,0 +1,37 @@
+use crate::{DuplicateKey, ErrorChild, ErrorChildren, ErrorRegistry, Plural, write_error_children_to_formatter};
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+
+/// All duplicate keys found by [`collect_unique`](crate::collect_unique) (in the order of their second occurrences).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct DuplicateKeysError<K, V> {
+    pub duplicates: Vec<DuplicateKey<K, V>>,
+}
+
+impl<K: Debug + 'static, V: Debug + 'static> Display for DuplicateKeysError<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl<K: Debug + 'static, V: Debug + 'static> Error for DuplicateKeysError<K, V> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl<K: Debug + 'static, V: Debug + 'static> ErrorChildren for DuplicateKeysError<K, V> {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "found {keys}", keys = Plural::new(self.duplicates.len(), "duplicate key"))
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        Box::new(
+            self.duplicates
+                .iter()
+                .map(|duplicate| ErrorChild::new(duplicate)),
+        )
+    }
+}
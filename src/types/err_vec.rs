@@ -1,38 +1,179 @@
-use crate::ErrorDisplayer;
+use crate::{Deduped, ErrorChild, ErrorChildren, ErrorRegistry, FirstOfMany, Plural, canonical_key, write_error_children_to_formatter};
 use core::error::Error;
-use core::fmt::{Debug, Write};
+use core::fmt::Debug;
 use core::fmt::{Display, Formatter};
 use core::ops::{Deref, DerefMut};
 
 /// An owned collection of errors
-#[derive(Default, Clone, Debug)]
+///
+/// The `Debug` impl renders the chain of messages of every child next to its `Debug` output (this is more readable in the error report than the derived impl).
+#[derive(Default, Clone)]
 pub struct ErrVec<E: Error>(pub Vec<E>);
 
 impl<E: Error> ErrVec<E> {
     pub fn new(iter: impl IntoIterator<Item = E>) -> Self {
         Self(iter.into_iter().collect())
     }
+
+    /// Collects the errors of the results (discarding the `Ok` values) without an intermediate vec.
+    ///
+    /// This is useful if only the errors are needed (use [`partition_result`](crate::partition_result) to keep the `Ok` values).
+    pub fn from_results<T>(results: impl IntoIterator<Item = Result<T, E>>) -> Self {
+        Self::new(results.into_iter().filter_map(Result::err))
+    }
+
+    /// Shrinks the capacity of the inner vec to its length.
+    ///
+    /// This is useful for long-lived error values: the vec that accumulated the errors may have over-allocated while growing.
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit()
+    }
+
+    /// Appends the errors of `other` to this vec (the order is preserved).
+    pub fn merge(&mut self, other: ErrVec<E>) {
+        self.0.extend(other.0)
+    }
+
+    /// Returns `Ok(ok)` if there are no errors, or `Err(self)` otherwise.
+    ///
+    /// This is useful at the end of manual accumulation loops.
+    pub fn into_result<T>(self, ok: T) -> Result<T, Self> {
+        if self.is_empty() { Ok(ok) } else { Err(self) }
+    }
+
+    /// Returns the first error with the number of the errors in this vec (see [`FirstOfMany`]), or `None` if there are no errors.
+    ///
+    /// This is useful for the APIs that accept a single error.
+    pub fn first_with_context(self) -> Option<FirstOfMany<E>> {
+        let len = self.len();
+        self.0.into_iter().next().map(|source| FirstOfMany {
+            source,
+            len,
+        })
+    }
 }
 
-impl<E: Error> Display for ErrVec<E> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        write!(f, "encountered {len} errors", len = self.len())?;
-        self.0.iter().try_for_each(|error| {
-            f.write_char('\n')?;
-            let recursive_displayer = ErrorDisplayer(error);
-            let string = format!("{recursive_displayer}");
-            let mut lines = string.lines();
-            let first_line_opt = lines.next();
-            if let Some(first_line) = first_line_opt {
-                write!(f, "  * {first_line}")?;
-                lines.try_for_each(|line| write!(f, "\n    {line}"))?;
+impl<E: Error + 'static> ErrVec<ErrVec<E>> {
+    /// Replaces every nested vec with its errors (the order is preserved).
+    ///
+    /// This is useful for the pipelines that process the items in chunks: the trace lists the leaf errors without the intermediate "encountered N errors" level per chunk.
+    /// Call this method once per nesting level (e.g. `ErrVec<ErrVec<ErrVec<E>>>` must be flattened twice).
+    pub fn flatten(self) -> ErrVec<E> {
+        ErrVec::new(self.0.into_iter().flat_map(|errors| errors.0))
+    }
+}
+
+impl<E: Error + 'static> ErrVec<E> {
+    /// Returns the sole error if there is exactly one error, or `Err(self)` otherwise.
+    ///
+    /// This is useful for the APIs that accept a single error: the trace doesn't contain a pointless "encountered 1 error" level.
+    pub fn into_single(mut self) -> Result<Box<dyn Error + 'static>, Self> {
+        match self.0.pop() {
+            Some(error) if self.is_empty() => Ok(Box::new(error)),
+            Some(error) => {
+                self.0.push(error);
+                Err(self)
             }
-            Ok(())
-        })
+            None => Err(self),
+        }
+    }
+}
+
+impl<E: Error + 'static> ErrVec<E> {
+    /// Sorts the errors by their [`canonical_key`], so the order doesn't depend on the order of the accumulation.
+    pub fn sort_canonical(&mut self) {
+        self.0.sort_by_cached_key(|error| canonical_key(error))
+    }
+
+    /// Sorts the errors by their [`canonical_key`] and collapses the identical errors into a single [`Deduped`] error with the count.
+    ///
+    /// The result can be merged with other canonical aggregates via [`merge_canonical`](crate::merge_canonical).
+    pub fn into_canonical(self) -> ErrVec<Deduped<E>> {
+        let mut keyed: Vec<_> = self
+            .0
+            .into_iter()
+            .map(|error| (canonical_key(&error), error))
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut deduped: Vec<(_, Deduped<E>)> = Vec::with_capacity(keyed.len());
+        keyed
+            .into_iter()
+            .for_each(|(key, error)| match deduped.last_mut() {
+                Some((last, last_deduped)) if *last == key => last_deduped.count += 1,
+                _ => deduped.push((
+                    key,
+                    Deduped {
+                        error,
+                        count: 1,
+                    },
+                )),
+            });
+        ErrVec(deduped.into_iter().map(|(_, deduped)| deduped).collect())
+    }
+}
+
+impl<E: Error + 'static> Display for ErrVec<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl<E: Error + 'static> Error for ErrVec<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        // the renderer calls `source` before looking up the children (see `ErrorRegistry`)
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl<E: Error> Debug for ErrVec<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ErrVec ")?;
+        f.debug_list()
+            .entries(self.0.iter().map(ErrVecChild))
+            .finish()
+    }
+}
+
+struct ErrVecChild<'a, E: Error>(&'a E);
+
+impl<E: Error> Debug for ErrVecChild<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let chain: Vec<String> = core::iter::successors(Some(self.0 as &dyn Error), |&error| error.source())
+            .map(ToString::to_string)
+            .collect();
+        f.debug_struct("Child")
+            .field("chain", &chain)
+            .field("error", self.0)
+            .finish()
     }
 }
 
-impl<E: Error> Error for ErrVec<E> {}
+impl<E: Error + 'static> ErrorChildren for ErrVec<E> {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "encountered {errors}", errors = Plural::new(self.len(), "error"))
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        Box::new(self.0.iter().map(|error| ErrorChild::new(error)))
+    }
+}
+
+/// Compares the errors by the sorted multiset of their `Display` strings (ignoring the order of the errors).
+///
+/// This impl is intended for tests, where `assert_eq!` is the most convenient way to check the expected errors.
+#[cfg(feature = "testing")]
+impl<E: Error> PartialEq for ErrVec<E> {
+    fn eq(&self, other: &Self) -> bool {
+        let get_messages = |err_vec: &Self| {
+            let mut messages: Vec<String> = err_vec.iter().map(ToString::to_string).collect();
+            messages.sort_unstable();
+            messages
+        };
+        self.len() == other.len() && get_messages(self) == get_messages(other)
+    }
+}
 
 impl<E: Error> Deref for ErrVec<E> {
     type Target = Vec<E>;
@@ -71,3 +212,219 @@ impl<E: Error + Clone> From<&[E]> for ErrVec<E> {
         Self(inner.to_vec())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer};
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_flatten_chunks() {
+        let too_long = |len| ParseError::TooLong {
+            len,
+        };
+        let chunks = ErrVec::new([
+            ErrVec::new([too_long(1), too_long(2)]),
+            ErrVec::new([]),
+            ErrVec::new([too_long(3), ParseError::Invalid, too_long(4)]),
+        ]);
+        let nested = ErrorDisplayer(&chunks).to_string();
+        let flat = chunks.flatten();
+        let messages = flat.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "input is too long: 1",
+                "input is too long: 2",
+                "input is too long: 3",
+                "invalid input",
+                "input is too long: 4"
+            ]
+        );
+        assert!(nested.contains("encountered 2 errors"), "{nested}");
+        assert!(
+            !ErrorDisplayer(&flat)
+                .to_string()
+                .contains("encountered 2 errors")
+        );
+    }
+
+    #[test]
+    fn must_merge() {
+        let mut errors = ErrVec::new([ParseError::Invalid]);
+        errors.merge(ErrVec::new([ParseError::TooLong {
+            len: 1,
+        }]));
+        let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(messages, ["invalid input", "input is too long: 1"]);
+    }
+
+    #[test]
+    fn must_collect_from_results() {
+        let results: Box<dyn Iterator<Item = Result<u32, ParseError>>> = Box::new(
+            [
+                Ok(1),
+                Err(ParseError::Invalid),
+                Ok(2),
+                Err(ParseError::Invalid),
+            ]
+            .into_iter(),
+        );
+        let errors = ErrVec::from_results(results);
+        assert_eq!(errors.len(), 2);
+        assert!(ErrVec::from_results([Ok::<_, ParseError>(1)]).is_empty());
+    }
+
+    #[test]
+    fn must_convert_into_result() {
+        let empty = ErrVec::<ParseError>::new([]);
+        assert!(matches!(empty.into_result(vec![1, 2]), Ok(oks) if oks == vec![1, 2]));
+        let non_empty = ErrVec::new([ParseError::Invalid]);
+        assert!(matches!(non_empty.into_result(()), Err(errors) if errors.len() == 1));
+    }
+
+    #[test]
+    fn must_shrink_to_fit() {
+        let mut errors = ErrVec(Vec::with_capacity(100));
+        errors.push(ParseError::Invalid);
+        errors.shrink_to_fit();
+        assert_eq!(errors.capacity(), errors.len());
+    }
+
+    #[test]
+    fn must_convert_into_single() {
+        let empty = ErrVec::<ParseError>::new([]);
+        assert!(matches!(empty.into_single(), Err(errors) if errors.is_empty()));
+        let single = ErrVec::new([ParseError::Invalid]);
+        assert!(matches!(single.into_single(), Ok(error) if error.to_string() == "invalid input"));
+        let many = ErrVec::new([
+            ParseError::Invalid,
+            ParseError::TooLong {
+                len: 3,
+            },
+        ]);
+        assert!(matches!(many.into_single(), Err(errors) if errors.len() == 2));
+    }
+
+    #[test]
+    fn must_return_first_with_context() {
+        assert!(ErrVec::<ParseError>::new([]).first_with_context().is_none());
+        let single = ErrVec::new([ParseError::Invalid])
+            .first_with_context()
+            .unwrap();
+        assert_eq!(ErrorDisplayer(&single).to_string(), "- first of 1 error (0 more suppressed)\n- invalid input");
+        let many = ErrVec::new([
+            ParseError::TooLong {
+                len: 3,
+            },
+            ParseError::Invalid,
+            ParseError::Invalid,
+        ])
+        .first_with_context()
+        .unwrap();
+        assert_eq!(ErrorDisplayer(&many).to_string(), "- first of 3 errors (2 more suppressed)\n- input is too long: 3");
+    }
+
+    #[test]
+    fn must_pluralize_count() {
+        assert_eq!(ErrVec::new([ParseError::Invalid]).to_string(), "encountered 1 error\n  * - invalid input");
+        assert_eq!(ErrVec::<ParseError>::new([]).to_string(), "encountered 0 errors");
+    }
+
+    #[test]
+    fn must_indent_children_once() {
+        let errors = ErrVec::new([ReadConfigError::ParseFailed {
+            source: ParseError::Invalid,
+            path: "config.toml".to_string(),
+        }]);
+        assert_eq!(errors.to_string(), "encountered 1 error\n  * - failed to parse config file 'config.toml'\n    - invalid input");
+        assert_eq!(ErrorDisplayer(&errors).to_string(), "- encountered 1 error\n  * - failed to parse config file 'config.toml'\n    - invalid input");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn must_compare_by_messages() {
+        use ParseError::*;
+        let actual = ErrVec::new([
+            Invalid,
+            TooLong {
+                len: 3,
+            },
+            Invalid,
+        ]);
+        assert_eq!(
+            actual,
+            ErrVec::new([
+                TooLong {
+                    len: 3
+                },
+                Invalid,
+                Invalid
+            ])
+        );
+        assert_ne!(
+            actual,
+            ErrVec::new([
+                TooLong {
+                    len: 3
+                },
+                Invalid
+            ])
+        );
+        assert_ne!(
+            actual,
+            ErrVec::new([
+                TooLong {
+                    len: 3
+                },
+                TooLong {
+                    len: 3
+                },
+                Invalid
+            ])
+        );
+        assert_ne!(
+            actual,
+            ErrVec::new([
+                TooLong {
+                    len: 4
+                },
+                Invalid,
+                Invalid
+            ])
+        );
+    }
+
+    #[test]
+    fn must_debug_children_with_chains() {
+        let errors = ErrVec::new([
+            ReadConfigError::ParseFailed {
+                source: ParseError::TooLong {
+                    len: 4,
+                },
+                path: "config.toml".into(),
+            },
+            ReadConfigError::PathEmpty,
+        ]);
+        let expected = include_str!("err_vec/fixtures/must_debug_children_with_chains.txt");
+        assert_eq!(format!("{errors:#?}\n"), expected);
+    }
+
+    #[derive(Error, Clone, Debug)]
+    enum ReadConfigError {
+        #[error("failed to parse config file '{path}'")]
+        ParseFailed { source: ParseError, path: String },
+        #[error("config path is empty")]
+        PathEmpty,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Error, Clone, Debug)]
+    enum ParseError {
+        #[error("invalid input")]
+        Invalid,
+        #[error("input is too long: {len}")]
+        TooLong { len: usize },
+    }
+}
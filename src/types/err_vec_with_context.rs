@@ -0,0 +1,130 @@
+use crate::{ErrVec, ErrorChild, ErrorChildren, ErrorRegistry, Plural, write_error_children_to_formatter};
+use core::error::Error;
+use core::fmt::{Display, Formatter, Write};
+use core::ops::{Deref, DerefMut};
+
+/// An [`ErrVec`] together with the key-value pairs that describe the batch (e.g. the directory or the tenant), so that the parent message doesn't have to include them.
+///
+/// The pairs are rendered in their original order as a suffix of the aggregate message: `encountered 7 errors [dir=/data/in, tenant=acme]`.
+/// The `\`, `,`, `=`, `]` characters and the newlines in the keys and values are escaped with a backslash, so the suffix can be split unambiguously.
+///
+/// Construct it via [`ErrVec::with_context`] or via the `context` block of [`handle_iter!`](crate::handle_iter).
+#[derive(Clone, Debug)]
+pub struct ErrVecWithContext<E: Error> {
+    pub errors: ErrVec<E>,
+    pub context: Vec<(String, String)>,
+}
+
+impl<E: Error> ErrVecWithContext<E> {
+    pub fn new(errors: impl IntoIterator<Item = E>, context: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>) -> Self {
+        Self {
+            errors: ErrVec::new(errors),
+            context: context
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+impl<E: Error> ErrVec<E> {
+    /// Attaches the key-value pairs that describe the batch (see [`ErrVecWithContext`]).
+    pub fn with_context(self, context: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>) -> ErrVecWithContext<E> {
+        ErrVecWithContext::new(self.0, context)
+    }
+}
+
+impl<E: Error + 'static> Display for ErrVecWithContext<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl<E: Error + 'static> Error for ErrVecWithContext<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl<E: Error + 'static> ErrorChildren for ErrVecWithContext<E> {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "encountered {errors}", errors = Plural::new(self.errors.len(), "error"))?;
+        if !self.context.is_empty() {
+            f.write_str(" [")?;
+            self.context
+                .iter()
+                .enumerate()
+                .try_for_each(|(index, (key, value))| {
+                    if index != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_escaped(key, f)?;
+                    f.write_char('=')?;
+                    write_escaped(value, f)
+                })?;
+            f.write_char(']')?;
+        }
+        Ok(())
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        self.errors.children()
+    }
+}
+
+impl<E: Error> Deref for ErrVecWithContext<E> {
+    type Target = ErrVec<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.errors
+    }
+}
+
+impl<E: Error> DerefMut for ErrVecWithContext<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.errors
+    }
+}
+
+fn write_escaped(part: &str, f: &mut Formatter<'_>) -> core::fmt::Result {
+    part.chars().try_for_each(|char| match char {
+        '\\' | ',' | '=' | ']' => {
+            f.write_char('\\')?;
+            f.write_char(char)
+        }
+        '\n' => f.write_str("\\n"),
+        _ => f.write_char(char),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer};
+    use pretty_assertions::assert_eq;
+    use std::io;
+
+    #[test]
+    fn must_display_context_in_order() {
+        let errors = ErrVec::new([io::Error::other("disk full"), io::Error::other("disk full")]).with_context([
+            ("dir", "/data/in"),
+            ("tenant", "acme"),
+            ("date", "2026-10-16"),
+        ]);
+        assert_eq!(errors.to_string(), "encountered 2 errors [dir=/data/in, tenant=acme, date=2026-10-16]\n  * - disk full\n  * - disk full");
+        assert_eq!(format!("{}\n", ErrorDisplayer(&errors)), include_str!("err_vec_with_context/fixtures/must_display_context_in_order.txt"));
+    }
+
+    #[test]
+    fn must_escape_context() {
+        let errors = ErrVec::new([io::Error::other("disk full")]).with_context([("filter", "a=1, b]2"), ("path", "C:\\data\nin")]);
+        assert_eq!(errors.to_string(), "encountered 1 error [filter=a\\=1\\, b\\]2, path=C:\\\\data\\nin]\n  * - disk full");
+    }
+
+    #[test]
+    fn must_not_write_empty_context() {
+        let errors = ErrVec::new([io::Error::other("disk full")]).with_context(Vec::<(String, String)>::new());
+        assert_eq!(errors.to_string(), "encountered 1 error\n  * - disk full");
+    }
+}
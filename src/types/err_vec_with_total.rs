@@ -0,0 +1,80 @@
+use crate::{ErrVec, ErrorChild, ErrorChildren, ErrorRegistry, FromErrors, Plural, write_error_children_to_formatter};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// An [`ErrVec`] together with the total number of the processed items (so that the message is not ambiguous: 3 errors in 3 items or in 30,000 items?)
+///
+/// Use it as a `source` of the iterator macros: they fill the `total` automatically (see [`FromErrors`]).
+#[derive(Clone, Debug)]
+pub struct ErrVecWithTotal<E: Error> {
+    pub errors: ErrVec<E>,
+    pub total: usize,
+}
+
+impl<E: Error> ErrVecWithTotal<E> {
+    pub fn new(errors: impl IntoIterator<Item = E>, total: usize) -> Self {
+        Self {
+            errors: ErrVec::new(errors),
+            total,
+        }
+    }
+}
+
+impl<E: Error + 'static> Display for ErrVecWithTotal<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl<E: Error + 'static> Error for ErrVecWithTotal<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl<E: Error + 'static> ErrorChildren for ErrVecWithTotal<E> {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "encountered {errors} in {items}", errors = Plural::new(self.errors.len(), "error"), items = Plural::new(self.total, "item"))
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        self.errors.children()
+    }
+}
+
+impl<E: Error> FromErrors<E> for ErrVecWithTotal<E> {
+    fn from_errors(errors: Vec<E>, total: usize) -> Self {
+        Self::new(errors, total)
+    }
+}
+
+impl<E: Error> Deref for ErrVecWithTotal<E> {
+    type Target = ErrVec<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.errors
+    }
+}
+
+impl<E: Error> DerefMut for ErrVecWithTotal<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVecWithTotal, ErrorDisplayer};
+    use pretty_assertions::assert_eq;
+    use std::io;
+
+    #[test]
+    fn must_display_total() {
+        let errors = ErrVecWithTotal::new([io::Error::other("disk full"), io::Error::other("disk full")], 120);
+        assert_eq!(errors.to_string(), "encountered 2 errors in 120 items\n  * - disk full\n  * - disk full");
+        assert_eq!(ErrorDisplayer(&errors).to_string(), "- encountered 2 errors in 120 items\n  * - disk full\n  * - disk full");
+    }
+}
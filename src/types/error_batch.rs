@@ -0,0 +1,52 @@
+use crate::{ErrVec, ErrorChild, ErrorChildren, ErrorRegistry, Plural, write_error_children_to_formatter};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::ops::Deref;
+use std::time::{Duration, Instant};
+
+/// The errors that were emitted by [`ErrorBatcher`](crate::ErrorBatcher) together with the time span they cover.
+#[derive(Clone, Debug)]
+pub struct ErrorBatch<E: Error> {
+    pub errors: ErrVec<E>,
+    pub first_at: Instant,
+    pub last_at: Instant,
+}
+
+impl<E: Error> ErrorBatch<E> {
+    /// Returns the time between the first and the last error.
+    pub fn span(&self) -> Duration {
+        self.last_at.saturating_duration_since(self.first_at)
+    }
+}
+
+impl<E: Error + 'static> Display for ErrorBatch<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl<E: Error + 'static> Error for ErrorBatch<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl<E: Error + 'static> ErrorChildren for ErrorBatch<E> {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "encountered {errors} over {span:?}", errors = Plural::new(self.errors.len(), "error"), span = self.span())
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        self.errors.children()
+    }
+}
+
+impl<E: Error> Deref for ErrorBatch<E> {
+    type Target = ErrVec<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.errors
+    }
+}
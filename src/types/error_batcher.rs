@@ -0,0 +1,129 @@
+use crate::{ErrVec, ErrorBatch};
+use core::error::Error;
+use core::mem::take;
+use std::time::{Duration, Instant};
+
+/// Accumulates the errors of a long-running pipeline (which has no natural end of batch) and emits them in batches.
+///
+/// A batch is emitted by [`poll_flush`](Self::poll_flush) when it contains `max_len` errors or when its oldest error is older than `max_age`.
+/// Call [`flush`](Self::flush) on shutdown to drain the remaining errors.
+/// The methods accept the current time explicitly, so that the batcher can be driven by any clock.
+#[derive(Debug)]
+pub struct ErrorBatcher<E: Error> {
+    errors: Vec<E>,
+    first_at: Option<Instant>,
+    last_at: Option<Instant>,
+    pub max_len: usize,
+    pub max_age: Duration,
+}
+
+impl<E: Error> ErrorBatcher<E> {
+    pub fn new(max_len: usize, max_age: Duration) -> Self {
+        Self {
+            errors: Vec::new(),
+            first_at: None,
+            last_at: None,
+            max_len,
+            max_age,
+        }
+    }
+
+    pub fn push(&mut self, error: E) {
+        self.push_at(error, Instant::now())
+    }
+
+    pub fn push_at(&mut self, error: E, now: Instant) {
+        self.first_at.get_or_insert(now);
+        self.last_at = Some(now);
+        self.errors.push(error);
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the time when the current batch must be emitted (if the batch is not empty).
+    pub fn deadline(&self) -> Option<Instant> {
+        self.first_at.map(|first_at| first_at + self.max_age)
+    }
+
+    /// Returns the batch if it is full or if its oldest error is older than `max_age`.
+    pub fn poll_flush(&mut self, now: Instant) -> Option<ErrorBatch<E>> {
+        let is_full = self.errors.len() >= self.max_len;
+        let is_expired = self.deadline().is_some_and(|deadline| deadline <= now);
+        if is_full || is_expired { self.flush() } else { None }
+    }
+
+    /// Returns the batch regardless of the thresholds (or [`None`] if there are no errors).
+    pub fn flush(&mut self) -> Option<ErrorBatch<E>> {
+        let first_at = self.first_at.take()?;
+        let last_at = self.last_at.take().unwrap_or(first_at);
+        Some(ErrorBatch {
+            errors: ErrVec(take(&mut self.errors)),
+            first_at,
+            last_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrorBatcher, ErrorDisplayer};
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use std::time::{Duration, Instant};
+
+    fn error(message: &str) -> io::Error {
+        io::Error::other(message.to_string())
+    }
+
+    #[test]
+    fn must_flush_when_full() {
+        let start = Instant::now();
+        let mut batcher = ErrorBatcher::new(3, Duration::from_secs(60));
+        batcher.push_at(error("a"), start);
+        batcher.push_at(error("b"), start + Duration::from_secs(1));
+        assert!(batcher.poll_flush(start + Duration::from_secs(1)).is_none());
+        batcher.push_at(error("c"), start + Duration::from_secs(2));
+        let batch = batcher.poll_flush(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.span(), Duration::from_secs(2));
+        assert!(batcher.is_empty());
+        assert!(
+            batcher
+                .poll_flush(start + Duration::from_secs(3600))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn must_flush_when_expired() {
+        let start = Instant::now();
+        let mut batcher = ErrorBatcher::new(100, Duration::from_secs(30));
+        batcher.push_at(error("a"), start);
+        batcher.push_at(error("b"), start + Duration::from_secs(30));
+        assert!(
+            batcher
+                .poll_flush(start + Duration::from_secs(29))
+                .is_none()
+        );
+        let batch = batcher.poll_flush(start + Duration::from_secs(30)).unwrap();
+        assert_eq!(ErrorDisplayer(&batch).to_string(), "- encountered 2 errors over 30s\n  * - a\n  * - b");
+    }
+
+    #[test]
+    fn must_drain_on_flush() {
+        let start = Instant::now();
+        let mut batcher = ErrorBatcher::new(100, Duration::from_secs(30));
+        assert!(batcher.flush().is_none());
+        batcher.push_at(error("a"), start);
+        let batch = batcher.flush().unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.span(), Duration::ZERO);
+        assert!(batcher.flush().is_none());
+    }
+}
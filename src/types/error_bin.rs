@@ -0,0 +1,40 @@
+use crate::DeferredErrors;
+use std::error::Error;
+use std::sync::{Mutex, PoisonError};
+
+static GLOBAL: Mutex<Vec<Box<dyn Error + Send + Sync>>> = Mutex::new(Vec::new());
+
+/// Serializes the tests that push into the bin or take from it (the tests run in parallel, but the bin is global)
+#[cfg(test)]
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// The global bin for the errors that can't be returned to the caller (e.g. the errors that occur in `Drop` impls).
+///
+/// The errors are reported at the end of the process by [`exit_result_with_bin`](crate::exit_result_with_bin).
+pub struct ErrorBin;
+
+impl ErrorBin {
+    /// Pushes the error into the bin.
+    pub fn push(error: impl Error + Send + Sync + 'static) {
+        GLOBAL
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(error))
+    }
+
+    /// Takes all errors from the bin, or returns `None` if the bin is empty.
+    pub fn take() -> Option<DeferredErrors> {
+        let errors = core::mem::take(&mut *GLOBAL.lock().unwrap_or_else(PoisonError::into_inner));
+        (!errors.is_empty()).then_some(DeferredErrors {
+            errors,
+        })
+    }
+}
+
+/// Locks the bin for the duration of the test and empties it, so that the test doesn't observe (or take) the errors of the other tests.
+#[cfg(test)]
+pub fn lock_error_bin_for_test() -> std::sync::MutexGuard<'static, ()> {
+    let guard = TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    ErrorBin::take();
+    guard
+}
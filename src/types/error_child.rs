@@ -0,0 +1,27 @@
+use alloc::string::String;
+use core::error::Error;
+
+/// A child of an aggregate error, optionally labeled (e.g. `"shard 3"`).
+#[derive(Debug)]
+pub struct ErrorChild<'a> {
+    /// The label that is rendered before the child error message.
+    pub label: Option<String>,
+    /// The child error.
+    pub error: &'a (dyn Error + 'static),
+}
+
+impl<'a> ErrorChild<'a> {
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        Self {
+            label: None,
+            error,
+        }
+    }
+
+    pub fn labeled(label: impl Into<String>, error: &'a (dyn Error + 'static)) -> Self {
+        Self {
+            label: Some(label.into()),
+            error,
+        }
+    }
+}
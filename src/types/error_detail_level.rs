@@ -0,0 +1,45 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+
+static CURRENT: AtomicU8 = AtomicU8::new(ErrorDetailLevel::Minimal as u8);
+
+/// Serializes the tests that depend on the level (the tests run in parallel, but the level is global)
+#[cfg(all(test, feature = "std"))]
+static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Determines whether the expensive fields of the errors are captured (see [`Detailed`](crate::Detailed)).
+///
+/// The level is global (the application sets it at startup via [`set_error_detail_level`](crate::set_error_detail_level), e.g. from a `--verbose-errors` flag). The default level is [`Minimal`](ErrorDetailLevel::Minimal).
+#[derive(Default, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+#[repr(u8)]
+pub enum ErrorDetailLevel {
+    /// Store only the size of the expensive values
+    #[default]
+    Minimal,
+    /// Store the expensive values
+    Full,
+}
+
+impl ErrorDetailLevel {
+    /// Returns the global error detail level.
+    pub fn current() -> Self {
+        match CURRENT.load(Ordering::Relaxed) {
+            level if level == Self::Full as u8 => Self::Full,
+            _ => Self::Minimal,
+        }
+    }
+
+    /// Sets the global error detail level (same as [`set_error_detail_level`](crate::set_error_detail_level)).
+    pub fn store(self) {
+        CURRENT.store(self as u8, Ordering::Relaxed)
+    }
+}
+
+/// Locks the level for the duration of the test and sets it, so that the test doesn't observe the levels set by the other tests.
+#[cfg(all(test, feature = "std"))]
+pub fn lock_error_detail_level_for_test(level: ErrorDetailLevel) -> std::sync::MutexGuard<'static, ()> {
+    let guard = TEST_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    level.store();
+    guard
+}
@@ -1,17 +1,87 @@
-use crate::writeln_error_to_formatter;
-use core::fmt::{Display, Formatter};
-use std::error::Error;
+use crate::{AsDynError, ErrorStyle, StyledErrorDisplayer, write_error_chain_to_fmt};
+use core::fmt::{Debug, Display, Formatter};
 
 pub struct ErrorDisplayer<'a, E: ?Sized>(pub &'a E);
 
-impl<'a, E: Error + ?Sized> Display for ErrorDisplayer<'a, E> {
+impl<'a, E: AsDynError + ?Sized> ErrorDisplayer<'a, E> {
+    /// Returns a displayer that renders the error trace with the provided markers (see [`ErrorStyle`]).
+    pub fn with_style(error: &'a E, style: ErrorStyle) -> StyledErrorDisplayer<'a, E> {
+        StyledErrorDisplayer {
+            error,
+            style,
+        }
+    }
+}
+
+impl<'a, E: AsDynError + ?Sized> Display for ErrorDisplayer<'a, E> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        writeln_error_to_formatter(self.0, f)
+        write_error_chain_to_fmt(self.0, f)
     }
 }
 
-impl<'a, E: Error + ?Sized> From<&'a E> for ErrorDisplayer<'a, E> {
+/// Renders the same error chain as the [`Display`] impl, so that `ErrorDisplayer` can be used as a field in `#[derive(Debug)]` structs.
+impl<'a, E: AsDynError + ?Sized> Debug for ErrorDisplayer<'a, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write_error_chain_to_fmt(self.0, f)
+    }
+}
+
+impl<'a, E: AsDynError + ?Sized> From<&'a E> for ErrorDisplayer<'a, E> {
     fn from(error: &'a E) -> Self {
         Self(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer, ErrorStyle};
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_display_with_style() {
+        let error = OuterError::BatchFailed {
+            source: ErrVec::new([
+                OuterError::InnerFailed {
+                    source: InnerError::Invalid,
+                },
+                OuterError::InnerFailed {
+                    source: InnerError::Invalid,
+                },
+            ]),
+        };
+        let default = ErrorDisplayer::with_style(&error, ErrorStyle::default()).to_string();
+        assert_eq!(default, ErrorDisplayer(&error).to_string());
+        let style = ErrorStyle {
+            chain_bullet: "→ ",
+            child_bullet: "• ",
+            indent: "    ",
+        };
+        let actual = ErrorDisplayer::with_style(&error, style).to_string();
+        assert_eq!(actual, "→ batch failed\n→ encountered 2 errors\n    • → inner failed\n        → invalid\n    • → inner failed\n        → invalid");
+    }
+
+    #[test]
+    fn must_debug_as_display() {
+        let error = OuterError::InnerFailed {
+            source: InnerError::Invalid,
+        };
+        let displayer = ErrorDisplayer(&error);
+        assert_eq!(format!("{displayer:?}"), format!("{displayer}"));
+        assert_eq!(format!("{displayer:?}"), "- inner failed\n- invalid");
+    }
+
+    #[derive(Error, Debug)]
+    enum OuterError {
+        #[error("inner failed")]
+        InnerFailed { source: InnerError },
+        #[error("batch failed")]
+        BatchFailed { source: ErrVec<OuterError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum InnerError {
+        #[error("invalid")]
+        Invalid,
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{ErrorChildren, ErrorPath, RetryAfter, RetryDelay};
+use core::any::TypeId;
+use std::error::Error;
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
+static CHILDREN: Downcasts<dyn ErrorChildren> = Downcasts::new();
+
+static RETRY_DELAYS: Downcasts<RetryDelay> = Downcasts::new();
+
+static PATHS: Downcasts<dyn ErrorPath> = Downcasts::new();
+
+/// The registry of the error types that the renderer inspects via their traits (e.g. the aggregates that implement [`ErrorChildren`]).
+///
+/// The renderer walks the error tree via `&dyn Error`, which can only be downcast to a concrete type, so every type is registered together with its downcast.
+/// The types of this crate register themselves in their `Error::source` impls (the renderer calls `source` before the lookup). Register your own types once at startup, or from their `Error::source` impls in the same way.
+pub struct ErrorRegistry;
+
+impl ErrorRegistry {
+    /// Registers the aggregate type, so that the renderer writes its children as nested bullets (see [`ErrorChildren`]).
+    ///
+    /// Registering the same type again does nothing.
+    pub fn register_children<T: Error + ErrorChildren + 'static>() {
+        CHILDREN.register::<T>(|error| {
+            error
+                .downcast_ref::<T>()
+                .map(|aggregate| aggregate as &dyn ErrorChildren)
+        })
+    }
+
+    /// Returns the children of the error if its type is registered via [`ErrorRegistry::register_children`].
+    pub fn children<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a dyn ErrorChildren> {
+        CHILDREN.find(error)
+    }
+
+    /// Registers the [`RetryAfter`] wrapper of the errors of type `E`, so that [`extract_retry_after`](crate::extract_retry_after) finds its delay.
+    ///
+    /// Registering the same type again does nothing.
+    pub fn register_retry_after<E: Error + 'static>() {
+        RETRY_DELAYS.register::<RetryAfter<E>>(|error| {
+            error
+                .downcast_ref::<RetryAfter<E>>()
+                .map(|retry_after| &retry_after.delay)
+        })
+    }
+
+    /// Returns the delay of the error if it's a [`RetryAfter`] that is registered via [`ErrorRegistry::register_retry_after`].
+    pub fn retry_delay<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a RetryDelay> {
+        RETRY_DELAYS.find(error)
+    }
+
+    /// Registers the error type that concerns a path, so that [`error_paths`](crate::error_paths) finds its path (see [`ErrorPath`]).
+    ///
+    /// Registering the same type again does nothing.
+    pub fn register_path<T: Error + ErrorPath + 'static>() {
+        PATHS.register::<T>(|error| {
+            error
+                .downcast_ref::<T>()
+                .map(|error| error as &dyn ErrorPath)
+        })
+    }
+
+    /// Returns the path of the error if its type is registered via [`ErrorRegistry::register_path`].
+    pub fn path<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a Path> {
+        PATHS.find(error).and_then(ErrorPath::path)
+    }
+}
+
+/// Converts the error into the view `V` if the error has the registered type
+type Downcast<V> = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a V>;
+
+/// The downcasts of the registered types into the view `V`
+struct Downcasts<V: ?Sized + 'static>(RwLock<Vec<(TypeId, Downcast<V>)>>);
+
+impl<V: ?Sized + 'static> Downcasts<V> {
+    const fn new() -> Self {
+        Self(RwLock::new(Vec::new()))
+    }
+
+    fn register<T: 'static>(&self, downcast: Downcast<V>) {
+        let type_id = TypeId::of::<T>();
+        let is_registered = |downcasts: &[(TypeId, Downcast<V>)]| downcasts.iter().any(|(id, _)| *id == type_id);
+        // the read lock is enough for the repeated registrations (the types of this crate register themselves on every `source` call)
+        if is_registered(&self.0.read().unwrap_or_else(PoisonError::into_inner)) {
+            return;
+        }
+        let mut downcasts = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        if !is_registered(&downcasts) {
+            downcasts.push((type_id, downcast))
+        }
+    }
+
+    fn find<'a>(&self, error: &'a (dyn Error + 'static)) -> Option<&'a V> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find_map(|(_, downcast)| downcast(error))
+    }
+}
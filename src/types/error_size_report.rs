@@ -0,0 +1,46 @@
+use crate::VariantSize;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// The memory layout of an error type (see [`error_size_report`](crate::error_size_report())).
+///
+/// Error types are moved through every [`Result`] that contains them, so it's useful to keep them small.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ErrorSizeReport {
+    /// The name of the error type.
+    pub type_name: &'static str,
+    /// The size of the error type in bytes.
+    pub size: usize,
+    /// The alignment of the error type in bytes.
+    pub align: usize,
+    /// The payload sizes of the variants (empty if the variants were not listed).
+    pub variants: Vec<VariantSize>,
+}
+
+impl ErrorSizeReport {
+    /// Returns the variants whose fields other than `source` exceed the budget (see [`VariantSize::non_source_size`]).
+    pub fn variants_over_budget(&self, max_non_source_size: usize) -> impl Iterator<Item = &VariantSize> {
+        self.variants
+            .iter()
+            .filter(move |variant| variant.non_source_size > max_non_source_size)
+    }
+}
+
+impl Display for ErrorSizeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: size {} bytes, align {} bytes", self.type_name, self.size, self.align)?;
+        let width = self
+            .variants
+            .iter()
+            .map(|variant| variant.name.len())
+            .chain(["variant".len()])
+            .max()
+            .unwrap_or_default();
+        if !self.variants.is_empty() {
+            write!(f, "\n  {:<width$}  payload  non-source", "variant")?;
+        }
+        self.variants
+            .iter()
+            .try_for_each(|variant| write!(f, "\n  {:<width$}  {:<7}  {}", variant.name, variant.payload_size, variant.non_source_size))
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{FmtPrefixer, ParsedTree};
+use core::fmt::Write;
+
+/// The markers of the error trace (see [`ErrorDisplayer::with_style`](crate::ErrorDisplayer::with_style)).
+///
+/// The default style produces the same output as [`ErrorDisplayer`](crate::ErrorDisplayer).
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub struct ErrorStyle {
+    /// The marker of every error in the chain
+    pub chain_bullet: &'static str,
+    /// The marker of every child of an aggregate (written before the chain bullet of the child)
+    pub child_bullet: &'static str,
+    /// The prefix of the continuation lines of every message and of the children of the aggregates
+    pub indent: &'static str,
+}
+
+impl ErrorStyle {
+    /// Writes the trace of the parsed error tree (every node of the chain on its own line, the children of the aggregates nested under their parents).
+    pub fn write_tree(&self, tree: &ParsedTree, writer: &mut dyn Write) -> core::fmt::Result {
+        writer.write_str(self.chain_bullet)?;
+        self.write_node(tree, &mut FmtPrefixer::continuation(writer, self.indent))?;
+        match &tree.source {
+            Some(source) => {
+                writer.write_char('\n')?;
+                self.write_tree(source, writer)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the message and the children of the node (without the bullet and without the source).
+    pub fn write_node(&self, tree: &ParsedTree, writer: &mut dyn Write) -> core::fmt::Result {
+        writer.write_str(&tree.message)?;
+        tree.children.iter().try_for_each(|child| {
+            write!(writer, "\n{}", self.child_bullet)?;
+            if let Some(label) = &child.label {
+                write!(writer, "[{label}] ")?;
+            }
+            self.write_tree(child, &mut FmtPrefixer::continuation(writer, self.indent))
+        })
+    }
+}
+
+impl Default for ErrorStyle {
+    fn default() -> Self {
+        Self {
+            chain_bullet: "- ",
+            child_bullet: "* ",
+            indent: "  ",
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::FieldPath;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// An error of a specific field of a nested structure (e.g. `server.listeners[2].port: must be greater than 0`).
+///
+/// The message of the source error is inlined into the message of this error (after the path), so [`source`](Error::source) returns the source of the source error.
+#[derive(Debug)]
+pub struct FieldError {
+    pub path: FieldPath,
+    pub source: Box<dyn Error + Send + Sync>,
+}
+
+impl FieldError {
+    pub fn new(path: impl Into<FieldPath>, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            path: path.into(),
+            source: source.into(),
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.source)
+        } else {
+            write!(f, "{}: {}", self.path, self.source)
+        }
+    }
+}
+
+impl Error for FieldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.source()
+    }
+}
@@ -0,0 +1,74 @@
+use crate::FieldSegment;
+use core::fmt::{Display, Formatter};
+
+/// A path into a nested structure (e.g. `server.listeners[2].port`).
+///
+/// The segments are pushed and popped during the traversal of the structure (see [`ValidationErrors::with_segment`](crate::ValidationErrors::with_segment)).
+#[derive(Eq, PartialEq, Hash, Default, Clone, Debug)]
+pub struct FieldPath(pub Vec<FieldSegment>);
+
+impl FieldPath {
+    pub fn new(segments: impl IntoIterator<Item = FieldSegment>) -> Self {
+        Self(segments.into_iter().collect())
+    }
+
+    pub fn push(&mut self, segment: impl Into<FieldSegment>) {
+        self.0.push(segment.into())
+    }
+
+    pub fn pop(&mut self) -> Option<FieldSegment> {
+        self.0.pop()
+    }
+
+    /// Returns a new path that consists of the segments of this path followed by the segments of the other path.
+    pub fn join(&self, other: &FieldPath) -> Self {
+        Self(self.0.iter().chain(other.0.iter()).cloned().collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for FieldPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, segment)| match segment {
+                FieldSegment::Name(_) if index > 0 => write!(f, ".{segment}"),
+                _ => write!(f, "{segment}"),
+            })
+    }
+}
+
+impl<T: Into<FieldSegment>> From<T> for FieldPath {
+    fn from(segment: T) -> Self {
+        Self(vec![segment.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FieldPath, FieldSegment};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_display_path() {
+        let mut path = FieldPath::default();
+        assert_eq!(path.to_string(), "");
+        path.push("server");
+        path.push("listeners");
+        path.push(2);
+        path.push("port");
+        assert_eq!(path.to_string(), "server.listeners[2].port");
+        assert_eq!(path.pop(), Some(FieldSegment::Name("port".to_string())));
+        assert_eq!(path.to_string(), "server.listeners[2]");
+        assert_eq!(
+            FieldPath::from(0)
+                .join(&FieldPath::from("name"))
+                .to_string(),
+            "[0].name"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use core::fmt::{Display, Formatter};
+
+/// A segment of a [`FieldPath`](crate::FieldPath): a field name or an index of an element.
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+pub enum FieldSegment {
+    Name(String),
+    Index(usize),
+}
+
+impl Display for FieldSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldSegment::Name(name) => f.write_str(name),
+            FieldSegment::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
+impl From<&str> for FieldSegment {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<String> for FieldSegment {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl From<usize> for FieldSegment {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
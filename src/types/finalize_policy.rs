@@ -0,0 +1,9 @@
+/// What a [`MustFinalize`](crate::MustFinalize) guard does if it is dropped without finalization.
+#[derive(Default, Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum FinalizePolicy {
+    /// Push the [`NotFinalized`](crate::NotFinalized) error into the [`ErrorBin`](crate::ErrorBin)
+    #[default]
+    Record,
+    /// Panic in debug builds (if the thread is not already panicking), record in release builds
+    PanicInDebug,
+}
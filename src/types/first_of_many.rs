@@ -0,0 +1,31 @@
+use crate::Plural;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// The first error of an [`ErrVec`](crate::ErrVec) together with the total number of the errors (see [`ErrVec::first_with_context`](crate::ErrVec::first_with_context)).
+///
+/// This is useful for the APIs that accept a single error: the trace contains the first error and the number of the suppressed errors, so the reader knows that the trace is incomplete.
+#[derive(Clone, Debug)]
+pub struct FirstOfMany<E: Error> {
+    pub source: E,
+    pub len: usize,
+}
+
+impl<E: Error> FirstOfMany<E> {
+    /// Returns the number of the errors that are not included in this error.
+    pub fn suppressed(&self) -> usize {
+        self.len.saturating_sub(1)
+    }
+}
+
+impl<E: Error> Display for FirstOfMany<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "first of {errors} ({suppressed} more suppressed)", errors = Plural::new(self.len, "error"), suppressed = self.suppressed())
+    }
+}
+
+impl<E: Error + 'static> Error for FirstOfMany<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
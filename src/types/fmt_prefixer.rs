@@ -0,0 +1,92 @@
+use core::fmt::Write;
+
+/// A [`fmt::Write`](Write) adapter that writes the prefix before every line (including the empty lines between the newlines, e.g. `"\n\n"` produces two prefixed empty lines).
+///
+/// The prefix is written lazily (right before the first character of the line, which may be the newline itself), so a trailing newline doesn't produce a dangling prefix, and an empty write doesn't produce anything.
+/// The output doesn't depend on how the input is split into writes.
+pub struct FmtPrefixer<'w> {
+    writer: &'w mut dyn Write,
+    prefix: &'w str,
+    is_line_start: bool,
+}
+
+impl<'w> FmtPrefixer<'w> {
+    /// Prefixes every line, including the first one.
+    pub fn new(writer: &'w mut dyn Write, prefix: &'w str) -> Self {
+        Self {
+            writer,
+            prefix,
+            is_line_start: true,
+        }
+    }
+
+    /// Prefixes every line except the first one (useful for indenting the continuation lines of a bullet).
+    pub fn continuation(writer: &'w mut dyn Write, prefix: &'w str) -> Self {
+        Self {
+            writer,
+            prefix,
+            is_line_start: false,
+        }
+    }
+}
+
+impl<'w> Write for FmtPrefixer<'w> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        s.split_inclusive('\n').try_for_each(|line| {
+            if self.is_line_start {
+                self.writer.write_str(self.prefix)?;
+            }
+            self.is_line_start = line.ends_with('\n');
+            self.writer.write_str(line)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::FmtPrefixer;
+    use alloc::string::String;
+    use core::fmt::Write;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_prefix_edge_inputs() {
+        assert_prefixed("", "", "");
+        assert_prefixed("\n", "> \n", "\n");
+        assert_prefixed("\n\n", "> \n> \n", "\n> \n");
+        assert_prefixed("a", "> a", "a");
+        assert_prefixed("a\n", "> a\n", "a\n");
+        assert_prefixed("a\nb", "> a\n> b", "a\n> b");
+        assert_prefixed("a\nb\n", "> a\n> b\n", "a\n> b\n");
+        assert_prefixed("a\n\n\nb", "> a\n> \n> \n> b", "a\n> \n> \n> b");
+        assert_prefixed("é\nü", "> é\n> ü", "é\n> ü");
+    }
+
+    #[test]
+    fn must_not_prefix_empty_writes() {
+        let mut output = String::new();
+        let mut prefixer = FmtPrefixer::new(&mut output, "> ");
+        prefixer.write_str("").unwrap();
+        prefixer.write_str("a\n").unwrap();
+        prefixer.write_str("").unwrap();
+        assert_eq!(output, "> a\n");
+    }
+
+    /// Checks the output of both constructors for every split of the input into two writes (the output must not depend on the split)
+    fn assert_prefixed(input: &str, expected_new: &str, expected_continuation: &str) {
+        (0..=input.len())
+            .filter(|&index| input.is_char_boundary(index))
+            .for_each(|index| {
+                let (head, tail) = input.split_at(index);
+                let write = |new: fn(&mut String) -> FmtPrefixer<'_>| {
+                    let mut output = String::new();
+                    let mut prefixer = new(&mut output);
+                    prefixer.write_str(head).unwrap();
+                    prefixer.write_str(tail).unwrap();
+                    output
+                };
+                assert_eq!(write(|output| FmtPrefixer::new(output, "> ")), expected_new, "input: {input:?}, split at {index}");
+                assert_eq!(write(|output| FmtPrefixer::continuation(output, "> ")), expected_continuation, "input: {input:?}, split at {index}");
+            });
+    }
+}
@@ -0,0 +1,35 @@
+use core::hash::Hasher;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// The incremental 64-bit FNV-1a hasher (see [`fnv1a_hash`](crate::fnv1a_hash)).
+///
+/// It also implements [`fmt::Write`](core::fmt::Write), so the formatted text can be hashed without building a `String`.
+#[derive(Copy, Clone, Debug)]
+pub struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = bytes
+            .iter()
+            .fold(self.0, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+    }
+}
+
+impl core::fmt::Write for Fnv1aHasher {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
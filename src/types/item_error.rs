@@ -1,11 +1,85 @@
 use thiserror::Error;
 
 /// Associates an error with the item that caused it.
+///
+/// `ItemError<T, E>` implements [`Error`](core::error::Error) only if `T: Debug + Display` and `E: Error` (the impl is derived), so the `From<Vec<ItemError<T, E>>>` impl of [`ErrVec`](crate::ErrVec) (used by [`handle_iter_of_refs!`](crate::handle_iter_of_refs)) is available only under these bounds.
+/// If the item doesn't implement `Display`, map it to a displayable key (e.g. an index or a name) before constructing the `ItemError`.
+///
+/// The message doesn't include the source error (the trace writes the source on the next line), so the source text appears only once.
+///
+/// `ItemError<T, E>` converts into `Box<dyn Error>` (and `Box<dyn Error + Send + Sync>` if `T` and `E` are `Send + Sync`) via the blanket impl in `std`, so it can be returned with `?` from functions that return boxed errors.
 #[derive(Error, Debug)]
-#[error("error occurred for item {item}: {source}")]
+#[error("error occurred for item {item}")]
 pub struct ItemError<T, E> {
     /// The item that produced the error.
     pub item: T,
     /// The error produced for the item.
     pub source: E,
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer, ItemError, handle_iter_of_refs, writeln_error_to_writer};
+    use pretty_assertions::assert_eq;
+    use std::error::Error;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn must_write_items() {
+        let errors: ErrVec<ItemError<String, ParseIntError>> = ["x", "1", "y"]
+            .into_iter()
+            .filter_map(|input| {
+                input.parse::<u32>().err().map(|source| ItemError {
+                    item: input.to_string(),
+                    source,
+                })
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let mut actual = Vec::new();
+        writeln_error_to_writer(&errors, &mut actual).unwrap();
+        let actual = String::from_utf8(actual).unwrap();
+        assert!(actual.contains("error occurred for item x"), "{actual}");
+        assert!(actual.contains("error occurred for item y"), "{actual}");
+    }
+
+    #[test]
+    fn must_convert_into_boxed_error() {
+        fn parse(input: &str) -> Result<u32, Box<dyn Error + Send + Sync>> {
+            let number = input.parse::<u32>().map_err(|source| ItemError {
+                item: input.to_string(),
+                source,
+            })?;
+            Ok(number)
+        }
+        let error = parse("x").unwrap_err();
+        assert_eq!(error.to_string(), "error occurred for item x");
+        assert_eq!(error.source().unwrap().to_string(), "invalid digit found in string");
+        assert!(
+            error
+                .downcast_ref::<ItemError<String, ParseIntError>>()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn must_write_item_and_source_once() {
+        let inputs = vec!["8080".to_string(), "http".to_string(), "-1".to_string()];
+        let error = parse_ports(&inputs).unwrap_err();
+        let expected = include_str!("item_error/fixtures/must_write_item_and_source_once.txt");
+        assert_eq!(ErrorDisplayer(&error).to_string(), expected.trim_end());
+    }
+
+    fn parse_ports(inputs: &[String]) -> Result<Vec<u16>, ParsePortsError> {
+        use ParsePortsError::*;
+        let results = inputs.iter().map(|input| input.parse::<u16>());
+        let (ports, _inputs) = handle_iter_of_refs!(results, inputs.iter().cloned(), PortsParseFailed);
+        Ok(ports)
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    enum ParsePortsError {
+        #[error("failed to parse {count} ports", count = source.len())]
+        PortsParseFailed { source: ErrVec<ItemError<String, ParseIntError>> },
+    }
+}
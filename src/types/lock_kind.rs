@@ -0,0 +1,20 @@
+use core::fmt::{Display, Formatter};
+
+/// The kind of the lock guard that was requested from a poisoned lock.
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum LockKind {
+    Read,
+    Write,
+    Mutex,
+}
+
+impl Display for LockKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use LockKind::*;
+        f.write_str(match self {
+            Read => "read",
+            Write => "write",
+            Mutex => "mutex",
+        })
+    }
+}
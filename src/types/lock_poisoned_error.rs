@@ -0,0 +1,20 @@
+use crate::{LockGuard, LockKind};
+use std::sync::PoisonError;
+use thiserror::Error;
+
+/// An owned `'static` replacement for [`PoisonError`], which can't be used as a `source` because it contains the lock guard (with a lifetime).
+///
+/// See also: [`handle_lock!`](crate::handle_lock)
+#[derive(Error, Eq, PartialEq, Copy, Clone, Debug)]
+#[error("{kind} lock poisoned")]
+pub struct LockPoisonedError {
+    pub kind: LockKind,
+}
+
+impl<G: LockGuard> From<PoisonError<G>> for LockPoisonedError {
+    fn from(_error: PoisonError<G>) -> Self {
+        Self {
+            kind: G::KIND,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use core::marker::PhantomData;
+
+/// An iterator that converts the errors of the inner iterator via [`Into`] (see [`MapErrIntoExt::map_err_into`](crate::MapErrIntoExt::map_err_into)).
+#[derive(Clone, Debug)]
+pub struct MapErrInto<I, E2> {
+    iter: I,
+    error: PhantomData<fn() -> E2>,
+}
+
+impl<I, E2> MapErrInto<I, E2> {
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            error: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E: Into<E2>, E2> Iterator for MapErrInto<I, E2> {
+    type Item = Result<T, E2>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|result| result.map_err(Into::into))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator<Item = Result<T, E>>, T, E: Into<E2>, E2> DoubleEndedIterator for MapErrInto<I, E2> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|result| result.map_err(Into::into))
+    }
+}
+
+impl<I: ExactSizeIterator<Item = Result<T, E>>, T, E: Into<E2>, E2> ExactSizeIterator for MapErrInto<I, E2> {}
@@ -0,0 +1,20 @@
+use crate::MessageFindingKind;
+use core::fmt::{Display, Formatter};
+
+/// A suspicious fragment of an error message (see [`audit_error_messages`](crate::audit_error_messages)).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct MessageFinding {
+    /// The index of the sample error
+    pub sample: usize,
+    /// The message of the error in the tree of the sample (without the children of the aggregates)
+    pub message: String,
+    /// The byte offset of the suspicious fragment in the message
+    pub offset: usize,
+    pub kind: MessageFindingKind,
+}
+
+impl Display for MessageFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "sample {}: {} at byte {}: {}", self.sample, self.kind, self.offset, self.message)
+    }
+}
@@ -0,0 +1,19 @@
+use core::fmt::{Display, Formatter};
+
+/// The kind of a [`MessageFinding`](crate::MessageFinding).
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum MessageFindingKind {
+    /// A double quote before a path (e.g. `"/etc/app.toml"`): the `Debug` output of a path was interpolated (use [`display_path`](crate::display_path) instead)
+    DebugQuotedPath,
+    /// An escaped double quote before a path (e.g. `\"/etc/app.toml\"`): the `Debug` output of a message that already contains the `Debug` output of a path
+    DoubleQuotedPath,
+}
+
+impl Display for MessageFindingKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MessageFindingKind::DebugQuotedPath => f.write_str("Debug-quoted path"),
+            MessageFindingKind::DoubleQuotedPath => f.write_str("double-quoted path"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{ErrorBin, FinalizePolicy, NotFinalized};
+use core::any::type_name;
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+
+/// A guard for a resource that must be finalized explicitly (e.g. a buffered writer that must be flushed, or a multipart upload that must be completed).
+///
+/// Call [`finalize`](MustFinalize::finalize) to consume the resource. If the guard is dropped without finalization, a [`NotFinalized`] error with the location of [`MustFinalize::new`] call is pushed into the [`ErrorBin`] (see [`FinalizePolicy`]), so the data loss is not silent.
+/// The guard can't be finalized twice, because `finalize` takes the guard by value.
+#[derive(Debug)]
+pub struct MustFinalize<T> {
+    resource: Option<T>,
+    created_at: &'static Location<'static>,
+    policy: FinalizePolicy,
+}
+
+impl<T> MustFinalize<T> {
+    #[track_caller]
+    pub fn new(resource: T) -> Self {
+        Self::with_policy(resource, FinalizePolicy::default())
+    }
+
+    #[track_caller]
+    pub fn with_policy(resource: T, policy: FinalizePolicy) -> Self {
+        Self {
+            resource: Some(resource),
+            created_at: Location::caller(),
+            policy,
+        }
+    }
+
+    /// Returns the location of the [`MustFinalize::new`] call.
+    pub fn created_at(&self) -> &'static Location<'static> {
+        self.created_at
+    }
+
+    /// Consumes the resource via `f` (e.g. `guard.finalize(|mut writer| writer.flush())`).
+    pub fn finalize<Out, E>(mut self, f: impl FnOnce(T) -> Result<Out, E>) -> Result<Out, E> {
+        let resource = self.take_resource();
+        f(resource)
+    }
+
+    fn take_resource(&mut self) -> T {
+        match self.resource.take() {
+            Some(resource) => resource,
+            None => unreachable!("the resource is taken only by `finalize` (which consumes the guard)"),
+        }
+    }
+}
+
+impl<T> Deref for MustFinalize<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.resource {
+            Some(resource) => resource,
+            None => unreachable!("the resource is taken only by `finalize` (which consumes the guard)"),
+        }
+    }
+}
+
+impl<T> DerefMut for MustFinalize<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.resource {
+            Some(resource) => resource,
+            None => unreachable!("the resource is taken only by `finalize` (which consumes the guard)"),
+        }
+    }
+}
+
+impl<T> Drop for MustFinalize<T> {
+    fn drop(&mut self) {
+        if self.resource.take().is_none() {
+            return;
+        }
+        let error = NotFinalized {
+            type_name: type_name::<T>(),
+            created_at: self.created_at,
+        };
+        match self.policy {
+            FinalizePolicy::PanicInDebug if cfg!(debug_assertions) && !std::thread::panicking() => panic!("{error}"),
+            _ => ErrorBin::push(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrorBin, MustFinalize, NotFinalized, lock_error_bin_for_test};
+    use std::io::{self, BufWriter, Write};
+
+    #[test]
+    fn must_record_only_dropped_guards() {
+        let _lock = lock_error_bin_for_test();
+        let created_at_line = line!() + 1;
+        let dropped = MustFinalize::new(BufWriter::new(Vec::<u8>::new()));
+        let mut finalized = MustFinalize::new(BufWriter::new(Vec::<u8>::new()));
+        finalized.write_all(b"data").unwrap();
+        let bytes = finalized
+            .finalize(|writer| writer.into_inner().map_err(io::IntoInnerError::into_error))
+            .unwrap();
+        assert_eq!(bytes, b"data");
+        drop(dropped);
+        let deferred = ErrorBin::take().expect("always succeeds because the dropped guard has pushed an error into the locked bin");
+        let errors = deferred
+            .errors
+            .iter()
+            .filter_map(|error| error.downcast_ref::<NotFinalized>())
+            .collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].created_at.line(), created_at_line);
+        assert!(errors[0].type_name.contains("BufWriter"), "{}", errors[0].type_name);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "dropped without finalization")]
+    fn must_panic_in_debug() {
+        let _guard = MustFinalize::with_policy(Vec::<u8>::new(), crate::FinalizePolicy::PanicInDebug);
+    }
+}
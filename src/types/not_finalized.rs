@@ -0,0 +1,10 @@
+use core::panic::Location;
+use thiserror::Error;
+
+/// A [`MustFinalize`](crate::MustFinalize) guard was dropped without calling [`finalize`](crate::MustFinalize::finalize).
+#[derive(Error, Eq, PartialEq, Copy, Clone, Debug)]
+#[error("a value of type '{type_name}' created at {created_at} was dropped without finalization")]
+pub struct NotFinalized {
+    pub type_name: &'static str,
+    pub created_at: &'static Location<'static>,
+}
@@ -0,0 +1,29 @@
+use crate::{CanonicalKey, Severity};
+use core::fmt::{Display, Formatter};
+
+/// A user-facing summary of an error (e.g. for a toast notification), see [`notify_error`](crate::notify_error).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Notification {
+    /// The first line of the message of the top error (without the severity marker)
+    pub title: String,
+    /// The first line of the message of the root cause (`None` if the top error is the root cause)
+    pub body: Option<String>,
+    pub severity: Severity,
+    /// The number of the occurrences of the error that this notification represents (see [`DedupingSink`](crate::DedupingSink))
+    pub count: usize,
+    /// The key of the error that is used for deduplication
+    pub key: CanonicalKey,
+}
+
+impl Display for Notification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.severity, self.title)?;
+        if let Some(body) = &self.body {
+            write!(f, ": {body}")?;
+        }
+        match self.count {
+            1 => Ok(()),
+            count => write!(f, " (occurred {count} times)"),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::ffi::{OsStr, OsString};
+use std::fmt::{Debug, Display, Formatter, Write};
+
+/// An [`OsString`] that is displayed lossily (for readability) and debugged losslessly (for reproducibility).
+///
+/// Use it for the error fields that hold paths or environment values which may be invalid UTF-8: converting them to `String` lossily destroys the evidence.
+/// The `Debug` impl writes the valid UTF-8 chunks with [`str::escape_debug`] and the invalid bytes as `\xNN` escapes of the [encoded bytes](OsStr::as_encoded_bytes), so the output doesn't depend on the `Debug` impl of `OsStr`.
+#[derive(Eq, PartialEq, Hash, Default, Clone)]
+pub struct OsStrDisplay(pub OsString);
+
+impl Display for OsStrDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0.to_string_lossy(), f)
+    }
+}
+
+impl Debug for OsStrDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char('"')?;
+        self.0
+            .as_encoded_bytes()
+            .utf8_chunks()
+            .try_for_each(|chunk| {
+                write!(f, "{}", chunk.valid().escape_debug())?;
+                chunk
+                    .invalid()
+                    .iter()
+                    .try_for_each(|byte| write!(f, "\\x{byte:02x}"))
+            })?;
+        f.write_char('"')
+    }
+}
+
+impl From<OsString> for OsStrDisplay {
+    fn from(value: OsString) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&OsStr> for OsStrDisplay {
+    fn from(value: &OsStr) -> Self {
+        Self(value.to_os_string())
+    }
+}
+
+/// Serializes the value as a pair of the [encoded bytes](OsStr::as_encoded_bytes) (lossless) and the lossy string (readable).
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsStrDisplay {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("OsStrDisplay", 2)?;
+        state.serialize_field("bytes", self.0.as_encoded_bytes())?;
+        state.serialize_field("lossy", &*self.0.to_string_lossy())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::OsStrDisplay;
+    use pretty_assertions::assert_eq;
+    use std::ffi::OsString;
+
+    #[cfg(unix)]
+    fn invalid_os_string() -> OsString {
+        use std::os::unix::ffi::OsStringExt;
+        OsString::from_vec(b"caf\xe9 \"x\"".to_vec())
+    }
+
+    #[cfg(windows)]
+    fn invalid_os_string() -> OsString {
+        use std::os::windows::ffi::OsStringExt;
+        // a lone surrogate between "caf" and " \"x\""
+        let wide: Vec<u16> = "caf"
+            .encode_utf16()
+            .chain([0xD800])
+            .chain(" \"x\"".encode_utf16())
+            .collect();
+        OsString::from_wide(&wide)
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn must_display_lossily_and_debug_losslessly() {
+        let value = OsStrDisplay::from(invalid_os_string());
+        assert_eq!(value.to_string(), "caf\u{FFFD} \"x\"");
+        #[cfg(unix)]
+        assert_eq!(format!("{value:?}"), r#""caf\xe9 \"x\"""#);
+        #[cfg(windows)]
+        assert_eq!(format!("{value:?}"), r#""caf\xed\xa0\x80 \"x\"""#);
+    }
+
+    #[test]
+    fn must_debug_valid_unicode() {
+        assert_eq!(format!("{:?}", OsStrDisplay::from(OsString::from("naïve\n"))), r#""naïve\n""#);
+    }
+
+    #[cfg(all(unix, feature = "serde"))]
+    #[test]
+    fn must_serialize_bytes_and_lossy_string() {
+        let value = OsStrDisplay::from(invalid_os_string());
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"bytes":[99,97,102,233,32,34,120,34],"lossy":"caf� \"x\""}"#);
+    }
+}
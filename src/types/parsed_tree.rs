@@ -0,0 +1,81 @@
+use core::fmt::{Display, Formatter, Write};
+
+/// A tree of the error messages (see [`error_parsed_tree`](crate::error_parsed_tree) and [`parse_error_trace`](crate::parse_error_trace)).
+///
+/// The `Display` impl writes the tree in the strict trace format v1 (without the header line):
+///
+/// * `-[d] message` is a node of the chain at depth `d` (the first node of the top chain or the source of the previous node at the same depth)
+/// * `*[d] message` is the first node of a child chain at depth `d` (a child of the last node at depth `d - 1`)
+/// * `@[d] label` is the label of the next child chain (always followed by a `*[d]` line)
+/// * `|[d] line` is a continuation line of the message of the previous node at depth `d`
+///
+/// Every line starts with a marker, so the messages can't be confused with the structure (even if they start with `- ` or `* `).
+/// The `\` characters, the carriage returns and the newlines in the messages and the labels are escaped as `\\`, `\r` and `\n` (the newlines in the messages are written as the continuation lines, so only the labels contain the escaped newlines).
+/// The lines are indented by two spaces per depth level for readability (the indentation is ignored by the parser).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ParsedTree {
+    /// The message of the error (without the children of the aggregate)
+    pub message: String,
+    /// The label of the error (if it is a labeled child of an aggregate)
+    pub label: Option<String>,
+    /// The children of the aggregate
+    pub children: Vec<ParsedTree>,
+    /// The source error
+    pub source: Option<Box<ParsedTree>>,
+}
+
+impl ParsedTree {
+    /// Returns the nodes without the children and without the source (the root causes), in the order of their appearance in the trace.
+    pub fn leaves(&self) -> Vec<&ParsedTree> {
+        if self.children.is_empty() && self.source.is_none() {
+            return vec![self];
+        }
+        self.children
+            .iter()
+            .chain(self.source.as_deref())
+            .flat_map(ParsedTree::leaves)
+            .collect()
+    }
+
+    fn write_strict(&self, f: &mut Formatter<'_>, depth: usize, marker: char) -> core::fmt::Result {
+        let indent = "  ".repeat(depth);
+        let mut lines = self.message.split('\n');
+        write!(f, "{indent}{marker}[{depth}] ")?;
+        write_escaped(lines.next().unwrap_or_default(), f)?;
+        lines.try_for_each(|line| {
+            write!(f, "\n{indent}|[{depth}] ")?;
+            write_escaped(line, f)
+        })?;
+        self.children.iter().try_for_each(|child| {
+            if let Some(label) = &child.label {
+                write!(f, "\n{indent}  @[{child_depth}] ", child_depth = depth + 1)?;
+                write_escaped(label, f)?;
+            }
+            f.write_str("\n")?;
+            child.write_strict(f, depth + 1, '*')
+        })?;
+        match &self.source {
+            Some(source) => {
+                f.write_str("\n")?;
+                source.write_strict(f, depth, '-')
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Escapes the characters that can't be preserved in a line of the trace (they are unescaped by [`parse_error_trace`](crate::parse_error_trace))
+fn write_escaped(text: &str, f: &mut Formatter<'_>) -> core::fmt::Result {
+    text.chars().try_for_each(|char| match char {
+        '\\' => f.write_str("\\\\"),
+        '\r' => f.write_str("\\r"),
+        '\n' => f.write_str("\\n"),
+        _ => f.write_char(char),
+    })
+}
+
+impl Display for ParsedTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_strict(f, 0, '-')
+    }
+}
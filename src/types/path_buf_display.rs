@@ -1,5 +1,31 @@
-use crate::DisplayAsDebug;
+use crate::{DisplayAsDebug, display_path};
+use core::fmt::{Display, Formatter};
 use std::path::PathBuf;
 
-/// A [`PathBuf`] that returns a `Debug` representation in [`Display`](std::fmt::Display) impl.
-pub type PathBufDisplay = DisplayAsDebug<PathBuf>;
+/// A [`PathBuf`] that is displayed via [`display_path`] (in single quotes, without escapes).
+///
+/// Use it for the error fields (e.g. the items of [`ItemError`](crate::ItemError)) that must be displayed as paths.
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Default, Clone, Debug)]
+pub struct PathBufDisplay(
+    /// Inner path
+    pub PathBuf,
+);
+
+impl Display for PathBufDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        display_path(&self.0).fmt(f)
+    }
+}
+
+impl From<PathBuf> for PathBufDisplay {
+    fn from(value: PathBuf) -> Self {
+        Self(value)
+    }
+}
+
+/// Converts the former representation (`PathBufDisplay` used to be an alias of `DisplayAsDebug<PathBuf>`).
+impl From<DisplayAsDebug<PathBuf>> for PathBufDisplay {
+    fn from(value: DisplayAsDebug<PathBuf>) -> Self {
+        Self(value.0)
+    }
+}
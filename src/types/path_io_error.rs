@@ -0,0 +1,42 @@
+use crate::{ErrorPath, ErrorRegistry, display_path};
+use core::fmt::{Display, Formatter};
+use std::error::Error;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An [`io::Error`] together with the path it concerns (the messages of `io::Error` don't include the path).
+///
+/// See also: [`handle_io!`](crate::handle_io)
+#[derive(Debug)]
+pub struct PathIoError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl PathIoError {
+    pub fn new(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl Display for PathIoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "I/O operation failed for {path}", path = display_path(&self.path))
+    }
+}
+
+impl Error for PathIoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_path::<Self>();
+        Some(&self.source)
+    }
+}
+
+impl ErrorPath for PathIoError {
+    fn path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+}
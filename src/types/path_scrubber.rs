@@ -0,0 +1,182 @@
+use crate::ScrubLevel;
+use std::borrow::Cow;
+use std::env;
+use std::sync::{PoisonError, RwLock};
+
+static GLOBAL: RwLock<Option<PathScrubber>> = RwLock::new(None);
+
+const SEPARATORS: [char; 2] = ['/', '\\'];
+
+/// Rewrites the paths in the rendered error traces, so the traces don't leak the username when they are pasted into public issues (see [`set_global_path_scrubber`](crate::set_global_path_scrubber)).
+///
+/// Only the path-looking tokens are rewritten: the tokens that start with `/`, `\`, `~` or a drive letter (e.g. `C:\`). The tokens are delimited by whitespace, quotes, brackets, `,`, `;` and `=`.
+/// Both `/` and `\` are recognized as separators, so the Unix paths, the Windows paths and the escaped Windows paths (in the `Debug` output) are rewritten.
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+pub struct PathScrubber {
+    pub home: Option<String>,
+    pub username: Option<String>,
+    pub level: ScrubLevel,
+}
+
+impl PathScrubber {
+    pub fn new(home: Option<String>, username: Option<String>, level: ScrubLevel) -> Self {
+        Self {
+            home,
+            username,
+            level,
+        }
+    }
+
+    /// Reads the home directory from `HOME` (or `USERPROFILE`) and the username from `USER` (or `USERNAME`).
+    pub fn from_env(level: ScrubLevel) -> Self {
+        let read = |names: [&str; 2]| {
+            names
+                .into_iter()
+                .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+        };
+        Self::new(read(["HOME", "USERPROFILE"]), read(["USER", "USERNAME"]), level)
+    }
+
+    /// Returns the global path scrubber (if it was set).
+    pub fn global() -> Option<Self> {
+        GLOBAL
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sets the global path scrubber (`None` clears it).
+    pub fn store_global(scrubber: Option<Self>) {
+        *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = scrubber;
+    }
+
+    /// Returns the text with the path-looking tokens rewritten according to the level.
+    pub fn scrub<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut output = String::with_capacity(text.len());
+        let mut changed = false;
+        let mut rest = text;
+        while let Some(start) = rest.find(|char: char| !Self::is_delimiter(char)) {
+            let (delimiters, tail) = rest.split_at(start);
+            let end = tail.find(Self::is_delimiter).unwrap_or(tail.len());
+            let (token, tail) = tail.split_at(end);
+            output.push_str(delimiters);
+            match self.scrub_token(token) {
+                Some(scrubbed) => {
+                    output.push_str(&scrubbed);
+                    changed = true;
+                }
+                None => output.push_str(token),
+            }
+            rest = tail;
+        }
+        output.push_str(rest);
+        if changed { Cow::Owned(output) } else { Cow::Borrowed(text) }
+    }
+
+    /// Returns `true` if the character delimits the tokens (so the text can be scrubbed in pieces that end with a delimiter, see [`ScrubWriter`](crate::ScrubWriter)).
+    pub fn is_delimiter(char: char) -> bool {
+        char.is_whitespace() || matches!(char, '\'' | '"' | '`' | '(' | ')' | '[' | ']' | '<' | '>' | '{' | '}' | ',' | ';' | '=')
+    }
+
+    fn scrub_token(&self, token: &str) -> Option<String> {
+        if !is_path_like(token) {
+            return None;
+        }
+        let home_stripped = self
+            .home
+            .as_deref()
+            .and_then(|home| strip_home(token, home))
+            .map(|rest| format!("~{rest}"));
+        let path = home_stripped.as_deref().unwrap_or(token);
+        let user_replaced = match (self.level, self.username.as_deref()) {
+            (ScrubLevel::Strict, Some(username)) => replace_component(path, username),
+            _ => None,
+        };
+        user_replaced.or(home_stripped)
+    }
+}
+
+fn is_path_like(token: &str) -> bool {
+    token.starts_with(SEPARATORS) || token.starts_with('~') || is_drive_prefixed(token)
+}
+
+fn is_drive_prefixed(token: &str) -> bool {
+    let mut chars = token.chars();
+    matches!((chars.next(), chars.next(), chars.next()), (Some(drive), Some(':'), Some('/' | '\\')) if drive.is_ascii_alphabetic())
+}
+
+/// Returns the rest of the token after the home directory (starting with a separator or empty), comparing the components (so `\\` matches `\`)
+fn strip_home<'a>(token: &'a str, home: &str) -> Option<&'a str> {
+    let case_insensitive = is_drive_prefixed(home) || home.contains('\\');
+    if home.starts_with(SEPARATORS) != token.starts_with(SEPARATORS) {
+        return None;
+    }
+    let mut components = home
+        .split(SEPARATORS)
+        .filter(|component| !component.is_empty())
+        .peekable();
+    components.peek()?;
+    let rest = components.try_fold(token, |rest, component| {
+        let rest = rest.trim_start_matches(SEPARATORS);
+        let prefix = rest.get(..component.len())?;
+        let is_equal = if case_insensitive { prefix.eq_ignore_ascii_case(component) } else { prefix == component };
+        is_equal.then(|| &rest[component.len()..])
+    })?;
+    (rest.is_empty() || rest.starts_with(SEPARATORS)).then_some(rest)
+}
+
+fn replace_component(path: &str, username: &str) -> Option<String> {
+    let mut changed = false;
+    let output = path
+        .split_inclusive(SEPARATORS)
+        .map(|segment| {
+            let component = segment.trim_end_matches(SEPARATORS);
+            if component == username {
+                changed = true;
+                format!("<user>{}", &segment[component.len()..])
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect::<String>();
+    changed.then_some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn unix(level: ScrubLevel) -> PathScrubber {
+        PathScrubber::new(Some("/home/alice".to_string()), Some("alice".to_string()), level)
+    }
+
+    fn windows(level: ScrubLevel) -> PathScrubber {
+        PathScrubber::new(Some(r"C:\Users\alice".to_string()), Some("alice".to_string()), level)
+    }
+
+    #[test]
+    fn must_replace_home_prefix() {
+        let trace = "- failed to read '/home/alice/.config/app.toml'\n- file not found (path=/home/alice)";
+        assert_eq!(unix(ScrubLevel::Home).scrub(trace), "- failed to read '~/.config/app.toml'\n- file not found (path=~)");
+        let trace = r#"- failed to open C:\Users\alice\AppData\app.toml: denied
+PathIoError { path: "c:\\users\\alice\\AppData\\app.toml" }"#;
+        let expected = r#"- failed to open ~\AppData\app.toml: denied
+PathIoError { path: "~\\AppData\\app.toml" }"#;
+        assert_eq!(windows(ScrubLevel::Home).scrub(trace), expected);
+    }
+
+    #[test]
+    fn must_not_replace_similar_paths_and_ordinary_words() {
+        let trace = "- alice's project at /home/alicia/app and /srv/home/alice failed";
+        assert!(matches!(unix(ScrubLevel::Home).scrub(trace), Cow::Borrowed(_)));
+        assert!(matches!(windows(ScrubLevel::Home).scrub("alice wrote D:\\alice"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn must_replace_username_components_in_strict_level() {
+        let trace = "- alice's mail at /var/mail/alice and /home/alice/alice.txt";
+        assert_eq!(unix(ScrubLevel::Strict).scrub(trace), "- alice's mail at /var/mail/<user> and ~/alice.txt");
+        assert_eq!(windows(ScrubLevel::Strict).scrub(r"see D:\backup\alice\db"), r"see D:\backup\<user>\db");
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt::{Display, Formatter};
+
+/// Displays the count together with the noun in the correct grammatical number (e.g. "1 error", "2 errors", "0 errors").
+///
+/// The plural form is constructed by appending "s" to the noun, which is enough for the nouns in the messages of this crate.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Plural<'a> {
+    pub count: usize,
+    pub noun: &'a str,
+}
+
+impl<'a> Plural<'a> {
+    pub fn new(count: usize, noun: &'a str) -> Self {
+        Self {
+            count,
+            noun,
+        }
+    }
+}
+
+impl Display for Plural<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let suffix = if self.count == 1 { "" } else { "s" };
+        write!(f, "{count} {noun}{suffix}", count = self.count, noun = self.noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Plural;
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_pluralize() {
+        assert_eq!(Plural::new(0, "error").to_string(), "0 errors");
+        assert_eq!(Plural::new(1, "error").to_string(), "1 error");
+        assert_eq!(Plural::new(2, "error").to_string(), "2 errors");
+    }
+}
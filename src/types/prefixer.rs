@@ -0,0 +1,80 @@
+use std::io;
+use std::io::Write;
+
+/// An [`io::Write`](Write) adapter that writes the prefix before every line (the byte-oriented counterpart of [`FmtPrefixer`](crate::FmtPrefixer) with the same line-prefixing logic).
+///
+/// This is useful for indenting the output of the functions that write to an `io::Write` (e.g. [`writeln_error_to_writer`](crate::writeln_error_to_writer)).
+pub struct Prefixer<'w> {
+    writer: &'w mut dyn Write,
+    prefix: &'w [u8],
+    is_line_start: bool,
+}
+
+impl<'w> Prefixer<'w> {
+    /// Prefixes every line, including the first one.
+    pub fn new(writer: &'w mut dyn Write, prefix: &'w str) -> Self {
+        Self {
+            writer,
+            prefix: prefix.as_bytes(),
+            is_line_start: true,
+        }
+    }
+
+    /// Prefixes every line except the first one (useful for indenting the continuation lines of a bullet).
+    pub fn continuation(writer: &'w mut dyn Write, prefix: &'w str) -> Self {
+        Self {
+            writer,
+            prefix: prefix.as_bytes(),
+            is_line_start: false,
+        }
+    }
+}
+
+impl<'w> Write for Prefixer<'w> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        buf.split_inclusive(|&byte| byte == b'\n')
+            .try_for_each(|line| {
+                if self.is_line_start {
+                    self.writer.write_all(self.prefix)?;
+                }
+                self.is_line_start = line.ends_with(b"\n");
+                self.writer.write_all(line)
+            })?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FmtPrefixer, Prefixer};
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[test]
+    fn must_prefix_like_fmt_prefixer() {
+        ["", "\n", "\n\n", "a", "a\n", "a\nb", "a\n\n\nb\n"]
+            .into_iter()
+            .for_each(|input| {
+                let mut expected = String::new();
+                std::fmt::Write::write_str(&mut FmtPrefixer::new(&mut expected, "> "), input).unwrap();
+                let mut actual = Vec::new();
+                Prefixer::new(&mut actual, "> ")
+                    .write_all(input.as_bytes())
+                    .unwrap();
+                assert_eq!(String::from_utf8(actual).unwrap(), expected, "input: {input:?}");
+            });
+    }
+
+    #[test]
+    fn must_prefix_continuation_lines() {
+        let mut output = Vec::new();
+        let mut prefixer = Prefixer::continuation(&mut output, "  ");
+        write!(prefixer, "- a\n- b").unwrap();
+        writeln!(prefixer).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "- a\n  - b\n");
+    }
+}
@@ -0,0 +1,27 @@
+use crate::PathBufDisplay;
+use core::fmt::{Display, Formatter};
+
+/// The origin of a configuration value.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Provenance {
+    /// The value is the default value.
+    Default,
+    /// The value was read from the file.
+    File(PathBufDisplay),
+    /// The value was read from the environment variable.
+    EnvVar(String),
+    /// The value was passed as a command-line flag.
+    CliFlag(String),
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use Provenance::*;
+        match self {
+            Default => f.write_str("default"),
+            File(path) => write!(f, "file {path}"),
+            EnvVar(name) => write!(f, "env var {name}"),
+            CliFlag(flag) => write!(f, "CLI flag {flag}"),
+        }
+    }
+}
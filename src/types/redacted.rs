@@ -0,0 +1,56 @@
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
+
+/// The text that is displayed instead of the [`Redacted`] value.
+pub const REDACTED: &str = "[redacted]";
+
+/// A secret value that is never displayed, debugged or serialized ([`REDACTED`] is written instead).
+///
+/// Combine it with [`Sourced`](crate::Sourced) to report where the secret came from without leaking it (e.g. `invalid token: [redacted] (from env var APP_TOKEN)`).
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Default, Copy, Clone)]
+pub struct Redacted<T>(
+    /// Inner secret value
+    pub T,
+);
+
+impl<T> Debug for Redacted<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> Display for Redacted<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> Deref for Redacted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Redacted<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// Serializes [`REDACTED`] instead of the value (the serialized errors are usually logged or sent to the clients).
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Redacted<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+/// Deserializes the value itself (so the secrets can be read from the config files).
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Redacted<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
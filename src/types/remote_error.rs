@@ -0,0 +1,158 @@
+use crate::{ErrorChild, ErrorChildren, ErrorRegistry, ParseTraceError, ParsedTree, parse_error_trace, write_error_children_to_formatter};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// An owned error tree that was reconstructed from the trace of another process (see [`parse_error_trace`]).
+///
+/// This is useful for the parent process that runs a subprocess built with this crate: the parsed trace can be stored in the `source` field of a normal variant, and the combined tree is rendered as if the remote errors were local.
+///
+/// The `source()` follows the chain of the remote error, while the children of the remote aggregates are exposed via [`ErrorChildren`] (like in [`ErrVec`](crate::ErrVec)).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct RemoteError {
+    pub message: String,
+    pub label: Option<String>,
+    pub children: Vec<RemoteError>,
+    pub source: Option<Box<RemoteError>>,
+}
+
+impl RemoteError {
+    /// Parses the trace in the strict format v1 (as written by [`writeln_error_strict`](crate::writeln_error_strict)).
+    pub fn from_trace(text: &str) -> Result<Self, ParseTraceError> {
+        parse_error_trace(text).map(Self::from)
+    }
+}
+
+impl From<ParsedTree> for RemoteError {
+    fn from(tree: ParsedTree) -> Self {
+        Self {
+            message: tree.message,
+            label: tree.label,
+            children: tree.children.into_iter().map(Self::from).collect(),
+            source: tree.source.map(|source| Box::new(Self::from(*source))),
+        }
+    }
+}
+
+impl Display for RemoteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl Error for RemoteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl ErrorChildren for RemoteError {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        Box::new(self.children.iter().map(|child| match &child.label {
+            Some(label) => ErrorChild::labeled(label.clone(), child),
+            None => ErrorChild::new(child),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorChild, ErrorChildren, ErrorRegistry, RemoteError, error_parsed_tree, write_error_children_to_formatter, writeln_error_strict, writeln_error_to_writer};
+    use core::fmt::{Display, Formatter};
+    use pretty_assertions::assert_eq;
+    use std::error::Error;
+    use thiserror::Error;
+
+    fn render(error: &(dyn Error + 'static)) -> String {
+        let mut output = Vec::new();
+        writeln_error_to_writer(error, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn must_nest_remote_tree_under_parent() {
+        let child = MigrateError::MigrateFailed {
+            source: PerTableErrors(vec![
+                ("users", TableError::LockTimeout),
+                (
+                    "orders",
+                    TableError::RowsInvalid {
+                        source: ErrVec::new([RowError::ColumnMissing, RowError::ColumnMissing]),
+                    },
+                ),
+            ]),
+        };
+        let mut trace = Vec::new();
+        writeln_error_strict(&child, &mut trace).unwrap();
+        let remote = RemoteError::from_trace(&String::from_utf8(trace).unwrap()).unwrap();
+        let parent = DeployError::MigrationCommandFailed {
+            source: remote,
+        };
+        assert_eq!(render(&parent), format!("- migration command failed\n{}", render(&child)));
+        assert_eq!(error_parsed_tree(&parent).source.map(|source| *source), Some(error_parsed_tree(&child)));
+    }
+
+    #[derive(Error, Debug)]
+    enum DeployError {
+        #[error("migration command failed")]
+        MigrationCommandFailed { source: RemoteError },
+    }
+
+    #[derive(Error, Debug)]
+    enum MigrateError {
+        #[error("failed to migrate the database")]
+        MigrateFailed { source: PerTableErrors },
+    }
+
+    #[derive(Debug)]
+    struct PerTableErrors(Vec<(&'static str, TableError)>);
+
+    impl ErrorChildren for PerTableErrors {
+        fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            write!(f, "encountered errors in {len} tables", len = self.0.len())
+        }
+
+        fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+            Box::new(
+                self.0
+                    .iter()
+                    .map(|(table, error)| ErrorChild::labeled(format!("table {table}"), error as &(dyn Error + 'static))),
+            )
+        }
+    }
+
+    impl Display for PerTableErrors {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            self.fmt_message(f)?;
+            write_error_children_to_formatter(self, f)
+        }
+    }
+
+    impl Error for PerTableErrors {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            ErrorRegistry::register_children::<Self>();
+            None
+        }
+    }
+
+    #[derive(Error, Debug)]
+    enum TableError {
+        #[error("lock timeout")]
+        LockTimeout,
+        #[error("found invalid rows")]
+        RowsInvalid { source: ErrVec<RowError> },
+    }
+
+    #[derive(Error, Debug)]
+    enum RowError {
+        #[error("column missing")]
+        ColumnMissing,
+    }
+}
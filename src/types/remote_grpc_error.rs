@@ -0,0 +1,223 @@
+use crate::RemoteError;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use tonic::Code;
+
+/// An error that was returned by a gRPC call (see [`from_status`](crate::from_status)).
+///
+/// If the remote tree is available, it's the source of this error, so the trace shows the server-side errors under the call.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct RemoteGrpcError {
+    pub code: Code,
+    /// The message of the status
+    pub message: String,
+    /// The error tree of the server (if it was attached by [`to_status`](crate::to_status))
+    pub remote: Option<RemoteError>,
+}
+
+impl Display for RemoteGrpcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "gRPC call failed with status {:?}", self.code)?;
+        match &self.remote {
+            None if !self.message.is_empty() => write!(f, ": {}", self.message),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Error for RemoteGrpcError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.remote
+            .as_ref()
+            .map(|remote| remote as &(dyn Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer, GRPC_DETAILS_MAX_LEN, RemoteGrpcError, from_status, impl_grpc_status, to_status};
+    use core::convert::Infallible;
+    use core::error::Error;
+    use core::future::{Ready, ready};
+    use core::task::{Context, Poll};
+    use http::Uri;
+    use http::uri::PathAndQuery;
+    use hyper_util::rt::TokioIo;
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+    use tonic::body::Body;
+    use tonic::client::Grpc;
+    use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+    use tonic::server::NamedService;
+    use tonic::transport::{Endpoint, Server};
+    use tonic::{Code, Request, Status};
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    #[derive(Error, Debug)]
+    enum GetUserError {
+        #[error("user not found: '{username}'")]
+        UserNotFound { username: String },
+        #[error("failed to load {count} user profiles", count = source.len())]
+        ProfilesLoadFailed { source: ErrVec<LoadProfileError> },
+    }
+
+    impl_grpc_status!(GetUserError, {
+        UserNotFound => Code::NotFound,
+    });
+
+    #[derive(Error, Debug)]
+    enum LoadProfileError {
+        #[error("failed to query profile {id}")]
+        QueryFailed { source: std::io::Error, id: u32 },
+    }
+
+    #[derive(Error, Debug)]
+    enum ShowUserError {
+        #[error("failed to call GetUser")]
+        GetUserCallFailed { source: RemoteGrpcError },
+    }
+
+    fn profiles_load_failed() -> GetUserError {
+        GetUserError::ProfilesLoadFailed {
+            source: ErrVec::new([1, 2].map(|id| LoadProfileError::QueryFailed {
+                source: std::io::Error::other("connection reset"),
+                id,
+            })),
+        }
+    }
+
+    /// The `users.Users` service that fails every call with the status that is returned by the function
+    #[derive(Clone)]
+    struct FailingService(fn() -> Status);
+
+    impl NamedService for FailingService {
+        const NAME: &'static str = "users.Users";
+    }
+
+    impl tower::Service<http::Request<Body>> for FailingService {
+        type Response = http::Response<Body>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<Body>) -> Self::Future {
+            ready(Ok((self.0)().into_http()))
+        }
+    }
+
+    /// The codec for the empty messages (the service never reads the request and never sends a response)
+    #[derive(Default)]
+    struct UnitCodec;
+
+    impl Codec for UnitCodec {
+        type Encode = ();
+        type Decode = ();
+        type Encoder = UnitCodec;
+        type Decoder = UnitCodec;
+
+        fn encoder(&mut self) -> Self::Encoder {
+            UnitCodec
+        }
+
+        fn decoder(&mut self) -> Self::Decoder {
+            UnitCodec
+        }
+    }
+
+    impl Encoder for UnitCodec {
+        type Item = ();
+        type Error = Status;
+
+        fn encode(&mut self, _item: Self::Item, _dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl Decoder for UnitCodec {
+        type Item = ();
+        type Error = Status;
+
+        fn decode(&mut self, _src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+            Ok(Some(()))
+        }
+    }
+
+    /// Calls `/users.Users/GetUser` on an in-process tonic server (connected via [`tokio::io::duplex`]) and returns the status that the client received
+    async fn call_get_user(status: fn() -> Status) -> Result<Status, Box<dyn Error>> {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = Server::builder()
+            .add_service(FailingService(status))
+            .serve_with_incoming(futures::stream::iter([Ok::<_, std::io::Error>(server_io)]));
+        tokio::spawn(server);
+        let mut client_io = Some(client_io);
+        let channel = Endpoint::try_from("http://[::]:50051")?
+            .connect_with_connector(tower::service_fn(move |_: Uri| {
+                let io = client_io.take().map(TokioIo::new);
+                async move { io.ok_or_else(|| std::io::Error::other("the duplex stream is already connected")) }
+            }))
+            .await?;
+        let mut client = Grpc::new(channel);
+        client.ready().await?;
+        match client
+            .unary(Request::new(()), PathAndQuery::from_static("/users.Users/GetUser"), UnitCodec)
+            .await
+        {
+            Ok(_) => Err("the call must fail".into()),
+            Err(status) => Ok(status),
+        }
+    }
+
+    #[tokio::test]
+    async fn must_round_trip_nested_error_through_server() -> TestResult {
+        let status = call_get_user(|| to_status(&profiles_load_failed())).await?;
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(status.message(), "failed to load 2 user profiles");
+        let error = ShowUserError::GetUserCallFailed {
+            source: from_status(&status),
+        };
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- failed to call GetUser\n- gRPC call failed with status Internal\n- failed to load 2 user profiles\n- encountered 2 errors\n  * - failed to query profile 1\n    - connection reset\n  * - failed to query profile 2\n    - connection reset");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn must_map_variants_to_codes() -> TestResult {
+        let status = call_get_user(|| {
+            to_status(&GetUserError::UserNotFound {
+                username: "alice".to_string(),
+            })
+        })
+        .await?;
+        assert_eq!(status.code(), Code::NotFound);
+        let remote = from_status(&status)
+            .remote
+            .ok_or("the remote tree must be recovered")?;
+        assert_eq!(remote.message, "user not found: 'alice'");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn must_fall_back_to_message_without_details() -> TestResult {
+        let error = from_status(&call_get_user(|| Status::not_found("user not found")).await?);
+        assert_eq!(error.remote, None);
+        assert_eq!(ErrorDisplayer(&error).to_string(), "- gRPC call failed with status NotFound: user not found");
+        let status = call_get_user(|| {
+            to_status(&GetUserError::UserNotFound {
+                username: "a".repeat(GRPC_DETAILS_MAX_LEN),
+            })
+        })
+        .await?;
+        assert!(status.details().is_empty());
+        assert_eq!(from_status(&status).remote, None);
+        Ok(())
+    }
+
+    #[test]
+    fn must_fall_back_to_message_with_foreign_details() {
+        let status = Status::with_details(Code::Unavailable, "try again later", b"\x08\x0e".as_slice().into());
+        assert_eq!(from_status(&status).remote, None);
+    }
+}
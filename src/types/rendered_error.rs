@@ -0,0 +1,207 @@
+use crate::{AsDynError, CanonicalKey, DebugDump, ErrorStyle, FmtPrefixer, ParsedTree, TreeShape, Verbosity, canonical_key_of_tree, error_parsed_tree_and_shape, writeln_concise_tree};
+use core::fmt::{Display, Formatter, Write};
+use std::io;
+
+/// An error that is rendered once: the messages and the names of every node of the tree, the key, and a handle to the Debug output.
+///
+/// Render the error once per failure and pass the result to every consumer (the trace, the full report, the Markdown) instead of the live error.
+/// The tree is traversed a single time, and every consumer receives the same text, even if a `Display` impl reads ambient state.
+///
+/// The Debug output is not rendered upfront: [`debug`](RenderedError::debug) is a handle that writes it on demand (only the full report and the Markdown need it).
+///
+/// The `Display` impl writes the error trace in the same format as [`ErrorDisplayer`](crate::ErrorDisplayer).
+#[derive(Clone, Debug)]
+pub struct RenderedError<'a> {
+    /// The messages of the errors
+    pub tree: ParsedTree,
+    /// The names of the errors (see [`get_debug_name`](crate::get_debug_name))
+    pub shape: TreeShape,
+    /// The stable key of the error (see [`canonical_key`](crate::canonical_key))
+    pub key: CanonicalKey,
+    /// The pretty-printed Debug output of the error (the full error report)
+    pub debug: DebugDump<'a>,
+}
+
+impl<'a> RenderedError<'a> {
+    pub fn new<E: AsDynError + ?Sized>(error: &'a E) -> Self {
+        let error = error.as_dyn();
+        let (tree, shape) = error_parsed_tree_and_shape(error);
+        let key = canonical_key_of_tree(&tree);
+        Self {
+            tree,
+            shape,
+            key,
+            debug: DebugDump(error),
+        }
+    }
+
+    /// Returns the first line of the message of the top error.
+    pub fn title(&self) -> &str {
+        self.tree.message.lines().next().unwrap_or_default()
+    }
+
+    /// Writes the error trace according to the verbosity (see [`Verbosity`]).
+    pub fn writeln(&self, writer: &mut dyn io::Write, verbosity: Verbosity) -> io::Result<()> {
+        match verbosity {
+            Verbosity::Concise => writeln_concise_tree(&self.tree, writer),
+            Verbosity::Normal => writeln!(writer, "{self}"),
+            Verbosity::Detailed => {
+                let mut trace = String::new();
+                // writing to a String never fails
+                let _ = write_tree_with_names(&self.tree, &self.shape, &mut trace);
+                writeln!(writer, "{trace}")
+            }
+        }
+    }
+}
+
+impl Display for RenderedError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        ErrorStyle::default().write_tree(&self.tree, f)
+    }
+}
+
+/// Writes the chain in the same format as [`write_error_chain_with_names_to_fmt`](crate::write_error_chain_with_names_to_fmt).
+fn write_tree_with_names(tree: &ParsedTree, shape: &TreeShape, writer: &mut dyn Write) -> core::fmt::Result {
+    let style = ErrorStyle::default();
+    write!(writer, "{}[{}] ", style.chain_bullet, shape.name)?;
+    {
+        let mut prefixer = FmtPrefixer::continuation(writer, style.indent);
+        prefixer.write_str(&tree.message)?;
+        tree.children
+            .iter()
+            .zip(&shape.children)
+            .try_for_each(|(child, child_shape)| {
+                write!(prefixer, "\n{}", style.child_bullet)?;
+                if let Some(label) = &child.label {
+                    write!(prefixer, "[{label}] ")?;
+                }
+                write_tree_with_names(child, child_shape, &mut FmtPrefixer::continuation(&mut prefixer, style.indent))
+            })?;
+    }
+    match (&tree.source, &shape.source) {
+        (Some(source), Some(source_shape)) => {
+            writer.write_char('\n')?;
+            write_tree_with_names(source, source_shape, writer)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer, RenderedError, Verbosity, render_rendered_error_markdown, writeln_error_concise, writeln_error_with_types};
+    use core::cell::Cell;
+    use core::fmt::{Display, Formatter};
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_render_the_same_trace_as_the_live_error() {
+        let error = SyncAllFailed {
+            source: ErrVec::new([
+                SyncFailed {
+                    source: StatefulError::default(),
+                    repo: "errgonomic".to_string(),
+                },
+                RepoNotFound,
+            ]),
+        };
+        let rendered = RenderedError::new(&error);
+        let render = |write: fn(&SyncAllFailed, &mut Vec<u8>) -> std::io::Result<()>| {
+            let mut output = Vec::new();
+            write(&error, &mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        let render_rendered = |verbosity| {
+            let mut output = Vec::new();
+            rendered.writeln(&mut output, verbosity).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        // every render of the live error increments the counter, so the renders are compared after replacing it
+        let normalize = |trace: String| trace.replace(|char: char| char.is_ascii_digit(), "N");
+        assert_eq!(normalize(render_rendered(Verbosity::Normal)), normalize(format!("{}\n", ErrorDisplayer(&error))));
+        assert_eq!(normalize(render_rendered(Verbosity::Concise)), normalize(render(|error, writer| writeln_error_concise(error, writer))));
+        assert_eq!(normalize(render_rendered(Verbosity::Detailed)), normalize(render(|error, writer| writeln_error_with_types(error, writer))));
+        assert_eq!(rendered.title(), "failed to sync 2 repos");
+    }
+
+    #[test]
+    fn must_call_display_once() {
+        let error = SyncFailed {
+            source: StatefulError::default(),
+            repo: "errgonomic".to_string(),
+        };
+        let rendered = RenderedError::new(&error);
+        let mut trace = Vec::new();
+        rendered.writeln(&mut trace, Verbosity::Normal).unwrap();
+        let markdown = render_rendered_error_markdown(&rendered, None);
+        let SyncFailed {
+            source,
+            ..
+        } = &error
+        else {
+            unreachable!()
+        };
+        assert_eq!(source.renders.get(), 1);
+        assert_eq!(String::from_utf8(trace).unwrap(), "- failed to sync repo errgonomic\n- rendered 1 times\n");
+        assert!(markdown.contains("- rendered 1 times\n"));
+        let error = SyncAllFailed {
+            source: ErrVec::new([SyncFailed {
+                source: StatefulError::default(),
+                repo: "errgonomic".to_string(),
+            }]),
+        };
+        let rendered = RenderedError::new(&error);
+        let mut trace = Vec::new();
+        rendered.writeln(&mut trace, Verbosity::Normal).unwrap();
+        let [
+            SyncFailed {
+                source,
+                ..
+            },
+        ] = error.source.as_slice()
+        else {
+            unreachable!()
+        };
+        assert_eq!(source.renders.get(), 1);
+        assert!(
+            String::from_utf8(trace)
+                .unwrap()
+                .contains("- rendered 1 times\n")
+        );
+        // the debug dump is written on demand, and it includes the chains of the children of `ErrVec`
+        let markdown = render_rendered_error_markdown(&rendered, None);
+        assert!(markdown.contains("- rendered 1 times\n"));
+        assert!(markdown.contains("chain"), "{markdown}");
+    }
+
+    #[derive(Error, Debug)]
+    #[error("failed to sync {count} repos", count = source.len())]
+    struct SyncAllFailed {
+        source: ErrVec<SyncAllError>,
+    }
+
+    #[derive(Error, Debug)]
+    enum SyncAllError {
+        #[error("failed to sync repo {repo}")]
+        SyncFailed { source: StatefulError, repo: String },
+        #[error("repo not found")]
+        RepoNotFound,
+    }
+
+    use SyncAllError::*;
+
+    /// An error that reads ambient state in its `Display` impl
+    #[derive(Error, Default, Debug)]
+    struct StatefulError {
+        renders: Cell<usize>,
+    }
+
+    impl Display for StatefulError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            self.renders.set(self.renders.get() + 1);
+            write!(f, "rendered {} times", self.renders.get())
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt::{Display, Formatter};
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<RequestId>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    static TASK_CURRENT: RequestId;
+}
+
+/// An identifier of the current request, which is attached to the errors for log correlation.
+///
+/// Set the ambient request id with [`RequestId::scope`] (or [`with_request_id`](crate::with_request_id) with the `tokio` feature), so that [`handle_id!`](crate::handle_id) can capture it without threading it through every function.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Debug)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Calls `f` with `id` set as the ambient request id of the current thread (the previous ambient request id is restored afterwards, even if `f` panics).
+    pub fn scope<R>(id: Self, f: impl FnOnce() -> R) -> R {
+        let _guard = RestoreOnDrop(CURRENT.replace(Some(id)));
+        f()
+    }
+
+    /// Polls `future` with `id` set as the ambient request id of the current task.
+    #[cfg(feature = "tokio")]
+    pub async fn scope_async<F: Future>(id: Self, future: F) -> F::Output {
+        TASK_CURRENT.scope(id, future).await
+    }
+
+    /// Returns the innermost ambient request id.
+    pub fn current() -> Option<Self> {
+        let current = CURRENT.with_borrow(Clone::clone);
+        #[cfg(feature = "tokio")]
+        let current = current.or_else(|| TASK_CURRENT.try_with(Clone::clone).ok());
+        current
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl Display for RequestId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+struct RestoreOnDrop(Option<RequestId>);
+
+impl Drop for RestoreOnDrop {
+    fn drop(&mut self) {
+        CURRENT.set(self.0.take());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RequestId;
+
+    #[test]
+    fn must_restore_previous_request_id() {
+        let outer = RequestId::new("outer");
+        let inner = RequestId::new("inner");
+        assert_eq!(RequestId::current(), None);
+        RequestId::scope(outer.clone(), || {
+            RequestId::scope(inner.clone(), || assert_eq!(RequestId::current(), Some(inner)));
+            assert_eq!(RequestId::current(), Some(outer));
+        });
+        assert_eq!(RequestId::current(), None);
+    }
+}
@@ -0,0 +1,37 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// An error together with the state that allows the caller to resume the operation from the point of failure (e.g. a checkpoint with the index of the first unprocessed item).
+///
+/// The `Display` and [`source`](Error::source) impls delegate to the inner error, so the trace is not changed.
+/// Use [`handle_resumable!`](crate::handle_resumable) to construct it and [`find_resume_state`](crate::find_resume_state) to extract the state from the error chain.
+#[derive(Clone, Debug)]
+pub struct Resumable<E, S> {
+    pub source: E,
+    pub resume_state: S,
+}
+
+impl<E, S> Resumable<E, S> {
+    pub fn new(source: E, resume_state: S) -> Self {
+        Self {
+            source,
+            resume_state,
+        }
+    }
+
+    pub fn into_parts(self) -> (E, S) {
+        (self.source, self.resume_state)
+    }
+}
+
+impl<E: Display, S> Display for Resumable<E, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl<E: Error, S: core::fmt::Debug> Error for Resumable<E, S> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.source()
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{ErrorRegistry, RetryDelay};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// An error together with the delay that the server has requested before the next attempt (e.g. from the `Retry-After` header of a 429 or 503 response).
+///
+/// The `Display` and [`source`](Error::source) impls delegate to the inner error, so the trace is not changed.
+/// Use [`extract_retry_after`](crate::extract_retry_after) to find the delay in the error tree.
+#[derive(Clone, Debug)]
+pub struct RetryAfter<E> {
+    pub source: E,
+    pub delay: RetryDelay,
+}
+
+impl<E> RetryAfter<E> {
+    pub fn new(source: E, delay: RetryDelay) -> Self {
+        Self {
+            source,
+            delay,
+        }
+    }
+}
+
+impl<E: Display> Display for RetryAfter<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl<E: Error + 'static> Error for RetryAfter<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_retry_after::<E>();
+        self.source.source()
+    }
+}
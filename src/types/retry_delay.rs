@@ -0,0 +1,119 @@
+use core::fmt::{Display, Formatter};
+use std::time::{Duration, SystemTime};
+
+/// The delay before the next attempt, as sent by the server in the `Retry-After` header (see [`RetryAfter`](crate::RetryAfter)).
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum RetryDelay {
+    /// Retry after the duration (the `Retry-After: 120` form)
+    After(Duration),
+    /// Retry at the time (the `Retry-After: Wed, 21 Oct 2015 07:28:00 GMT` form)
+    At(SystemTime),
+}
+
+impl RetryDelay {
+    /// Parses the value of the `Retry-After` header: either the number of seconds or the HTTP date in the IMF-fixdate format.
+    pub fn from_header_value(value: &str) -> Option<Self> {
+        let value = value.trim();
+        match value.parse::<u64>() {
+            Ok(seconds) => Some(Self::After(Duration::from_secs(seconds))),
+            Err(_) => parse_http_date(value).map(Self::At),
+        }
+    }
+
+    /// Parses the `Retry-After` header if the status is `429 Too Many Requests` or `503 Service Unavailable`.
+    #[cfg(feature = "http")]
+    pub fn from_http_response(status: http::StatusCode, headers: &http::HeaderMap) -> Option<Self> {
+        if status != http::StatusCode::TOO_MANY_REQUESTS && status != http::StatusCode::SERVICE_UNAVAILABLE {
+            return None;
+        }
+        let value = headers.get(http::header::RETRY_AFTER)?.to_str().ok()?;
+        Self::from_header_value(value)
+    }
+
+    /// Returns the remaining delay at `now` (zero if the time has already passed).
+    pub fn remaining_at(&self, now: SystemTime) -> Duration {
+        match self {
+            Self::After(duration) => *duration,
+            Self::At(time) => time.duration_since(now).unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for RetryDelay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::After(duration) => write!(f, "retry after {}s", duration.as_secs()),
+            Self::At(time) => match time.duration_since(SystemTime::UNIX_EPOCH) {
+                Ok(since_epoch) => write!(f, "retry at {}s since the Unix epoch", since_epoch.as_secs()),
+                Err(_) => f.write_str("retry now"),
+            },
+        }
+    }
+}
+
+/// Parses the IMF-fixdate (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`), which is the preferred format of the HTTP dates
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let [_weekday, day, month, year, time, "GMT"] = value.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let day = day.parse::<u32>().ok()?;
+    let month = MONTHS.iter().position(|name| *name == month)? as u32 + 1;
+    let year = year.parse::<i64>().ok()?;
+    let [hours, minutes, seconds] = time
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?[..]
+    else {
+        return None;
+    };
+    if !(1..=31).contains(&day) || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let seconds = days * 86_400 + hours * 3_600 + minutes * 60 + seconds;
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+}
+
+/// Returns the number of days since 1970-01-01 (the algorithm from <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_parse_seconds() {
+        assert_eq!(RetryDelay::from_header_value(" 120 "), Some(RetryDelay::After(Duration::from_secs(120))));
+        assert_eq!(RetryDelay::from_header_value("-1"), None);
+    }
+
+    #[test]
+    fn must_parse_http_date() {
+        let delay = RetryDelay::from_header_value("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(delay, RetryDelay::At(SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777)));
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_700);
+        assert_eq!(delay.remaining_at(now), Duration::from_secs(77));
+        assert_eq!(delay.remaining_at(now + Duration::from_secs(600)), Duration::ZERO);
+        assert_eq!(RetryDelay::from_header_value("Sun, 06 Foo 1994 08:49:37 GMT"), None);
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn must_parse_only_rate_limit_responses() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::RETRY_AFTER, http::HeaderValue::from_static("30"));
+        assert_eq!(RetryDelay::from_http_response(http::StatusCode::TOO_MANY_REQUESTS, &headers), Some(RetryDelay::After(Duration::from_secs(30))));
+        assert_eq!(RetryDelay::from_http_response(http::StatusCode::BAD_GATEWAY, &headers), None);
+    }
+}
@@ -0,0 +1,9 @@
+/// How aggressively the [`PathScrubber`](crate::PathScrubber) rewrites the paths.
+#[derive(Default, Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum ScrubLevel {
+    /// Replace the home directory prefix with `~`
+    #[default]
+    Home,
+    /// Also replace every path component that is equal to the username with `<user>` (e.g. in `/var/mail/alice`)
+    Strict,
+}
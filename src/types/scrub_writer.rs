@@ -0,0 +1,70 @@
+use crate::PathScrubber;
+use core::fmt::Write;
+
+/// A [`fmt::Write`](Write) adapter that scrubs the paths in the text that is written in pieces (see [`PathScrubber::scrub`]).
+///
+/// The text is passed on up to the last delimiter, and the unfinished token is kept until the next delimiter, so the output is the same as if the whole text was scrubbed at once.
+/// Call [`finish`](ScrubWriter::finish) to write the last token.
+pub struct ScrubWriter<'w> {
+    scrubber: &'w PathScrubber,
+    writer: &'w mut dyn Write,
+    token: String,
+}
+
+impl<'w> ScrubWriter<'w> {
+    pub fn new(scrubber: &'w PathScrubber, writer: &'w mut dyn Write) -> Self {
+        Self {
+            scrubber,
+            writer,
+            token: String::new(),
+        }
+    }
+
+    /// Writes the last token.
+    pub fn finish(mut self) -> core::fmt::Result {
+        let token = core::mem::take(&mut self.token);
+        self.writer.write_str(&self.scrubber.scrub(&token))
+    }
+}
+
+impl Write for ScrubWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let Some((index, delimiter)) = s
+            .char_indices()
+            .rfind(|&(_, char)| PathScrubber::is_delimiter(char))
+        else {
+            self.token.push_str(s);
+            return Ok(());
+        };
+        let (text, token) = s.split_at(index + delimiter.len_utf8());
+        self.token.push_str(text);
+        self.writer.write_str(&self.scrubber.scrub(&self.token))?;
+        self.token.clear();
+        self.token.push_str(token);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PathScrubber, ScrubLevel, ScrubWriter};
+    use core::fmt::Write;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn must_scrub_in_pieces_like_at_once() {
+        let scrubber = PathScrubber::new(Some("/home/alice".to_string()), Some("alice".to_string()), ScrubLevel::Strict);
+        let text = "PathIoError { path: \"/home/alice/app/config.toml\", source: Os { code: 2 } } at /srv/alice/cache";
+        (1..text.len()).for_each(|piece_len| {
+            let mut output = String::new();
+            let mut writer = ScrubWriter::new(&scrubber, &mut output);
+            text.as_bytes()
+                .chunks(piece_len)
+                .map(|piece| core::str::from_utf8(piece).expect("always succeeds because the text is ASCII"))
+                .try_for_each(|piece| writer.write_str(piece))
+                .and_then(|()| writer.finish())
+                .expect("always succeeds because writing to a String never fails");
+            assert_eq!(output, scrubber.scrub(text), "{piece_len}");
+        });
+    }
+}
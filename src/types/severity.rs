@@ -0,0 +1,28 @@
+use core::fmt::{Display, Formatter};
+
+/// The severity of an error (see [`WithSeverity`](crate::WithSeverity)).
+///
+/// The variants are ordered from the least to the most severe, so the most severe error in a collection can be found with `max`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+pub enum Severity {
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    /// Returns the marker that is written before the message (e.g. `[WARN]`).
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Severity::Warning => "[WARN]",
+            Severity::Error => "[ERROR]",
+            Severity::Fatal => "[FATAL]",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.marker())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Provenance;
+use core::fmt::{Display, Formatter};
+use core::ops::Deref;
+
+/// A value together with its [`Provenance`].
+///
+/// Use it as a type of an error variant field, so that the error message reports where the value came from (e.g. `invalid timeout: abc (from env var APP_TIMEOUT)`).
+/// Wrap the secrets in [`Redacted`](crate::Redacted) to report only the provenance (e.g. `invalid token: [redacted] (from env var APP_TOKEN)`).
+/// See also: [`handle_sourced_parse!`](crate::handle_sourced_parse)
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Sourced<T> {
+    pub value: T,
+    pub provenance: Provenance,
+}
+
+impl<T> Sourced<T> {
+    pub fn new(value: T, provenance: Provenance) -> Self {
+        Self {
+            value,
+            provenance,
+        }
+    }
+}
+
+impl<T: Display> Display for Sourced<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} (from {})", self.value, self.provenance)
+    }
+}
+
+impl<T> Deref for Sourced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Serializes only the value (the provenance is a runtime detail).
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Sourced<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PathBufDisplay, Provenance, Redacted, Sourced};
+    use Provenance::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn must_display_provenance() {
+        assert_eq!(Sourced::new(30, Default).to_string(), "30 (from default)");
+        assert_eq!(Sourced::new(30, File(PathBufDisplay::from(PathBuf::from("/etc/app.toml")))).to_string(), "30 (from file '/etc/app.toml')");
+        assert_eq!(Sourced::new("abc", EnvVar("APP_TIMEOUT".to_string())).to_string(), "abc (from env var APP_TIMEOUT)");
+        assert_eq!(Sourced::new("abc", CliFlag("--timeout".to_string())).to_string(), "abc (from CLI flag --timeout)");
+    }
+
+    #[test]
+    fn must_display_provenance_of_redacted_value() {
+        let token = Sourced::new(Redacted("hunter2".to_string()), EnvVar("APP_TOKEN".to_string()));
+        assert_eq!(token.to_string(), "[redacted] (from env var APP_TOKEN)");
+        assert!(!format!("{token:?}").contains("hunter2"));
+        assert_eq!(token.value.0, "hunter2");
+    }
+
+    #[test]
+    fn must_deref_to_value() {
+        let timeout = Sourced::new(30u64, Default);
+        assert_eq!(*timeout + 1, 31);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn must_serialize_value_only() {
+        let timeout = Sourced::new(30u64, EnvVar("APP_TIMEOUT".to_string()));
+        assert_eq!(serde_json::to_string(&timeout).unwrap(), "30");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn must_serialize_redacted_value_as_placeholder() {
+        let token = Sourced::new(Redacted("hunter2"), EnvVar("APP_TOKEN".to_string()));
+        assert_eq!(serde_json::to_string(&token).unwrap(), "\"[redacted]\"");
+    }
+}
@@ -0,0 +1,14 @@
+use crate::{AsDynError, ErrorStyle, error_parsed_tree};
+use core::fmt::{Display, Formatter};
+
+/// Renders the error trace with the custom markers (see [`ErrorDisplayer::with_style`]).
+pub struct StyledErrorDisplayer<'a, E: ?Sized> {
+    pub error: &'a E,
+    pub style: ErrorStyle,
+}
+
+impl<'a, E: AsDynError + ?Sized> Display for StyledErrorDisplayer<'a, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.style.write_tree(&error_parsed_tree(self.error), f)
+    }
+}
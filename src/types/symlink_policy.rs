@@ -0,0 +1,9 @@
+/// Determines how [`walk_dir_collect_with`](crate::walk_dir_collect_with) treats the symlinks.
+#[derive(Default, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum SymlinkPolicy {
+    /// Ignore the symlinks (both to the files and to the directories).
+    #[default]
+    Skip,
+    /// Resolve the symlinks: pass the targets that are files to the callback, recurse into the targets that are directories (each directory is visited at most once, so the cycles are not followed), and report the dangling symlinks as errors.
+    Follow,
+}
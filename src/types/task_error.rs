@@ -0,0 +1,29 @@
+use thiserror::Error;
+use tokio::task::JoinError;
+
+/// An error of a spawned task: either the task has panicked or has been cancelled (see [`JoinError`]), or the task has returned an error.
+///
+/// Use [`TaskError::from_join_result`] or [`handle_join!`](crate::handle_join) to flatten the `Result<Result<T, E>, JoinError>` that is returned by awaiting a [`JoinHandle`](tokio::task::JoinHandle).
+#[derive(Error, Debug)]
+pub enum TaskError<E> {
+    #[error("failed to join the task")]
+    JoinFailed { source: JoinError },
+    #[error("task failed")]
+    TaskFailed { source: E },
+}
+
+impl<E> TaskError<E> {
+    /// Flattens the two-layer result of a spawned task.
+    pub fn from_join_result<T>(result: Result<Result<T, E>, JoinError>) -> Result<T, Self> {
+        use TaskError::*;
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(source)) => Err(TaskFailed {
+                source,
+            }),
+            Err(source) => Err(JoinFailed {
+                source,
+            }),
+        }
+    }
+}
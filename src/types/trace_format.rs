@@ -0,0 +1,41 @@
+use crate::{AsDynError, writeln_error_concise, writeln_error_deduped, writeln_error_numbered, writeln_error_strict, writeln_error_to_writer, writeln_error_with_types};
+use std::io;
+use std::io::Write;
+
+/// The format of the error trace (see [`writeln_error_tee`](crate::writeln_error_tee)).
+#[derive(Default, Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum TraceFormat {
+    /// See [`writeln_error_to_writer`]
+    #[default]
+    Human,
+    /// See [`writeln_error_concise`]
+    Concise,
+    /// See [`writeln_error_deduped`]
+    Deduped,
+    /// See [`writeln_error_numbered`]
+    Numbered,
+    /// See [`writeln_error_with_types`]
+    WithTypes,
+    /// See [`writeln_error_strict`]
+    Strict,
+    /// See [`writeln_error_jsonl`](crate::writeln_error_jsonl)
+    #[cfg(feature = "json")]
+    Jsonl,
+}
+
+impl TraceFormat {
+    /// Writes the error trace in this format to the provided writer.
+    pub fn writeln<E: AsDynError + ?Sized>(&self, error: &E, writer: &mut dyn Write) -> io::Result<()> {
+        use TraceFormat::*;
+        match self {
+            Human => writeln_error_to_writer(error, writer),
+            Concise => writeln_error_concise(error, writer),
+            Deduped => writeln_error_deduped(error, writer),
+            Numbered => writeln_error_numbered(error, writer),
+            WithTypes => writeln_error_with_types(error, writer),
+            Strict => writeln_error_strict(error, writer),
+            #[cfg(feature = "json")]
+            Jsonl => crate::writeln_error_jsonl(error, writer),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::FmtPrefixer;
+use core::fmt::{Display, Formatter, Write};
+
+/// A structural summary of an error tree: the names of the errors and the nesting, without the messages.
+///
+/// Two trees that differ only in the message text have equal shapes, so the shapes can be compared in tests that must survive the changes in the wording of the dependency errors.
+/// The `Display` impl writes an outline in the same format as the error trace (with the names instead of the messages).
+/// See [`error_tree_shape`](crate::error_tree_shape).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct TreeShape {
+    /// The name of the error (see [`get_debug_name`](crate::get_debug_name))
+    pub name: String,
+    /// The label of the error (if it is a labeled child of an aggregate)
+    pub label: Option<String>,
+    /// The children of the aggregate (e.g. the errors in an [`ErrVec`](crate::ErrVec))
+    pub children: Vec<TreeShape>,
+    /// The shape of the source error
+    pub source: Option<Box<TreeShape>>,
+}
+
+impl Display for TreeShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "- [{label}] {}", self.name)?,
+            None => write!(f, "- {}", self.name)?,
+        }
+        self.children.iter().try_for_each(|child| {
+            f.write_str("\n  * ")?;
+            write!(FmtPrefixer::continuation(f, "    "), "{child}")
+        })?;
+        match &self.source {
+            Some(source) => write!(f, "\n{source}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Serializes the shape as a nested object (the empty fields are skipped to keep the expected shapes in tests short).
+#[cfg(feature = "serde")]
+impl serde::Serialize for TreeShape {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TreeShape", 4)?;
+        state.serialize_field("name", self.name.as_str())?;
+        match &self.label {
+            Some(label) => state.serialize_field("label", label.as_str())?,
+            None => state.skip_field("label")?,
+        }
+        if self.children.is_empty() {
+            state.skip_field("children")?
+        } else {
+            state.serialize_field("children", self.children.as_slice())?
+        }
+        match &self.source {
+            Some(source) => state.serialize_field("source", source.as_ref())?,
+            None => state.skip_field("source")?,
+        }
+        state.end()
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{ErrorChild, ErrorChildren, ErrorRegistry, FieldError, FieldPath, FieldSegment, Plural, write_error_children_to_formatter};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::ops::Deref;
+
+/// An aggregate of the [`FieldError`]s found during the validation of a nested structure.
+///
+/// The validators call [`with_segment`](Self::with_segment) when they descend into a field or an element, so the errors are pushed with the full path without manual bookkeeping.
+#[derive(Default, Debug)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+    path: FieldPath,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes the error at the current path.
+    pub fn push(&mut self, error: impl Into<Box<dyn Error + Send + Sync>>) {
+        self.errors.push(FieldError::new(self.path.clone(), error))
+    }
+
+    /// Pushes the error at the provided path (relative to the current path).
+    pub fn push_at(&mut self, path: impl Into<FieldPath>, error: impl Into<Box<dyn Error + Send + Sync>>) {
+        self.errors
+            .push(FieldError::new(self.path.join(&path.into()), error))
+    }
+
+    /// Calls `f` with the segment appended to the current path (the segment is removed after `f` returns).
+    pub fn with_segment<R>(&mut self, segment: impl Into<FieldSegment>, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.path.push(segment);
+        let output = f(self);
+        self.path.pop();
+        output
+    }
+
+    /// Returns `Ok(ok)` if there are no errors, or `Err(self)` otherwise.
+    pub fn into_result<T>(self, ok: T) -> Result<T, Self> {
+        if self.errors.is_empty() { Ok(ok) } else { Err(self) }
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_message(f)?;
+        write_error_children_to_formatter(self, f)
+    }
+}
+
+impl Error for ValidationErrors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_children::<Self>();
+        None
+    }
+}
+
+impl ErrorChildren for ValidationErrors {
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "found {errors}", errors = Plural::new(self.errors.len(), "invalid field"))
+    }
+
+    fn children(&self) -> Box<dyn Iterator<Item = ErrorChild<'_>> + '_> {
+        Box::new(self.errors.iter().map(|error| ErrorChild::new(error)))
+    }
+}
+
+impl Deref for ValidationErrors {
+    type Target = Vec<FieldError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.errors
+    }
+}
+
+impl From<ValidationErrors> for Vec<FieldError> {
+    fn from(val: ValidationErrors) -> Self {
+        val.errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrorDisplayer, ValidationErrors};
+    use ValueError::*;
+    use pretty_assertions::assert_eq;
+    use thiserror::Error;
+
+    #[test]
+    fn must_render_field_paths() {
+        let config = Config {
+            name: String::new(),
+            server: Server {
+                host: String::new(),
+                listeners: vec![
+                    Listener {
+                        port: 80,
+                    },
+                    Listener {
+                        port: 443,
+                    },
+                    Listener {
+                        port: 0,
+                    },
+                ],
+            },
+        };
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(ErrorDisplayer(&errors).to_string(), "- found 3 invalid fields\n  * - name: must not be empty\n  * - server.host: must not be empty\n  * - server.listeners[2].port: must be greater than 0");
+    }
+
+    #[test]
+    fn must_accept_valid_config() {
+        let config = Config {
+            name: "main".to_string(),
+            server: Server {
+                host: "localhost".to_string(),
+                listeners: vec![Listener {
+                    port: 80,
+                }],
+            },
+        };
+        assert!(validate_config(&config).is_ok());
+    }
+
+    fn validate_config(config: &Config) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if config.name.is_empty() {
+            errors.push_at("name", ValueEmpty);
+        }
+        errors.with_segment("server", |errors| validate_server(&config.server, errors));
+        errors.into_result(())
+    }
+
+    fn validate_server(server: &Server, errors: &mut ValidationErrors) {
+        if server.host.is_empty() {
+            errors.push_at("host", ValueEmpty);
+        }
+        errors.with_segment("listeners", |errors| {
+            server
+                .listeners
+                .iter()
+                .enumerate()
+                .for_each(|(index, listener)| errors.with_segment(index, |errors| validate_listener(listener, errors)))
+        });
+    }
+
+    fn validate_listener(listener: &Listener, errors: &mut ValidationErrors) {
+        if listener.port == 0 {
+            errors.with_segment("port", |errors| errors.push(ValueNotPositive));
+        }
+    }
+
+    struct Config {
+        name: String,
+        server: Server,
+    }
+
+    struct Server {
+        host: String,
+        listeners: Vec<Listener>,
+    }
+
+    struct Listener {
+        port: u16,
+    }
+
+    #[derive(Error, Debug)]
+    enum ValueError {
+        #[error("must not be empty")]
+        ValueEmpty,
+        #[error("must be greater than 0")]
+        ValueNotPositive,
+    }
+}
@@ -0,0 +1,12 @@
+/// The size of the payload (all fields) of an error enum variant.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct VariantSize {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The size of the variant fields in bytes (without the discriminant).
+    pub payload_size: usize,
+    /// The total size of the fields other than `source` in bytes (without the padding).
+    ///
+    /// The source is usually another error that has its own budget, so only the context fields (e.g. the path or the row) count against the budget of the variant.
+    pub non_source_size: usize,
+}
@@ -0,0 +1,45 @@
+/// Determines how much of the error trace is written to the terminal (see [`exit_result_verbosity`](crate::exit_result_verbosity)).
+///
+/// The full error report file is written regardless of the verbosity. The verbosity can be converted from the number of the `-v` flags.
+#[derive(Default, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+pub enum Verbosity {
+    /// The message of the top error and the messages of the root causes on a single line
+    Concise,
+    /// The full error trace
+    #[default]
+    Normal,
+    /// The full error trace with the names of the errors
+    Detailed,
+}
+
+impl From<u8> for Verbosity {
+    fn from(level: u8) -> Self {
+        match level {
+            0 => Self::Concise,
+            1 => Self::Normal,
+            _ => Self::Detailed,
+        }
+    }
+}
+
+impl Verbosity {
+    /// Parses the value of the [`VERBOSITY_ENV_VAR`](crate::VERBOSITY_ENV_VAR) environment variable.
+    ///
+    /// An empty value, `0`, `false`, `no` and `off` mean [`Concise`](Self::Concise), the other numbers are converted like the number of the `-v` flags, and any other value means [`Normal`](Self::Normal).
+    pub fn from_env_value(value: &str) -> Self {
+        let value = value.trim();
+        match value.parse::<u8>() {
+            Ok(level) => Self::from(level),
+            Err(_)
+                if value.is_empty()
+                    || ["false", "no", "off"]
+                        .iter()
+                        .any(|off| value.eq_ignore_ascii_case(off)) =>
+            {
+                Self::Concise
+            }
+            Err(_) if value.bytes().all(|byte| byte.is_ascii_digit()) => Self::Detailed,
+            Err(_) => Self::Normal,
+        }
+    }
+}
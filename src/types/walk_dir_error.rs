@@ -0,0 +1,82 @@
+use crate::{ErrorPath, ErrorRegistry, ItemError, PathBufDisplay, display_path};
+use WalkDirError::*;
+use core::fmt::{Display, Formatter};
+use std::error::Error;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An error that occurred for a single entry during [`walk_dir_collect`](crate::walk_dir_collect) (the walk continues after it).
+#[derive(Debug)]
+pub enum WalkDirError<E> {
+    ReadDirFailed { path: PathBuf, source: io::Error },
+    MetadataUnreadable { path: PathBuf, source: io::Error },
+    SymlinkUnresolvable { path: PathBuf, source: io::Error },
+    CallbackFailed { source: ItemError<PathBufDisplay, E> },
+}
+
+impl<E> Display for WalkDirError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ReadDirFailed {
+                path,
+                ..
+            } => write!(f, "failed to read the directory {path}", path = display_path(path)),
+            MetadataUnreadable {
+                path,
+                ..
+            } => write!(f, "failed to read the metadata of {path}", path = display_path(path)),
+            SymlinkUnresolvable {
+                path,
+                ..
+            } => write!(f, "failed to resolve the symlink {path}", path = display_path(path)),
+            CallbackFailed {
+                ..
+            } => f.write_str("failed to process the file"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for WalkDirError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        ErrorRegistry::register_path::<Self>();
+        match self {
+            ReadDirFailed {
+                source,
+                ..
+            }
+            | MetadataUnreadable {
+                source,
+                ..
+            }
+            | SymlinkUnresolvable {
+                source,
+                ..
+            } => Some(source),
+            CallbackFailed {
+                source,
+            } => Some(source),
+        }
+    }
+}
+
+impl<E> ErrorPath for WalkDirError<E> {
+    fn path(&self) -> Option<&Path> {
+        match self {
+            ReadDirFailed {
+                path,
+                ..
+            }
+            | MetadataUnreadable {
+                path,
+                ..
+            }
+            | SymlinkUnresolvable {
+                path,
+                ..
+            } => Some(path),
+            CallbackFailed {
+                ..
+            } => None,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::Severity;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// An error tagged with a [`Severity`].
+///
+/// The message of the error is prefixed with the severity marker (e.g. `[WARN] config key is deprecated`), so every renderer shows the severity.
+/// The message of the inner error is inlined into the message of this error, so [`source`](Error::source) returns the source of the inner error.
+/// Use `ErrVec<WithSeverity<E>>` for the collections of the errors with mixed severities.
+#[derive(Clone, Debug)]
+pub struct WithSeverity<E> {
+    pub severity: Severity,
+    pub error: E,
+}
+
+impl<E> WithSeverity<E> {
+    pub fn new(severity: Severity, error: E) -> Self {
+        Self {
+            severity,
+            error,
+        }
+    }
+
+    pub fn warning(error: E) -> Self {
+        Self::new(Severity::Warning, error)
+    }
+
+    pub fn error(error: E) -> Self {
+        Self::new(Severity::Error, error)
+    }
+
+    pub fn fatal(error: E) -> Self {
+        Self::new(Severity::Fatal, error)
+    }
+}
+
+impl<E: Display> Display for WithSeverity<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.severity, self.error)
+    }
+}
+
+impl<E: Error> Error for WithSeverity<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ErrVec, ErrorDisplayer, Severity, WithSeverity};
+    use pretty_assertions::assert_eq;
+    use std::io;
+
+    #[test]
+    fn must_render_mixed_severities() {
+        let errors = ErrVec::new([
+            WithSeverity::warning(io::Error::other("config key 'timeout' is deprecated")),
+            WithSeverity::error(io::Error::other("failed to connect to the cache")),
+            WithSeverity::fatal(io::Error::other("failed to open the database")),
+        ]);
+        assert_eq!(ErrorDisplayer(&errors).to_string(), "- encountered 3 errors\n  * - [WARN] config key 'timeout' is deprecated\n  * - [ERROR] failed to connect to the cache\n  * - [FATAL] failed to open the database");
+        assert_eq!(errors.iter().map(|error| error.severity).max(), Some(Severity::Fatal));
+    }
+}
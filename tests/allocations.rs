@@ -0,0 +1,168 @@
+//! Checks the number and the size of the allocations in the iterator macros and in the error report with a counting allocator.
+//!
+//! The allocations are counted per thread, so the tests can run in parallel.
+
+#![cfg(feature = "std")]
+
+use errgonomic::{ErrVec, handle_iter, handle_iter_unit, writeln_error_to_writer_and_file};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io;
+use std::io::Write;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static LARGEST_ALLOCATION: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record_allocation(size: usize) {
+    ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+    LARGEST_ALLOCATION.with(|largest| largest.set(largest.get().max(size)));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation(new_size);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let output = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    (output, after - before)
+}
+
+fn measure_largest_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = LARGEST_ALLOCATION.with(|largest| largest.replace(0));
+    let output = f();
+    let largest = LARGEST_ALLOCATION.with(|largest| largest.replace(before.max(largest.get())));
+    (output, largest)
+}
+
+fn parse_all(inputs: &[&str]) -> Result<Vec<u32>, ParseAllError> {
+    use ParseAllError::*;
+    Ok(handle_iter!(inputs.iter().map(|input| input.parse::<u32>()), ParseAllFailed))
+}
+
+fn check_all(inputs: &[&str]) -> Result<(), ParseAllError> {
+    use ParseAllError::*;
+    handle_iter_unit!(inputs.iter().map(|input| input.parse::<u32>().map(drop)), CheckAllFailed);
+    Ok(())
+}
+
+#[test]
+fn must_not_allocate_for_empty_input() {
+    let (result, allocations) = count_allocations(|| parse_all(&[]));
+    assert!(matches!(result, Ok(numbers) if numbers.is_empty()));
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn must_not_allocate_for_all_ok_units() {
+    let inputs = ["1"; 1000];
+    let (result, allocations) = count_allocations(|| check_all(&inputs));
+    assert!(result.is_ok());
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn must_allocate_only_errors_for_early_error() {
+    let mut inputs = ["1"; 1000];
+    inputs[0] = "x";
+    let (result, allocations) = count_allocations(|| check_all(&inputs));
+    assert!(matches!(result, Err(ParseAllError::CheckAllFailed { source }) if source.len() == 1));
+    // one allocation for the errors vec and one reallocation for shrinking it
+    assert!(allocations <= 2, "allocations: {allocations}");
+}
+
+#[test]
+fn must_cap_preallocation_for_early_error() {
+    let mut inputs = ["1"; 1000];
+    inputs[0] = "x";
+    let (result, allocations) = count_allocations(|| parse_all(&inputs));
+    assert!(result.is_err());
+    // the oks vec is pre-allocated (with a capped capacity) and then dropped, the errors vec is allocated and shrunk
+    assert!(allocations <= 3, "allocations: {allocations}");
+}
+
+#[test]
+fn must_stream_large_report_into_file() -> Result<(), Box<dyn std::error::Error>> {
+    let error = SyncAllFailed {
+        source: (0..1000)
+            .map(|index| SyncFailed {
+                source: io::Error::other(format!("replica {index} refused the connection: {}", "x".repeat(1000))),
+                index,
+            })
+            .collect::<Vec<_>>()
+            .into(),
+    };
+    let mut output = TailWriter(Vec::new());
+    let (result, largest) = measure_largest_allocation(|| writeln_error_to_writer_and_file(&error, &mut output));
+    result?;
+    let output = String::from_utf8(output.0)?;
+    let report_path = output
+        .lines()
+        .find_map(|line| line.strip_prefix("less "))
+        .ok_or("the report path must be written")?;
+    let report_len = std::fs::metadata(report_path)?.len();
+    std::fs::remove_file(report_path)?;
+    // the report would be allocated in full if it was rendered into a String
+    assert!(largest < usize::try_from(report_len)? / 8, "largest allocation: {largest}, report: {report_len}");
+    Ok(())
+}
+
+/// A writer that keeps only the tail of the output (so the trace itself doesn't need a large allocation)
+struct TailWriter(Vec<u8>);
+
+impl Write for TailWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        if self.0.len() > 4096 {
+            self.0.drain(..self.0.len() - 1024);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("failed to sync {len} replicas", len = source.len())]
+struct SyncAllFailed {
+    source: ErrVec<SyncFailed>,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to sync replica {index}")]
+struct SyncFailed {
+    source: io::Error,
+    index: usize,
+}
+
+#[derive(Error, Debug)]
+enum ParseAllError {
+    #[error("failed to parse {len} inputs", len = source.len())]
+    ParseAllFailed { source: ErrVec<ParseIntError> },
+    #[error("failed to check {len} inputs", len = source.len())]
+    CheckAllFailed { source: ErrVec<ParseIntError> },
+}
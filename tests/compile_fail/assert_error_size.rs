@@ -0,0 +1,12 @@
+use errgonomic::assert_error_size;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("failed to load {name}")]
+    LoadFailed { name: String, attempts: [u64; 8] },
+}
+
+assert_error_size!(LoadError, 32);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use errgonomic::error_size_report;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("failed to load {name}")]
+    LoadFailed { source: io::Error, name: String },
+    #[error("config not found")]
+    NotFound,
+}
+
+fn main() {
+    let _ = error_size_report!(LoadError, {
+        LoadFailed { source: io::Error, name: u64 },
+    });
+}
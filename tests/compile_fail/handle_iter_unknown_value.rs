@@ -0,0 +1,18 @@
+use errgonomic::{ErrVec, handle_iter};
+use thiserror::Error;
+
+fn parse_all(inputs: Vec<&str>) -> Result<Vec<u32>, ParseAllError> {
+    use ParseAllError::*;
+    let results = inputs.into_iter().map(|input| input.parse::<u32>());
+    Ok(handle_iter!(results, ParseAllFailed, total: @total))
+}
+
+#[derive(Error, Debug)]
+enum ParseAllError {
+    #[error("failed to parse {len} of {total} inputs", len = source.len())]
+    ParseAllFailed { source: ErrVec<std::num::ParseIntError>, total: usize },
+}
+
+fn main() {
+    let _ = parse_all(vec!["1"]);
+}
@@ -0,0 +1,7 @@
+use errgonomic::MustFinalize;
+
+fn main() {
+    let guard = MustFinalize::new(Vec::<u8>::new());
+    let _ = guard.finalize(Ok::<_, std::io::Error>);
+    let _ = guard.finalize(Ok::<_, std::io::Error>);
+}
@@ -0,0 +1,9 @@
+#![deny(unused_must_use)]
+
+use errgonomic::{exit_iterator_of_results_print_first, exit_result};
+use std::io;
+
+fn main() {
+    exit_result(Ok::<(), io::Error>(()));
+    exit_iterator_of_results_print_first([Ok::<(), io::Error>(())]);
+}
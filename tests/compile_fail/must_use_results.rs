@@ -0,0 +1,9 @@
+#![deny(unused_must_use)]
+
+use errgonomic::{partition_result, writeln_error_to_writer};
+use std::io;
+
+fn main() {
+    partition_result(["1", "x"].map(|input| input.parse::<u32>()));
+    writeln_error_to_writer(&io::Error::other("failed"), &mut io::sink());
+}
@@ -0,0 +1,638 @@
+//! Checks that the macro expansions don't depend on the prelude or on the names that are in scope at the call site.
+
+#![cfg(feature = "std")]
+
+use errgonomic::{ErrVec, ItemError};
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[no_implicit_prelude]
+mod hygiene {
+    use ::errgonomic::{box_err, convert, convert_vec, finish, handle, handle_bool, handle_bool_static, handle_discard, handle_fi, handle_finalize, handle_from, handle_into_iter, handle_io, handle_iter, handle_iter_dedup, handle_iter_first, handle_iter_flat, handle_iter_non_empty, handle_iter_of_refs, handle_iter_unit, handle_loc, handle_map_source, handle_maybe, handle_opt, handle_opt_flat, handle_opt_take, handle_opt_with, handle_os_var, handle_output, handle_push, handle_recv, handle_resumable, handle_send, handle_seq, handle_status, handle_unique, handle_validate, handle_walk, map_err, map_err_discard, static_errors, try_from_handle, try_from_impl, try_handle, try_handle_bool, try_handle_opt, validate, wrap};
+    use ::std::num::ParseIntError;
+    use ::std::option::Option;
+    use ::std::result::Result;
+    use ::std::string::String;
+    use ::std::vec::Vec;
+
+    pub fn parse(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = handle!(input.parse::<u32>(), ParseFailed, input: ::std::string::ToString::to_string(input));
+        handle_bool!(number == 0, NumberIsZero);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_boxed(input: &str) -> Result<u32, ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>> {
+        let number = box_err!(input.parse::<u32>(), "failed to parse '{}'", input);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_cause(input: &str) -> Result<u32, super::LegacyError> {
+        use super::LegacyError::*;
+        let number = handle!(input.parse::<u32>(), ParseFailed @ cause);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_map_source(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = handle_map_source!(input.parse::<u32>(), ParseMaybeFailed, ::std::option::Option::Some);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_from(input: &str) -> Result<u32, super::ParseFromError> {
+        let number = handle_from!(input.parse::<u32>());
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_discard(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = handle_discard!(input.parse::<u32>(), ParseDiscarded);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_map_err(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        map_err!(input.parse::<u32>(), ParseFailed, input: ::std::string::ToString::to_string(input))
+    }
+
+    pub fn parse_located(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = handle_loc!(input.parse::<u32>(), ParseLocatedFailed, input: ::std::string::ToString::to_string(input));
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_fault_injected(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = handle_fi!("parse", input.parse::<u32>(), ParseFailed, input: ::std::string::ToString::to_string(input));
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_finish(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        finish!(input.parse::<u32>(), ParseFailed, input: ::std::string::ToString::to_string(input))
+    }
+
+    pub fn parse_map_err_discard(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        map_err_discard!(input.parse::<u32>(), ParseDiscarded)
+    }
+
+    pub fn parse_maybe(result: Result<u32, Option<ParseIntError>>) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = handle_maybe!(result, ParseMaybeFailed);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_wrap(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let number = input
+            .parse::<u32>()
+            .map_err(wrap!(ParseFailed, input: ::std::string::ToString::to_string(input)))?;
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn read_var(name: &str) -> Result<String, super::VarError> {
+        use super::VarError::*;
+        let value = handle_os_var!(name, VarReadFailed);
+        ::std::result::Result::Ok(value)
+    }
+
+    pub fn send_and_recv(number: u32, is_closed: bool) -> Result<u32, super::ChannelError> {
+        use super::ChannelError::*;
+        let (tx, rx) = ::std::sync::mpsc::channel();
+        if is_closed {
+            ::std::mem::drop(rx);
+            handle_send!(tx.send(number), NumberSendFailed);
+            ::std::result::Result::Ok(0)
+        } else {
+            handle_send!(tx.send(number), NumberSendFailed);
+            let number = handle_recv!(rx.try_recv(), ChannelEmpty, ChannelDisconnected);
+            ::std::result::Result::Ok(number)
+        }
+    }
+
+    pub fn read(path: ::std::path::PathBuf) -> Result<String, super::ReadError> {
+        use super::ReadError::*;
+        let contents = handle_io!(::std::fs::read_to_string(&path), ReadFailed, path);
+        ::std::result::Result::Ok(contents)
+    }
+
+    pub fn parse_resumable(input: &str, index: usize) -> Result<u32, super::ResumableError> {
+        use super::ResumableError::*;
+        let number = handle_resumable!(input.parse::<u32>(), NumberParseFailed, index, input: input);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn parse_each(inputs: Vec<&str>) -> Result<Vec<u32>, ::errgonomic::ErrVec<::std::num::ParseIntError>> {
+        let mut numbers = ::std::vec::Vec::new();
+        let mut errors = ::errgonomic::ErrVec::new([]);
+        for input in inputs {
+            let number = handle_push!(input.parse::<u32>(), &mut errors);
+            numbers.push(number);
+        }
+        errors.into_result(numbers)
+    }
+
+    pub fn index(pairs: Vec<(u32, &'static str)>) -> Result<::std::collections::HashMap<u32, &'static str>, super::IndexError> {
+        use super::IndexError::*;
+        let index = handle_unique!(pairs, KeysDuplicated);
+        ::std::result::Result::Ok(index)
+    }
+
+    pub fn parse_seq(input: &str) -> Result<u32, super::SeqError> {
+        use super::SeqError::*;
+        let number = handle_seq!(input.parse::<u32>(), NumberParseFailed, input: input);
+        ::std::result::Result::Ok(number)
+    }
+
+    pub fn read_all(root: ::std::path::PathBuf) -> Result<Vec<String>, super::ReadError> {
+        use super::ReadError::*;
+        let contents = handle_walk!(&root, |path| ::std::fs::read_to_string(path), WalkFailed, root);
+        ::std::result::Result::Ok(contents)
+    }
+
+    pub fn first(numbers: Vec<u32>) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let first = handle_opt!(numbers.first(), NumbersEmpty);
+        ::std::result::Result::Ok(*first)
+    }
+
+    pub fn first_with(numbers: Vec<u32>) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let first = handle_opt_with!(numbers.first(), &numbers, NumbersEmpty, |_numbers| {});
+        ::std::result::Result::Ok(*first)
+    }
+
+    pub fn first_flat(numbers: Vec<Option<u32>>) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let first = handle_opt_flat!(numbers.first().copied(), NumbersEmpty);
+        ::std::result::Result::Ok(first)
+    }
+
+    pub fn take_warning(mut warning: Option<String>) -> Result<(), super::HygieneError> {
+        use super::HygieneError::*;
+        handle_opt_take!(warning, WarningPresent, warning);
+        ::std::result::Result::Ok(())
+    }
+
+    pub fn parse_all(inputs: Vec<&str>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter!(results, ParseAllFailed))
+    }
+
+    pub fn parse_all_with_context(inputs: Vec<&str>, tenant: &str) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter!(results, ParseAllWithContextFailed, context: { tenant: tenant }))
+    }
+
+    pub fn parse_all_first(inputs: Vec<&str>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter_first!(results, ParseAllFailed))
+    }
+
+    pub fn parse_all_labeled(inputs: Vec<&str>, label: &str) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter!(results, ParseAllLabeledFailed, label: label.to_uppercase()))
+    }
+
+    pub fn parse_all_labeled_with_context(inputs: Vec<&str>, label: &str) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter!(results, ParseAllWithContextFailed, context: { label: label.to_uppercase() }))
+    }
+
+    pub fn parse_all_counted(inputs: Vec<&str>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter!(results, ParseAllCountedFailed, total: @count))
+    }
+
+    pub fn parse_all_non_empty(inputs: Vec<&str>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter_non_empty!(results, ParseAllFailed, NumbersEmpty))
+    }
+
+    pub fn parse_all_dedup(inputs: Vec<&str>) -> Result<Vec<u32>, super::DedupError> {
+        use super::DedupError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>());
+        ::std::result::Result::Ok(handle_iter_dedup!(results, ParseAllFailed))
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn parse_joined(result: Result<Result<u32, ParseIntError>, ::tokio::task::JoinError>) -> Result<u32, super::JoinError> {
+        use super::JoinError::*;
+        ::std::result::Result::Ok(::errgonomic::handle_join!(result, TaskFailed))
+    }
+
+    #[cfg(feature = "anyhow")]
+    pub fn parse_anyhow(result: ::anyhow::Result<u32>) -> Result<u32, super::AnyhowParseError> {
+        use super::AnyhowParseError::*;
+        ::std::result::Result::Ok(::errgonomic::handle_anyhow!(result, AnyhowParseFailed, input: ::std::string::ToString::to_string("x")))
+    }
+
+    pub fn finalize(input: &str) -> Result<u32, super::HygieneError> {
+        use super::HygieneError::*;
+        let guard = ::errgonomic::MustFinalize::new(::std::string::ToString::to_string(input));
+        ::std::result::Result::Ok(handle_finalize!(guard, |input: String| input.parse::<u32>(), ParseFailed, input: ::std::string::ToString::to_string(input)))
+    }
+
+    static_errors! {
+        pub InputEmpty, INPUT_EMPTY, "the input is empty";
+    }
+
+    pub fn parse_non_empty(input: &str) -> Result<(), super::StaticError> {
+        use super::StaticError::*;
+        handle_bool_static!(input.is_empty(), &INPUT_EMPTY, InputInvalid, input: ::std::string::ToString::to_string(input));
+        ::std::result::Result::Ok(())
+    }
+
+    pub fn validate_inputs(inputs: Vec<&str>) -> Result<(), super::ValidateInputsError> {
+        use super::ValidateInputsError::*;
+        handle_validate!(inputs, InputsInvalid, {
+            first => check_non_empty(inputs),
+            inputs[i] for (i, input) in ::std::iter::Iterator::enumerate(::std::iter::IntoIterator::into_iter(inputs)) => input.parse::<u32>(),
+        });
+        ::std::result::Result::Ok(())
+    }
+
+    fn check_non_empty(inputs: &[&str]) -> Result<(), &'static str> {
+        if inputs.is_empty() {
+            ::std::result::Result::Err("no inputs")
+        } else {
+            ::std::result::Result::Ok(())
+        }
+    }
+
+    pub fn parse_chunks(chunks: Vec<Vec<&str>>) -> Result<Vec<Vec<u32>>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(chunks), |chunk| ::std::result::Result::map_err(::errgonomic::partition_result(::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(chunk), |input| input.parse::<u32>())), ::errgonomic::ErrVec::new));
+        ::std::result::Result::Ok(handle_iter_flat!(results, ParseAllFailed))
+    }
+
+    pub fn check_all(inputs: Vec<&str>) -> Result<(), super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| ::std::result::Result::map(input.parse::<u32>(), ::std::mem::drop));
+        handle_iter_unit!(results, ParseAllFailed);
+        ::std::result::Result::Ok(())
+    }
+
+    pub fn parse_all_try(inputs: Vec<&str>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| {
+            let number = try_handle!(input.parse::<u32>(), ParseFailed, input: ::std::string::ToString::to_string(input))?;
+            try_handle_bool!(number == 0, NumberIsZero)?;
+            try_handle_opt!(::std::option::Option::Some(number), NumbersEmpty)
+        });
+        ::std::result::Result::Ok(handle_iter!(results, ParseAllTryFailed))
+    }
+
+    pub fn parse_all_into_iter(inputs: Vec<&str>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results: Vec<_> = ::std::iter::Iterator::collect(::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(inputs), |input| input.parse::<u32>()));
+        ::std::result::Result::Ok(handle_into_iter!(results, ParseAllFailed))
+    }
+
+    pub fn parse_all_of_refs(inputs: Vec<String>) -> Result<Vec<u32>, super::HygieneError> {
+        use super::HygieneError::*;
+        let results = ::std::iter::Iterator::map(inputs.iter(), |input| input.parse::<u32>());
+        let (outputs, _inputs) = handle_iter_of_refs!(results, ::std::iter::IntoIterator::into_iter(::std::clone::Clone::clone(&inputs)), ParseAllOfRefsFailed);
+        ::std::result::Result::Ok(outputs)
+    }
+
+    pub fn validate_range(start: u32, end: u32) -> Result<(), super::ValidateRangeError> {
+        use super::ValidateRangeError;
+        validate!(
+            ValidateRangeError,
+            [
+                (start <= end, StartAfterEnd, start, end),
+                (end < 100, EndTooHigh, end)
+            ]
+        );
+        ::std::result::Result::Ok(())
+    }
+
+    #[cfg(unix)]
+    pub fn check_status(code: i32) -> Result<(), super::StatusError> {
+        use super::StatusError::*;
+        let status: ::std::process::ExitStatus = ::std::os::unix::process::ExitStatusExt::from_raw(code << 8);
+        handle_status!(status, StatusInvalid, name: ::std::string::ToString::to_string("check"));
+        let output = ::std::process::Output {
+            status,
+            stdout: Vec::new(),
+            stderr: ::std::vec![b'x'],
+        };
+        let _output = handle_output!(output, OutputInvalid);
+        ::std::result::Result::Ok(())
+    }
+
+    pub struct RawNumbers(pub Vec<String>);
+
+    pub struct Numbers(pub Vec<u32>);
+
+    try_from_impl!(RawNumbers, Numbers, ConvertRawNumbersToNumbersError, |RawNumbers(inputs)| {
+        let numbers = convert_vec!(inputs, |input: String| input.parse::<u32>(), InputsParseFailed);
+        let first = convert!(::std::result::Result::Ok::<_, ParseIntError>(0), FirstParseFailed);
+        ::std::result::Result::Ok(Numbers(::std::iter::Iterator::collect(::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(numbers), |number| number + first))))
+    });
+
+    #[derive(Debug)]
+    pub struct RawNumber(pub String);
+
+    pub struct Number(pub u32);
+
+    try_from_impl!(RawNumber, Number, ConvertRawNumberToNumberError, |raw| {
+        let number = try_from_handle!(raw, |raw: &RawNumber| raw.0.parse::<u32>(), InputParseFailed);
+        ::std::result::Result::Ok(Number(number))
+    });
+
+    #[derive(::thiserror::Error, Debug)]
+    pub enum ConvertRawNumberToNumberError {
+        #[error("failed to parse '{}'", raw.0)]
+        InputParseFailed { source: ParseIntError, raw: RawNumber },
+    }
+
+    #[derive(::thiserror::Error, Debug)]
+    pub enum ConvertRawNumbersToNumbersError {
+        #[error("failed to parse inputs")]
+        InputsParseFailed { source: ::errgonomic::ErrVec<::errgonomic::ItemError<usize, ParseIntError>> },
+        #[error("failed to parse first")]
+        FirstParseFailed { source: ParseIntError },
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum HygieneError {
+    #[error("failed to parse '{input}'")]
+    ParseFailed { source: ParseIntError, input: String },
+    #[error("failed to parse '{input}' at {location}")]
+    ParseLocatedFailed { source: ParseIntError, input: String, location: &'static str },
+    #[error("failed to parse")]
+    ParseDiscarded,
+    #[error("failed to parse")]
+    ParseMaybeFailed { source: Option<ParseIntError> },
+    #[error("number is zero")]
+    NumberIsZero,
+    #[error("numbers are empty")]
+    NumbersEmpty,
+    #[error("warning is present: {warning}")]
+    WarningPresent { warning: String },
+    #[error("failed to parse {len} inputs", len = source.len())]
+    ParseAllFailed { source: ErrVec<ParseIntError> },
+    #[error("failed to parse {len} inputs", len = source.len())]
+    ParseAllTryFailed { source: ErrVec<HygieneError> },
+    #[error("failed to parse {len} inputs", len = source.len())]
+    ParseAllOfRefsFailed { source: ErrVec<ItemError<String, ParseIntError>> },
+    #[error("failed to parse inputs")]
+    ParseAllWithContextFailed { source: errgonomic::ErrVecWithContext<ParseIntError> },
+    #[error("failed to parse {label}")]
+    ParseAllLabeledFailed { source: ErrVec<ParseIntError>, label: String },
+    #[error("failed to parse {len} of {total} inputs", len = source.len())]
+    ParseAllCountedFailed { source: ErrVec<ParseIntError>, total: usize },
+}
+
+#[derive(Error, Debug)]
+pub enum StatusError {
+    #[error("command '{name}' failed")]
+    StatusInvalid { code: Option<i32>, signal: Option<i32>, name: String },
+    #[error("command failed: {stderr}")]
+    OutputInvalid { code: Option<i32>, signal: Option<i32>, stderr: String },
+}
+
+#[derive(Error, Debug)]
+pub enum VarError {
+    #[error("failed to read environment variable '{name}'")]
+    VarReadFailed { name: String, value: Option<errgonomic::OsStrDisplay> },
+}
+
+#[derive(Error, Debug)]
+pub enum ChannelError {
+    #[error("failed to send {message}")]
+    NumberSendFailed { source: errgonomic::ChannelClosed, message: u32 },
+    #[error("channel is empty")]
+    ChannelEmpty,
+    #[error("channel is disconnected")]
+    ChannelDisconnected,
+}
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+    #[error("failed to read")]
+    ReadFailed { source: errgonomic::PathIoError },
+    #[error("failed to read the files in '{root}'", root = root.display())]
+    WalkFailed { source: errgonomic::ErrVec<errgonomic::WalkDirError<std::io::Error>>, root: std::path::PathBuf },
+}
+
+#[derive(Error, Debug)]
+pub enum ResumableError {
+    #[error("failed to parse '{input}'")]
+    NumberParseFailed { source: errgonomic::Resumable<std::num::ParseIntError, usize>, input: String },
+}
+
+#[derive(Error, Debug)]
+pub enum IndexError {
+    #[error("failed to build the index")]
+    KeysDuplicated { source: errgonomic::DuplicateKeysError<u32, &'static str> },
+}
+
+#[derive(Error, Debug)]
+pub enum DedupError {
+    #[error("failed to parse all numbers")]
+    ParseAllFailed { source: ErrVec<errgonomic::Deduped<ParseIntError>> },
+}
+
+#[cfg(feature = "tokio")]
+#[derive(Error, Debug)]
+pub enum JoinError {
+    #[error("task failed")]
+    TaskFailed { source: errgonomic::TaskError<ParseIntError> },
+}
+
+#[cfg(feature = "anyhow")]
+#[derive(Error, Debug)]
+pub enum AnyhowParseError {
+    #[error("failed to parse '{input}'")]
+    AnyhowParseFailed { source: errgonomic::AnyhowError, input: String },
+}
+
+#[derive(Error, Debug)]
+pub enum StaticError {
+    #[error("invalid input '{input}'")]
+    InputInvalid { source: &'static hygiene::InputEmpty, input: String },
+}
+
+#[derive(Error, Debug)]
+pub enum ValidateInputsError {
+    #[error("invalid inputs")]
+    InputsInvalid { source: errgonomic::ValidationErrors },
+}
+
+#[derive(Error, Debug)]
+pub enum SeqError {
+    #[error("failed to parse '{input}'")]
+    NumberParseFailed { source: std::num::ParseIntError, seq: u64, input: String },
+}
+
+#[derive(Error, Debug)]
+pub enum LegacyError {
+    #[error("failed to parse")]
+    ParseFailed {
+        #[source]
+        cause: ParseIntError,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum ParseFromError {
+    #[error("failed to parse")]
+    ParseFailed {
+        #[from]
+        source: ParseIntError,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum ValidateRangeError {
+    #[error("start is after end: {start} > {end}")]
+    StartAfterEnd { start: u32, end: u32 },
+    #[error("end is too high: {end}")]
+    EndTooHigh { end: u32 },
+    #[error("range is invalid")]
+    Multiple { source: ErrVec<ValidateRangeError> },
+}
+
+#[test]
+fn must_expand_without_prelude() {
+    use HygieneError::*;
+    assert!(matches!(hygiene::parse("42"), Ok(42)));
+    assert!(matches!(hygiene::parse("x"), Err(ParseFailed { .. })));
+    assert!(matches!(hygiene::parse("0"), Err(NumberIsZero)));
+    assert!(matches!(hygiene::parse_fault_injected("42"), Ok(42)));
+    assert!(matches!(hygiene::parse_fault_injected("x"), Err(ParseFailed { .. })));
+    assert!(matches!(hygiene::parse_discard("x"), Err(ParseDiscarded)));
+    assert!(matches!(hygiene::parse_map_err("x"), Err(ParseFailed { .. })));
+    assert!(matches!(hygiene::parse_map_err_discard("x"), Err(ParseDiscarded)));
+    assert!(matches!(hygiene::parse_finish("x"), Err(ParseFailed { .. })));
+    assert!(matches!(hygiene::parse_located("x"), Err(ParseLocatedFailed { location, .. }) if location.starts_with("tests/macro_hygiene.rs:")));
+    assert!(matches!(
+        hygiene::parse_maybe(Err(None)),
+        Err(ParseMaybeFailed {
+            source: None
+        })
+    ));
+    assert!(matches!(hygiene::parse_wrap("x"), Err(ParseFailed { .. })));
+    assert!(matches!(
+        hygiene::parse_map_source("x"),
+        Err(ParseMaybeFailed {
+            source: Some(_)
+        })
+    ));
+    assert!(matches!(hygiene::parse_from("x"), Err(ParseFromError::ParseFailed { .. })));
+    assert!(matches!(hygiene::first(vec![]), Err(NumbersEmpty)));
+    assert!(matches!(hygiene::first_flat(vec![None]), Err(NumbersEmpty)));
+    assert!(matches!(hygiene::first_with(vec![]), Err(NumbersEmpty)));
+    assert!(matches!(hygiene::first_flat(vec![Some(7)]), Ok(7)));
+    assert!(matches!(hygiene::take_warning(Some("deprecated".to_string())), Err(WarningPresent { .. })));
+    assert!(matches!(hygiene::parse_all(vec!["1", "x", "y"]), Err(ParseAllFailed { source }) if source.len() == 2));
+    assert!(matches!(hygiene::parse_all_first(vec!["1", "x", "y"]), Err(ParseAllFailed { source }) if source.len() == 1));
+    assert!(matches!(hygiene::parse_all_with_context(vec!["x"], "acme"), Err(ParseAllWithContextFailed { source }) if source.context.len() == 1));
+    assert!(matches!(hygiene::parse_all_labeled(vec!["x"], "ports"), Err(ParseAllLabeledFailed { label, .. }) if label == "PORTS"));
+    assert!(matches!(hygiene::parse_all_labeled_with_context(vec!["x"], "ports"), Err(ParseAllWithContextFailed { source }) if source.context[0].1 == "PORTS"));
+    assert!(matches!(hygiene::parse_all_counted(vec!["1", "x", "y"]), Err(ParseAllCountedFailed { source, total: 3 }) if source.len() == 2));
+    assert!(matches!(hygiene::parse_all_non_empty(vec![]), Err(NumbersEmpty)));
+    assert!(matches!(hygiene::parse_all_dedup(vec!["x", "1", "y"]), Err(DedupError::ParseAllFailed { source }) if source.len() == 1 && source[0].count == 2));
+    assert!(matches!(hygiene::parse_chunks(vec![vec!["x", "1"], vec!["y"]]), Err(ParseAllFailed { source }) if source.len() == 2));
+    assert!(matches!(hygiene::parse_all_non_empty(vec!["1"]), Ok(numbers) if numbers == vec![1]));
+    assert!(matches!(hygiene::check_all(vec!["1", "x"]), Err(ParseAllFailed { source }) if source.len() == 1));
+    assert!(matches!(hygiene::parse_all_try(vec!["1", "x", "0"]), Err(ParseAllTryFailed { source }) if source.len() == 2));
+    assert!(matches!(hygiene::parse_all_into_iter(vec!["1", "2"]), Ok(numbers) if numbers == vec![1, 2]));
+    assert!(matches!(hygiene::parse_all_of_refs(vec!["1".to_string(), "x".to_string()]), Err(ParseAllOfRefsFailed { source }) if source.len() == 1));
+    assert!(matches!(hygiene::Numbers::try_from(hygiene::RawNumbers(vec!["1".to_string(), "x".to_string()])), Err(hygiene::ConvertRawNumbersToNumbersError::InputsParseFailed { source }) if source.len() == 1));
+    assert!(matches!(hygiene::Numbers::try_from(hygiene::RawNumbers(vec!["1".to_string(), "2".to_string()])), Ok(hygiene::Numbers(numbers)) if numbers == vec![1, 2]));
+    assert!(matches!(hygiene::Number::try_from(hygiene::RawNumber("x".to_string())), Err(hygiene::ConvertRawNumberToNumberError::InputParseFailed { raw: hygiene::RawNumber(input), .. }) if input == "x"));
+    assert!(matches!(hygiene::Number::try_from(hygiene::RawNumber("7".to_string())), Ok(hygiene::Number(7))));
+    #[cfg(unix)]
+    {
+        assert!(matches!(hygiene::check_status(0), Ok(())));
+        assert!(matches!(
+            hygiene::check_status(1),
+            Err(StatusError::StatusInvalid {
+                code: Some(1),
+                ..
+            })
+        ));
+    }
+    assert!(matches!(hygiene::parse_boxed("x"), Err(error) if error.to_string() == "failed to parse 'x'"));
+    assert!(matches!(
+        hygiene::read_var("ERRGONOMIC_HYGIENE_UNSET"),
+        Err(VarError::VarReadFailed {
+            value: None,
+            ..
+        })
+    ));
+    assert!(matches!(hygiene::send_and_recv(5, false), Ok(5)));
+    assert!(matches!(
+        hygiene::send_and_recv(5, true),
+        Err(ChannelError::NumberSendFailed {
+            message: 5,
+            ..
+        })
+    ));
+    assert!(matches!(hygiene::read("/nonexistent/errgonomic".into()), Err(ReadError::ReadFailed { source }) if source.path.ends_with("errgonomic")));
+    assert!(matches!(hygiene::read_all("/nonexistent/errgonomic".into()), Err(ReadError::WalkFailed { source, .. }) if source.len() == 1));
+    assert!(matches!(hygiene::parse_resumable("x", 3), Err(ResumableError::NumberParseFailed { source, .. }) if source.resume_state == 3));
+    assert!(matches!(hygiene::parse_each(vec!["x", "1", "y"]), Err(errors) if errors.len() == 2));
+    assert!(matches!(hygiene::index(vec![(1, "a"), (1, "b")]), Err(IndexError::KeysDuplicated { source }) if source.duplicates.len() == 1));
+    assert!(matches!(hygiene::parse_seq("x"), Err(SeqError::NumberParseFailed { .. })));
+    assert!(matches!(hygiene::parse_cause("x"), Err(LegacyError::ParseFailed { .. })));
+    assert!(matches!(hygiene::validate_range(200, 150), Err(ValidateRangeError::Multiple { source }) if source.len() == 2));
+    #[cfg(feature = "tokio")]
+    assert!(matches!(
+        hygiene::parse_joined(Ok("x".parse())),
+        Err(JoinError::TaskFailed {
+            source: errgonomic::TaskError::TaskFailed { .. }
+        })
+    ));
+    #[cfg(feature = "anyhow")]
+    assert!(matches!(hygiene::parse_anyhow(Err(::anyhow::anyhow!("invalid digit"))), Err(AnyhowParseError::AnyhowParseFailed { source, .. }) if source.to_string() == "invalid digit"));
+    assert!(matches!(hygiene::finalize("x"), Err(ParseFailed { .. })));
+    assert!(matches!(hygiene::parse_non_empty(""), Err(StaticError::InputInvalid { source, .. }) if source.to_string() == "the input is empty"));
+    assert!(matches!(hygiene::validate_inputs(vec!["1", "x"]), Err(ValidateInputsError::InputsInvalid { source }) if source.len() == 1 && source[0].path.to_string() == "inputs[1]"));
+}
+
+/// The variants of this enum shadow the prelude `Ok` / `Err` / `Some` / `None` in the `shadowing` module
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Outcome {
+    Ok,
+    Err,
+    Some,
+    None,
+}
+
+mod shadowing {
+    use super::HygieneError;
+    use super::Outcome::{Err, None, Ok, Some};
+    use errgonomic::{handle, handle_iter, handle_opt};
+
+    pub fn parse_first(inputs: Vec<&str>) -> Result<Vec<u32>, HygieneError> {
+        use HygieneError::*;
+        let first = handle_opt!(inputs.first(), NumbersEmpty);
+        let _number = handle!(first.parse::<u32>(), ParseFailed, input: first.to_string());
+        let numbers = handle_iter!(inputs.iter().map(|input| input.parse::<u32>()), ParseAllFailed);
+        let _outcomes = [Ok, Err, Some, None];
+        Result::Ok(numbers)
+    }
+}
+
+#[test]
+fn must_expand_with_shadowed_prelude_variants() {
+    assert!(matches!(shadowing::parse_first(vec!["1", "2"]), Result::Ok(numbers) if numbers == vec![1, 2]));
+    assert!(matches!(shadowing::parse_first(vec![]), Result::Err(HygieneError::NumbersEmpty)));
+    assert!(matches!(shadowing::parse_first(vec!["x"]), Result::Err(HygieneError::ParseFailed { .. })));
+    assert!(matches!(shadowing::parse_first(vec!["1", "x"]), Result::Err(HygieneError::ParseAllFailed { source }) if source.len() == 1));
+}